@@ -0,0 +1,78 @@
+use std::io::Cursor;
+use std::io::SeekFrom;
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use ris_math::vector::Vec3;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+
+fn write_vec3s(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_vec3s");
+
+    let mut rng = Rng::new(Seed::new().unwrap());
+    let count = 1 << 16;
+    let values = (0..count).map(|_| rng.next_pos_3()).collect::<Vec<_>>();
+
+    group.bench_function("element wise", |b| {
+        b.iter(|| {
+            let mut stream = Cursor::new(Vec::with_capacity(count * 12));
+            for value in values.iter() {
+                ris_io::write_vec3(&mut stream, *value).unwrap();
+            }
+
+            black_box(stream);
+        })
+    });
+
+    group.bench_function("write_slice", |b| {
+        b.iter(|| {
+            let mut stream = Cursor::new(Vec::with_capacity(count * 12));
+            ris_io::write_slice(&mut stream, &values).unwrap();
+
+            black_box(stream);
+        })
+    });
+
+    group.finish();
+}
+
+fn read_vec3s(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_vec3s");
+
+    let mut rng = Rng::new(Seed::new().unwrap());
+    let count = 1 << 16;
+    let values = (0..count).map(|_| rng.next_pos_3()).collect::<Vec<_>>();
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_slice(&mut stream, &values).unwrap();
+
+    group.bench_function("element wise", |b| {
+        b.iter(|| {
+            ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+            let mut result = Vec::with_capacity(count);
+            for _ in 0..count {
+                result.push(ris_io::read_vec3(&mut stream).unwrap());
+            }
+
+            black_box(result);
+        })
+    });
+
+    group.bench_function("read_slice", |b| {
+        b.iter(|| {
+            ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+            let mut result = vec![Vec3::default(); count];
+            ris_io::read_slice(&mut stream, &mut result).unwrap();
+
+            black_box(result);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, write_vec3s, read_vec3s);
+criterion_main!(benches);
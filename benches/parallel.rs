@@ -0,0 +1,81 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use ris_jobs::job_system;
+use ris_jobs::parallel;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+
+fn parallel_for(c: &mut Criterion) {
+    let cpu_count = std::thread::available_parallelism().map_or(1, |x| x.get());
+    let job_system = job_system::init(
+        job_system::DEFAULT_BUFFER_CAPACITY,
+        cpu_count,
+        cpu_count,
+        false,
+    );
+
+    for count in [1_000, 100_000, 10_000_000] {
+        let mut group = c.benchmark_group(format!("parallel_for_{}", count));
+
+        let mut rng = Rng::new(Seed::new().unwrap());
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            values.push(rng.next_f32());
+        }
+
+        group.bench_function("serial", |b| {
+            b.iter(|| {
+                for value in values.iter_mut() {
+                    *value = f32::sqrt(*value * *value + 1.0);
+                }
+
+                black_box(&values);
+            })
+        });
+
+        group.bench_function("par_chunks_mut", |b| {
+            b.iter(|| {
+                parallel::par_chunks_mut(&mut values, 4096, |_, chunk| {
+                    for value in chunk.iter_mut() {
+                        *value = f32::sqrt(*value * *value + 1.0);
+                    }
+                });
+
+                black_box(&values);
+            })
+        });
+
+        group.bench_function("serial_sum", |b| {
+            b.iter(|| {
+                let mut sum = 0.0;
+                for value in values.iter() {
+                    sum += f32::sqrt(*value);
+                }
+
+                black_box(sum);
+            })
+        });
+
+        group.bench_function("parallel_reduce", |b| {
+            b.iter(|| {
+                let sum = parallel::parallel_reduce(
+                    0..values.len(),
+                    4096,
+                    0.0,
+                    |i| f32::sqrt(values[i]),
+                    |a, b| a + b,
+                );
+
+                black_box(sum);
+            })
+        });
+    }
+
+    drop(job_system);
+}
+
+criterion_group!(benches, parallel_for);
+criterion_main!(benches);
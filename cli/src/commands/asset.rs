@@ -1,8 +1,12 @@
+use std::io::Write;
 use std::path::PathBuf;
 
 use ris_asset::asset_compiler;
 use ris_asset::asset_compiler::CompileOptions;
+use ris_asset::asset_graph;
+use ris_asset::asset_graph::AssetGraph;
 use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
 use ris_core::log_appenders::console_appender::ConsoleAppender;
 use ris_error::RisResult;
 use ris_log::log::IAppender;
@@ -16,23 +20,32 @@ const LOG_LEVEL: LogLevel = LogLevel::Trace;
 pub const COMPILE: &str = "compile";
 pub const DECOMPILE: &str = "decompile";
 pub const IMPORT: &str = "import";
+pub const GRAPH: &str = "graph";
+pub const FORCE: &str = "--force";
+pub const STRIP_UNREACHABLE: &str = "--strip-unreachable";
 
 pub enum AssetCommand {
-    Compile,
+    Compile(CompileOptions),
     Decompile,
-    Import,
+    Import(ImportOptions),
+    Graph,
 }
 
 pub struct Asset;
 
 impl ICommand for Asset {
     fn args() -> String {
-        String::from("<command> [<source> <target>]")
+        format!(
+            "<command> [{}] [{}] [<source> <target>]",
+            FORCE, STRIP_UNREACHABLE,
+        )
     }
 
     fn explanation(level: ExplanationLevel) -> String {
         match level {
-            ExplanationLevel::Short => String::from("Compile, decompile or import assets."),
+            ExplanationLevel::Short => {
+                String::from("Compile, decompile, import or validate assets.")
+            }
             ExplanationLevel::Detailed => {
                 let mut explanation = String::new();
                 let short_explanation = Self::explanation(ExplanationLevel::Short);
@@ -42,7 +55,7 @@ impl ICommand for Asset {
                 explanation.push('\n');
                 explanation.push_str(&format!("{}\n", COMPILE));
                 explanation
-                    .push_str("Compiles the <source> directory into the asset file <target>.\n");
+                    .push_str("Compiles the <source> directory into the asset file <target>. Pass --strip-unreachable to skip all assets that cannot be reached from the god asset.\n");
                 explanation.push_str(&format!(
                     "default source: {}\n",
                     asset_compiler::DEFAULT_ASSET_DIRECTORY
@@ -65,7 +78,7 @@ impl ICommand for Asset {
                 ));
                 explanation.push('\n');
                 explanation.push_str(&format!("{}\n", IMPORT));
-                explanation.push_str("Recursively imports ALL files in directory <source> into the directory <target>.\n");
+                explanation.push_str("Recursively imports ALL files in directory <source> into the directory <target>. Files that did not change since their last import are skipped. Pass --force to delete <target> and import everything.\n");
                 explanation.push_str(&format!(
                     "default source: {}\n",
                     asset_importer::DEFAULT_SOURCE_DIRECTORY
@@ -74,12 +87,30 @@ impl ICommand for Asset {
                     "default target: {}\n",
                     asset_importer::DEFAULT_TARGET_DIRECTORY
                 ));
+                explanation.push('\n');
+                explanation.push_str(&format!("{}\n", GRAPH));
+                explanation.push_str("Builds the reference graph of the <source> directory, starting from the god asset. Reports missing references, cycles and unreachable assets, and exports the graph as DOT to the file <target>. Fails if there are missing references.\n");
+                explanation.push_str(&format!(
+                    "default source: {}\n",
+                    asset_compiler::DEFAULT_ASSET_DIRECTORY
+                ));
+                explanation.push_str(&format!(
+                    "default target: {}\n",
+                    asset_graph::DEFAULT_DOT_FILE
+                ));
                 explanation
             }
         }
     }
 
     fn run(args: Vec<String>, _target_dir: PathBuf) -> RisResult<()> {
+        let force = args.iter().any(|x| x == FORCE);
+        let strip_unreachable = args.iter().any(|x| x == STRIP_UNREACHABLE);
+        let args = args
+            .into_iter()
+            .filter(|x| x != FORCE && x != STRIP_UNREACHABLE)
+            .collect::<Vec<_>>();
+
         let parse_result = match args.len() {
             0 | 1 => Err(String::from("too few args")),
             2 => Err(String::from("no args provided")),
@@ -92,9 +123,13 @@ impl ICommand for Asset {
         match parse_result {
             Ok((command, source_target)) => {
                 let asset_command = match command.to_lowercase().as_str() {
-                    COMPILE => AssetCommand::Compile,
+                    COMPILE => AssetCommand::Compile(CompileOptions {
+                        include_original_paths: false,
+                        strip_unreachable,
+                    }),
                     DECOMPILE => AssetCommand::Decompile,
-                    IMPORT => AssetCommand::Import,
+                    IMPORT => AssetCommand::Import(ImportOptions { force }),
+                    GRAPH => AssetCommand::Graph,
                     command => {
                         return crate::util::command_error(
                             &format!("unkown command: {}", command),
@@ -128,22 +163,14 @@ impl Asset {
         let _log_guard = ris_log::log::init(LOG_LEVEL, appenders);
 
         match command {
-            AssetCommand::Compile => {
-                let compile_options = CompileOptions {
-                    include_original_paths: false,
-                };
-
-                match source_target {
-                    Some((source, target)) => {
-                        asset_compiler::compile(source, target, compile_options)
-                    }
-                    None => asset_compiler::compile(
-                        asset_compiler::DEFAULT_ASSET_DIRECTORY,
-                        asset_compiler::DEFAULT_COMPILED_FILE,
-                        compile_options,
-                    ),
-                }
-            }
+            AssetCommand::Compile(compile_options) => match source_target {
+                Some((source, target)) => asset_compiler::compile(source, target, compile_options),
+                None => asset_compiler::compile(
+                    asset_compiler::DEFAULT_ASSET_DIRECTORY,
+                    asset_compiler::DEFAULT_COMPILED_FILE,
+                    compile_options,
+                ),
+            },
             AssetCommand::Decompile => match source_target {
                 Some((source, target)) => asset_compiler::decompile(source, target),
                 None => asset_compiler::decompile(
@@ -151,15 +178,65 @@ impl Asset {
                     asset_compiler::DEFAULT_DECOMPILED_DIRECTORY,
                 ),
             },
-            AssetCommand::Import => match source_target {
-                Some((source, target)) => asset_importer::import_all(source, target, None),
+            AssetCommand::Import(options) => match source_target {
+                Some((source, target)) => asset_importer::import_all(source, target, None, options),
                 None => asset_importer::import_all(
                     asset_importer::DEFAULT_SOURCE_DIRECTORY,
                     asset_importer::DEFAULT_TARGET_DIRECTORY,
                     //Some("temp"),
                     None,
+                    options,
+                ),
+            },
+            AssetCommand::Graph => match source_target {
+                Some((source, target)) => Self::graph(source, target),
+                None => Self::graph(
+                    asset_compiler::DEFAULT_ASSET_DIRECTORY,
+                    asset_graph::DEFAULT_DOT_FILE,
                 ),
             },
         }
     }
+
+    fn graph(source: &str, target: &str) -> RisResult<()> {
+        let graph = AssetGraph::build(source)?;
+
+        match graph.root() {
+            Some(root) => ris_log::info!("root: \"{}\"", root),
+            None => ris_log::error!("no god asset found in \"{}\"", source),
+        }
+
+        let missing_references = graph.missing_references();
+        for (from, to) in missing_references.iter() {
+            ris_log::error!("missing reference: \"{}\" -> \"{}\"", from, to);
+        }
+
+        let cycles = graph.cycles();
+        for cycle in cycles.iter() {
+            ris_log::warning!("cycle: {:?}", cycle);
+        }
+
+        let unreachable = graph.unreachable();
+        for id in unreachable.iter() {
+            ris_log::info!("unreachable: \"{}\"", id);
+        }
+
+        ris_log::info!(
+            "assets: {}, missing references: {}, cycles: {}, unreachable: {}",
+            graph.assets().len(),
+            missing_references.len(),
+            cycles.len(),
+            unreachable.len(),
+        );
+
+        let mut file = std::fs::File::create(target)?;
+        file.write_all(graph.to_dot().as_bytes())?;
+        ris_log::info!("exported graph to \"{}\"", target);
+
+        if graph.root().is_none() || !missing_references.is_empty() {
+            return ris_error::new_result!("asset graph is invalid");
+        }
+
+        Ok(())
+    }
 }
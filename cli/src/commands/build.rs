@@ -2,6 +2,8 @@ use std::io::Read;
 use std::io::Write;
 use std::path::PathBuf;
 
+use ris_asset::asset_compiler::CompileOptions;
+use ris_asset::asset_importer::ImportOptions;
 use ris_error::RisResult;
 
 use crate::Asset;
@@ -146,9 +148,11 @@ impl ICommand for Build {
         }
 
         eprintln!("importing assets...");
-        Asset::execute_command(AssetCommand::Import, None)?;
+        let import_options = ImportOptions { force: true };
+        Asset::execute_command(AssetCommand::Import(import_options), None)?;
         eprintln!("compiling assets...");
-        Asset::execute_command(AssetCommand::Compile, None)?;
+        let compile_options = CompileOptions::default();
+        Asset::execute_command(AssetCommand::Compile(compile_options), None)?;
 
         eprintln!("compiling workspace...");
         crate::cmd::run("cargo build --release")?;
@@ -297,11 +297,7 @@ fn print_help() {
 
 fn write_god_asset(god_asset: &RisGodAsset, path: impl AsRef<Path>) -> RisResult<()> {
     match god_asset.serialize() {
-        Ok(bytes) => {
-            let mut file = std::fs::File::create(path)?;
-            ris_io::write(&mut file, &bytes)?;
-            Ok(())
-        }
+        Ok(bytes) => ris_io::fallback_file::write_atomic(path, &bytes),
         Err(e) => Err(e),
     }
 }
@@ -8,8 +8,11 @@ use std::path::PathBuf;
 use ris_data::asset_id::AssetId;
 use ris_error::Extensions;
 use ris_error::RisResult;
+use ris_io::fallback_file::AtomicFile;
 use ris_io::FatPtr;
 
+use crate::asset_graph::AssetGraph;
+use crate::import_cache;
 use crate::RisHeader;
 
 // # File Format
@@ -35,6 +38,8 @@ pub const DEFAULT_IGNORE_DIRECTORY: &str = "assets/__raw";
 #[derive(Default, Debug, Clone, Copy)]
 pub struct CompileOptions {
     pub include_original_paths: bool,
+    /// assets that cannot be reached from the god asset are not compiled. see `AssetGraph`.
+    pub strip_unreachable: bool,
 }
 
 /// compiles a directory to a ris_asset file
@@ -42,39 +47,28 @@ pub struct CompileOptions {
 /// - `target`: the path to the final compiled file. if this file exists already, it will be
 /// overwritten
 pub fn compile(source: &str, target: &str, options: CompileOptions) -> RisResult<()> {
-    let mut assets = Vec::new();
-    let mut asset_lookup_hashmap = HashMap::new();
-    let mut directories = std::collections::VecDeque::new();
     let source_path = PathBuf::from(source);
-    directories.push_back(source_path.clone());
-
-    // find all asset files
-    while let Some(current) = directories.pop_front() {
-        let entries = std::fs::read_dir(&current)?;
+    let mut assets = find_assets(source)?;
 
-        for entry in entries {
-            let entry = entry?;
-            let metadata = entry.metadata()?;
-            let entry_path = entry.path();
+    if options.strip_unreachable {
+        let graph = AssetGraph::build(source)?;
 
-            let to_ignore = PathBuf::from(DEFAULT_IGNORE_DIRECTORY);
-            if entry_path == to_ignore {
-                ris_log::debug!("ignoring \"{}\"", ris_io::path::to_str(entry_path),);
-                continue;
-            }
-
-            if metadata.is_file() {
-                asset_lookup_hashmap.insert(entry_path.clone(), assets.len());
-                assets.push(entry_path);
-            } else if metadata.is_dir() {
-                directories.push_back(entry_path);
+        let mut reachable_assets = Vec::with_capacity(assets.len());
+        for asset in assets {
+            let id = asset_id(source, &asset)?;
+            if graph.is_reachable(&id) {
+                reachable_assets.push(asset);
             } else {
-                return ris_error::new_result!(
-                    "entry \"{}\" is neither a file, nor a directory",
-                    ris_io::path::to_str(entry_path),
-                );
+                ris_log::info!("stripping unreachable asset \"{}\"", id);
             }
         }
+
+        assets = reachable_assets;
+    }
+
+    let mut asset_lookup_hashmap = HashMap::new();
+    for (i, asset) in assets.iter().enumerate() {
+        asset_lookup_hashmap.insert(asset.clone(), i);
     }
 
     ris_log::trace!("found {} assets:", assets.len());
@@ -82,14 +76,10 @@ pub fn compile(source: &str, target: &str, options: CompileOptions) -> RisResult
         ris_log::trace!("{}: \"{}\"", i, ris_io::path::to_str(file),);
     }
 
-    // create the target file
+    // create the target file. it replaces the previous one only once compilation succeeded
     let target_path = Path::new(target);
-    if target_path.exists() {
-        std::fs::remove_file(target_path)?;
-    }
-
-    let mut target_file = File::create(target_path)?;
-    let target_file = &mut target_file;
+    let mut atomic_target_file = AtomicFile::create(target_path)?;
+    let target_file = &mut atomic_target_file;
 
     // write magic
     ris_io::seek(target_file, SeekFrom::Start(0))?;
@@ -176,18 +166,7 @@ pub fn compile(source: &str, target: &str, options: CompileOptions) -> RisResult
     let p_original_asset_names = if options.include_original_paths {
         let original_paths = assets
             .iter()
-            .map(|x| {
-                Ok({
-                    let mut original_path = x.to_str().into_ris_error()?.to_string();
-                    original_path.replace_range(0..source.len(), "");
-                    let mut original_path = original_path.replace('\\', "/");
-                    if original_path.starts_with('/') {
-                        original_path.remove(0);
-                    }
-
-                    original_path
-                })
-            })
+            .map(|x| asset_id(source, x))
             .collect::<RisResult<Vec<_>>>()?;
 
         let original_paths = original_paths
@@ -216,7 +195,60 @@ pub fn compile(source: &str, target: &str, options: CompileOptions) -> RisResult
         ris_io::write_u64(target_file, *asset_lookup_entry)?;
     }
 
-    Ok(())
+    atomic_target_file.commit()
+}
+
+/// returns the paths of all files in `source` that are compiled
+pub fn find_assets(source: &str) -> RisResult<Vec<PathBuf>> {
+    let mut assets = Vec::new();
+    let mut directories = std::collections::VecDeque::new();
+    let source_path = PathBuf::from(source);
+    directories.push_back(source_path);
+
+    while let Some(current) = directories.pop_front() {
+        let entries = std::fs::read_dir(&current)?;
+
+        for entry in entries {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let entry_path = entry.path();
+
+            let to_ignore = PathBuf::from(DEFAULT_IGNORE_DIRECTORY);
+            if entry_path == to_ignore {
+                ris_log::debug!("ignoring \"{}\"", ris_io::path::to_str(entry_path),);
+                continue;
+            }
+
+            if entry.file_name() == import_cache::FILE_NAME {
+                continue;
+            }
+
+            if metadata.is_file() {
+                assets.push(entry_path);
+            } else if metadata.is_dir() {
+                directories.push_back(entry_path);
+            } else {
+                return ris_error::new_result!(
+                    "entry \"{}\" is neither a file, nor a directory",
+                    ris_io::path::to_str(entry_path),
+                );
+            }
+        }
+    }
+
+    Ok(assets)
+}
+
+/// returns the directory id of the asset at `path`, which is located in `source`
+pub fn asset_id(source: &str, path: &Path) -> RisResult<String> {
+    let mut id = path.to_str().into_ris_error()?.to_string();
+    id.replace_range(0..source.len(), "");
+    let mut id = id.replace('\\', "/");
+    if id.starts_with('/') {
+        id.remove(0);
+    }
+
+    Ok(id)
 }
 
 /// decompiles a .ris_asset file to a directory.
@@ -344,8 +376,7 @@ pub fn decompile(source: &str, target: &str) -> RisResult<()> {
         let parent = asset_path.parent().into_ris_error()?;
         std::fs::create_dir_all(parent)?;
 
-        let mut decompiled_file = File::create(&asset_path)?;
-        ris_io::write(&mut decompiled_file, &modified_file_content)?;
+        ris_io::fallback_file::write_atomic(&asset_path, &modified_file_content)?;
     }
 
     Ok(())
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use ris_data::asset_id::AssetId;
+use ris_error::RisResult;
+
+use crate::asset_compiler;
+use crate::assets::ris_god_asset;
+use crate::RisHeader;
+
+pub const DEFAULT_DOT_FILE: &str = "asset_graph.dot";
+
+/// the references between all assets in an asset directory. the root of the graph is the god
+/// asset, as every asset that is used at runtime must be reachable from it.
+pub struct AssetGraph {
+    root: Option<String>,
+    references: BTreeMap<String, Vec<String>>,
+    reachable: BTreeSet<String>,
+}
+
+impl AssetGraph {
+    /// builds the graph of all assets in the directory `source`. assets are identified by their
+    /// directory id, i.e. their path relative to `source`.
+    pub fn build(source: &str) -> RisResult<Self> {
+        let mut references = BTreeMap::new();
+
+        for asset in asset_compiler::find_assets(source)? {
+            let id = asset_compiler::asset_id(source, &asset)?;
+            let bytes = std::fs::read(&asset)?;
+
+            let mut asset_references = Vec::new();
+            if let Some(header) = RisHeader::load(&bytes)? {
+                for reference in header.references {
+                    match reference {
+                        AssetId::Path(reference) => asset_references.push(reference),
+                        AssetId::Index(reference) => {
+                            return ris_error::new_result!(
+                                "\"{}\" references the compiled asset {}. only directory assets can be added to the graph",
+                                id,
+                                reference,
+                            );
+                        }
+                    }
+                }
+            }
+
+            references.insert(id, asset_references);
+        }
+
+        let root = [ris_god_asset::PATH, ris_god_asset::UNNAMED_PATH]
+            .into_iter()
+            .find(|x| references.contains_key(*x))
+            .map(String::from);
+
+        // breadth first search, starting from the root
+        let mut reachable = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        if let Some(root) = &root {
+            reachable.insert(root.clone());
+            queue.push_back(root.clone());
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let Some(asset_references) = references.get(&current) else {
+                continue;
+            };
+
+            for reference in asset_references {
+                if reachable.insert(reference.clone()) {
+                    queue.push_back(reference.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            root,
+            references,
+            reachable,
+        })
+    }
+
+    /// the id of the god asset, or `None` if the directory does not contain one
+    pub fn root(&self) -> Option<&str> {
+        self.root.as_deref()
+    }
+
+    pub fn assets(&self) -> Vec<&str> {
+        self.references.keys().map(|x| x.as_str()).collect()
+    }
+
+    pub fn references(&self, id: &str) -> Option<&[String]> {
+        self.references.get(id).map(|x| x.as_slice())
+    }
+
+    pub fn is_reachable(&self, id: &str) -> bool {
+        self.reachable.contains(id)
+    }
+
+    /// returns all assets which are not reachable from the root
+    pub fn unreachable(&self) -> Vec<&str> {
+        self.references
+            .keys()
+            .filter(|x| !self.reachable.contains(*x))
+            .map(|x| x.as_str())
+            .collect()
+    }
+
+    /// returns all references to assets that do not exist, as `(from, to)` pairs
+    pub fn missing_references(&self) -> Vec<(&str, &str)> {
+        let mut result = Vec::new();
+
+        for (id, asset_references) in self.references.iter() {
+            for reference in asset_references {
+                if !self.references.contains_key(reference) {
+                    result.push((id.as_str(), reference.as_str()));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// returns all groups of assets that reference each other in a cycle. this includes assets
+    /// that reference themselves.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        // tarjan's strongly connected components algorithm
+        struct Tarjan<'a> {
+            graph: &'a AssetGraph,
+            index: usize,
+            indices: HashMap<&'a str, usize>,
+            low_links: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: BTreeSet<&'a str>,
+            result: Vec<Vec<&'a str>>,
+        }
+
+        impl<'a> Tarjan<'a> {
+            fn strong_connect(&mut self, id: &'a str) {
+                self.indices.insert(id, self.index);
+                self.low_links.insert(id, self.index);
+                self.index += 1;
+                self.stack.push(id);
+                self.on_stack.insert(id);
+
+                let graph = self.graph;
+                let asset_references = graph.references.get(id).into_iter().flatten();
+                for reference in asset_references {
+                    let reference = reference.as_str();
+                    if !graph.references.contains_key(reference) {
+                        continue;
+                    }
+
+                    if !self.indices.contains_key(reference) {
+                        self.strong_connect(reference);
+                        let low_link = self.low_links[id].min(self.low_links[reference]);
+                        self.low_links.insert(id, low_link);
+                    } else if self.on_stack.contains(reference) {
+                        let low_link = self.low_links[id].min(self.indices[reference]);
+                        self.low_links.insert(id, low_link);
+                    }
+                }
+
+                if self.low_links[id] != self.indices[id] {
+                    return;
+                }
+
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member);
+
+                    if member == id {
+                        break;
+                    }
+                }
+
+                let is_cycle = component.len() > 1
+                    || graph
+                        .references
+                        .get(id)
+                        .is_some_and(|x| x.iter().any(|y| y == id));
+
+                if is_cycle {
+                    component.sort();
+                    self.result.push(component);
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: HashMap::new(),
+            low_links: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            result: Vec::new(),
+        };
+
+        for id in self.references.keys() {
+            if !tarjan.indices.contains_key(id.as_str()) {
+                tarjan.strong_connect(id);
+            }
+        }
+
+        tarjan.result
+    }
+
+    /// exports the graph in the DOT language of graphviz. the root is drawn as a box, unreachable
+    /// assets are gray and missing assets are red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n");
+
+        for id in self.references.keys() {
+            let attributes = if self.root.as_ref() == Some(id) {
+                " [shape=box]"
+            } else if !self.reachable.contains(id) {
+                " [color=gray, fontcolor=gray]"
+            } else {
+                ""
+            };
+
+            dot.push_str(&format!("    {}{};\n", quote(id), attributes));
+        }
+
+        let missing = self
+            .missing_references()
+            .into_iter()
+            .map(|(_, to)| to)
+            .collect::<BTreeSet<_>>();
+        for id in missing {
+            dot.push_str(&format!(
+                "    {} [color=red, fontcolor=red, style=dashed];\n",
+                quote(id),
+            ));
+        }
+
+        for (id, asset_references) in self.references.iter() {
+            for reference in asset_references {
+                dot.push_str(&format!("    {} -> {};\n", quote(id), quote(reference)));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fs::File;
 use std::path::Path;
 use std::path::PathBuf;
@@ -5,15 +6,25 @@ use std::path::PathBuf;
 use ris_error::Extensions;
 use ris_error::RisResult;
 
+use crate::import_cache;
+use crate::import_cache::ImportCache;
+use crate::import_cache::ImportCacheEntry;
 use crate::importer::*;
 
 pub const DEFAULT_SOURCE_DIRECTORY: &str = "assets/__raw";
 pub const DEFAULT_TARGET_DIRECTORY: &str = "assets/__imported_raw";
 
-pub const EXTENSIONS_TO_SKIP: &[&str] = &["aseprite"];
+pub const EXTENSIONS_TO_SKIP: &[&str] = &[
+    "aseprite",
+    gltf_to_ris_mesh_importer::BUFFER_EXT,
+    obj_to_ris_mesh_importer::MATERIAL_EXT,
+    png_to_ris_texture_importer::META_EXT,
+];
 
 pub enum ImporterKind {
     GLSL,
+    GLTF,
+    OBJ,
     PNG,
 }
 
@@ -33,21 +44,56 @@ pub enum ImporterInfo {
     DeduceFromFileName(DeduceImporterInfo),
 }
 
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ImportOptions {
+    /// deletes the target directory and imports everything, regardless of the import cache
+    pub force: bool,
+}
+
+/// imports all files in `source_directory` into `target_directory`. files that did not change
+/// since their last import are skipped, unless `options.force` is set.
+///
+/// `target_directory` must lie directly in the asset directory, because asset ids written by
+/// importers are relative to its parent.
 pub fn import_all(
     source_directory: &str,
     target_directory: &str,
     temp_directory: Option<&str>,
+    options: ImportOptions,
 ) -> RisResult<()> {
-    let mut directories = std::collections::VecDeque::new();
-    let source_path = PathBuf::from(source_directory);
-    directories.push_back(source_path);
-
     let target_directory_path = PathBuf::from(target_directory);
-    if target_directory_path.exists() {
-        std::fs::remove_dir_all(target_directory_path)?;
+    if options.force && target_directory_path.exists() {
+        std::fs::remove_dir_all(&target_directory_path)?;
     }
 
     let temp_directory = temp_directory.map(PathBuf::from);
+    let mut cache = ImportCache::load(&target_directory_path);
+
+    let result = import_directory(
+        source_directory,
+        target_directory,
+        temp_directory.as_deref(),
+        &mut cache,
+    );
+
+    // save the cache, even when an import failed. otherwise all assets that were imported
+    // successfully up to this point would be imported again next time.
+    cache.save(&target_directory_path)?;
+
+    result
+}
+
+fn import_directory(
+    source_directory: &str,
+    target_directory: &str,
+    temp_directory: Option<&Path>,
+    cache: &mut ImportCache,
+) -> RisResult<()> {
+    let mut directories = std::collections::VecDeque::new();
+    let source_path = PathBuf::from(source_directory);
+    directories.push_back(source_path);
+
+    let mut imported_sources = HashSet::new();
 
     while let Some(current) = directories.pop_front() {
         let entries = std::fs::read_dir(&current)?;
@@ -58,39 +104,16 @@ pub fn import_all(
             let entry_path = entry.path();
 
             if metadata.is_file() {
-                let mut source_directory = source_directory.replace('\\', "/");
-                let mut target_directory = target_directory.replace('\\', "/");
-
-                if !source_directory.ends_with('/') {
-                    source_directory.push('/');
-                }
-
-                if !target_directory.ends_with('/') {
-                    target_directory.push('/');
-                }
-
-                let source_path = entry_path.to_str().into_ris_error()?;
-                let mut target_path_part = source_path.replace('\\', "/");
-                target_path_part.replace_range(0..source_directory.len(), "");
-
-                let mut target_path = PathBuf::new();
-                target_path.push(target_directory.clone());
-                target_path.push(&target_path_part);
-                let target_path = PathBuf::from(target_path.parent().unwrap());
-
-                ris_log::debug!(
-                    "import \"{}\" to \"{}\"",
-                    ris_io::path::to_str(&entry_path),
-                    ris_io::path::to_str(&target_path),
-                );
-
-                let info = DeduceImporterInfo {
-                    source_file_path: entry_path,
-                    target_directory: target_path,
-                };
-                let importer_info = ImporterInfo::DeduceFromFileName(info);
-                let temp_directory = temp_directory.as_deref();
-                import(importer_info, temp_directory)?;
+                import_file(
+                    source_directory,
+                    target_directory,
+                    &entry_path,
+                    temp_directory,
+                    cache,
+                )?;
+
+                let source = relative_path(source_directory, &entry_path)?;
+                imported_sources.insert(source);
             } else if metadata.is_dir() {
                 directories.push_back(entry_path);
             } else {
@@ -101,9 +124,143 @@ pub fn import_all(
             }
         }
     }
+
+    // delete the imported files of raw assets that no longer exist
+    for source in cache.sources() {
+        if imported_sources.contains(&source) {
+            continue;
+        }
+
+        if let Some(entry) = cache.remove(&source) {
+            ris_log::debug!("remove imported files of deleted asset \"{}\"", source);
+            remove_targets(target_directory, &entry.targets, &[])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// imports a single file, if it is not up to date according to `cache`. `source_file_path`
+/// must be located in `source_directory`. the imported files are written to the same relative
+/// path in `target_directory`.
+pub fn import_file(
+    source_directory: &str,
+    target_directory: &str,
+    source_file_path: &Path,
+    temp_directory: Option<&Path>,
+    cache: &mut ImportCache,
+) -> RisResult<()> {
+    let source = relative_path(source_directory, source_file_path)?;
+
+    let mut target_path = PathBuf::new();
+    target_path.push(target_directory);
+    target_path.push(&source);
+    let target_path = PathBuf::from(target_path.parent().unwrap());
+
+    let info = DeduceImporterInfo {
+        source_file_path: source_file_path.to_path_buf(),
+        target_directory: target_path.clone(),
+    };
+    let Some(info) = deduce_importer_info(info)? else {
+        return Ok(());
+    };
+
+    let importer_version = importer_version(&info.importer);
+    let source_hash = import_cache::hash_file(source_file_path)?;
+    let is_stale = cache.is_stale(
+        &source,
+        source_hash,
+        importer_version,
+        Path::new(target_directory),
+    );
+    if !is_stale {
+        ris_log::trace!("up to date \"{}\"", ris_io::path::to_str(source_file_path),);
+        return Ok(());
+    }
+
+    ris_log::debug!(
+        "import \"{}\" to \"{}\"",
+        ris_io::path::to_str(source_file_path),
+        ris_io::path::to_str(&target_path),
+    );
+
+    let target_file_paths = info.target_file_paths.clone();
+    let dependencies = import(
+        ImporterInfo::Specific(info),
+        Path::new(target_directory),
+        temp_directory,
+    )?;
+
+    // not every importer writes all of its target files. only remember the ones that exist.
+    let mut targets = Vec::new();
+    for target_file_path in target_file_paths {
+        if target_file_path.exists() {
+            let target = relative_path(target_directory, &target_file_path)?;
+            targets.push(target);
+        }
+    }
+
+    let mut dependency_hashes = Vec::new();
+    for dependency in dependencies {
+        let dependency_hash = import_cache::hash_dependency(&dependency)?;
+        let dependency = dependency.to_str().into_ris_error()?.replace('\\', "/");
+        dependency_hashes.push((dependency, dependency_hash));
+    }
+
+    // an updated raw asset may produce fewer files than before
+    if let Some(previous_entry) = cache.get(&source) {
+        remove_targets(target_directory, &previous_entry.targets, &targets)?;
+    }
+
+    let entry = ImportCacheEntry {
+        source_hash,
+        importer_version,
+        targets,
+        dependencies: dependency_hashes,
+    };
+    cache.insert(source, entry);
+
+    Ok(())
+}
+
+fn relative_path(directory: &str, path: &Path) -> RisResult<String> {
+    let mut directory = directory.replace('\\', "/");
+    if !directory.ends_with('/') {
+        directory.push('/');
+    }
+
+    let path = path.to_str().into_ris_error()?;
+    let mut relative_path = path.replace('\\', "/");
+    relative_path.replace_range(0..directory.len(), "");
+
+    Ok(relative_path)
+}
+
+fn remove_targets(target_directory: &str, targets: &[String], to_keep: &[String]) -> RisResult<()> {
+    for target in targets {
+        if to_keep.contains(target) {
+            continue;
+        }
+
+        let target_path = PathBuf::from(target_directory).join(target);
+        if target_path.exists() {
+            std::fs::remove_file(target_path)?;
+        }
+    }
+
     Ok(())
 }
 
+fn importer_version(importer: &ImporterKind) -> u32 {
+    match importer {
+        ImporterKind::GLSL => glsl_to_spirv_importer::VERSION,
+        ImporterKind::GLTF => gltf_to_ris_mesh_importer::VERSION,
+        ImporterKind::OBJ => obj_to_ris_mesh_importer::VERSION,
+        ImporterKind::PNG => png_to_ris_texture_importer::VERSION,
+        // insert more importers here...
+    }
+}
+
 pub fn create_file(file_path: &Path) -> RisResult<File> {
     let parent = file_path.parent();
     if let Some(parent) = parent {
@@ -120,61 +277,108 @@ pub fn create_file(file_path: &Path) -> RisResult<File> {
     Ok(file)
 }
 
-fn import(info: ImporterInfo, temp_directory: Option<&Path>) -> RisResult<()> {
-    let (source_path, target_paths, importer) = match info {
-        ImporterInfo::Specific(info) => {
-            (info.source_file_path, info.target_file_paths, info.importer)
+/// returns the paths of all files the import depended on, other than the source file itself.
+/// `target_directory` is the root of all imported assets.
+fn import(
+    info: ImporterInfo,
+    target_directory: &Path,
+    temp_directory: Option<&Path>,
+) -> RisResult<Vec<PathBuf>> {
+    let info = match info {
+        ImporterInfo::Specific(info) => info,
+        ImporterInfo::DeduceFromFileName(info) => match deduce_importer_info(info)? {
+            Some(info) => info,
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    let SpecificImporterInfo {
+        source_file_path: source_path,
+        target_file_paths: target_paths,
+        importer,
+    } = info;
+
+    match importer {
+        ImporterKind::GLSL => glsl_to_spirv_importer::import(
+            source_path,
+            target_paths,
+            asset_directory(target_directory),
+            temp_directory,
+        ),
+        ImporterKind::GLTF => gltf_to_ris_mesh_importer::import(source_path, target_paths),
+        ImporterKind::OBJ => obj_to_ris_mesh_importer::import(source_path, target_paths),
+        ImporterKind::PNG => png_to_ris_texture_importer::import(source_path, target_paths),
+        // insert more importers here...
+    }
+}
+
+/// the directory that asset ids are relative to. imported assets lie in a directory inside of it,
+/// like `assets/__imported_raw`, thus their ids start with the name of that directory.
+fn asset_directory(target_directory: &Path) -> &Path {
+    target_directory.parent().unwrap_or(target_directory)
+}
+
+/// returns `None` if the file should be skipped
+fn deduce_importer_info(info: DeduceImporterInfo) -> RisResult<Option<SpecificImporterInfo>> {
+    let source_path = info.source_file_path;
+    let target_directory = info.target_directory;
+
+    let source_extension = source_path.extension().into_ris_error()?;
+    let source_extension = source_extension.to_str().into_ris_error()?;
+    let source_extension = source_extension.to_lowercase();
+
+    let (importer, target_extensions) = match source_extension.as_str() {
+        glsl_to_spirv_importer::IN_EXT => {
+            // the targets depend on the variants the shader declares
+            let target_extensions = glsl_to_spirv_importer::target_extensions(&source_path)?;
+            (ImporterKind::GLSL, target_extensions)
         }
-        ImporterInfo::DeduceFromFileName(info) => {
-            let source_path = info.source_file_path;
-            let target_directory = info.target_directory;
-
-            let source_extension = source_path.extension().into_ris_error()?;
-            let source_extension = source_extension.to_str().into_ris_error()?;
-            let source_extension = source_extension.to_lowercase();
-
-            let (importer, target_extensions) = match source_extension.as_str() {
-                glsl_to_spirv_importer::IN_EXT => {
-                    (ImporterKind::GLSL, glsl_to_spirv_importer::OUT_EXT)
-                }
-                png_to_qoi_importer::IN_EXT => (ImporterKind::PNG, png_to_qoi_importer::OUT_EXT),
-                // insert new inporter here...
-                extension => {
-                    if EXTENSIONS_TO_SKIP.contains(&extension) {
-                        ris_log::debug!("skipped import \"{}\"", ris_io::path::to_str(source_path),);
-                        return Ok(());
-                    } else {
-                        return ris_error::new_result!(
-                            "failed to deduce importer. unkown extension: {}",
-                            source_extension
-                        );
-                    }
-                }
-            };
-
-            let source_stem = source_path.file_stem().into_ris_error()?;
-            let source_stem = source_stem.to_str().into_ris_error()?;
-            let source_stem = String::from(source_stem);
-
-            let mut target_paths = Vec::new();
-
-            for target_extension in target_extensions {
-                let mut target_path = PathBuf::new();
-                target_path.push(target_directory.clone());
-                target_path.push(format!("{source_stem}.{target_extension}"));
-
-                target_paths.push(target_path);
+        gltf_to_ris_mesh_importer::IN_EXT_GLTF | gltf_to_ris_mesh_importer::IN_EXT_GLB => (
+            ImporterKind::GLTF,
+            to_vec(gltf_to_ris_mesh_importer::OUT_EXT),
+        ),
+        obj_to_ris_mesh_importer::IN_EXT => {
+            (ImporterKind::OBJ, to_vec(obj_to_ris_mesh_importer::OUT_EXT))
+        }
+        png_to_ris_texture_importer::IN_EXT => (
+            ImporterKind::PNG,
+            to_vec(png_to_ris_texture_importer::OUT_EXT),
+        ),
+        // insert new inporter here...
+        extension => {
+            if EXTENSIONS_TO_SKIP.contains(&extension) {
+                ris_log::debug!("skipped import \"{}\"", ris_io::path::to_str(source_path),);
+                return Ok(None);
+            } else {
+                return ris_error::new_result!(
+                    "failed to deduce importer. unkown extension: {}",
+                    source_extension
+                );
             }
-
-            (source_path, target_paths, importer)
         }
     };
 
-    match importer {
-        ImporterKind::GLSL => {
-            glsl_to_spirv_importer::import(source_path, target_paths, temp_directory)
-        }
-        ImporterKind::PNG => png_to_qoi_importer::import(source_path, target_paths),
-        // insert more importers here...
+    let source_stem = source_path.file_stem().into_ris_error()?;
+    let source_stem = source_stem.to_str().into_ris_error()?;
+    let source_stem = String::from(source_stem);
+
+    let mut target_paths = Vec::new();
+
+    for target_extension in target_extensions {
+        let mut target_path = PathBuf::new();
+        target_path.push(target_directory.clone());
+        target_path.push(format!("{source_stem}.{target_extension}"));
+
+        target_paths.push(target_path);
     }
+
+    Ok(Some(SpecificImporterInfo {
+        source_file_path: source_path,
+        target_file_paths: target_paths,
+        importer,
+    }))
+}
+
+fn to_vec(extensions: &[&str]) -> Vec<String> {
+    extensions.iter().map(|x| x.to_string()).collect()
 }
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use ris_data::asset_id::AssetId;
+use ris_error::Extensions;
+use ris_error::RisResult;
+
+use crate::asset_importer;
+use crate::import_cache;
+use crate::import_cache::ImportCache;
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct AssetWatcherInfo {
+    /// the directory the asset loader loads from
+    pub asset_directory: PathBuf,
+    /// the directory containing raw assets, which must be imported before they can be loaded
+    pub source_directory: String,
+    /// the directory where imported raw assets are written to
+    pub target_directory: String,
+    pub temp_directory: Option<PathBuf>,
+    /// the minimum time between two scans. scans requested before this time has passed are
+    /// skipped
+    pub poll_interval: Duration,
+}
+
+/// polls the modification times of all files in the asset and the raw asset directories.
+/// changed raw assets are reimported, and the ids of all changed assets are reported.
+///
+/// only works with the directory asset loader. compiled assets are never hot reloaded.
+pub struct AssetWatcher {
+    info: AssetWatcherInfo,
+    canonical_source_directory: PathBuf,
+    modified: HashMap<PathBuf, SystemTime>,
+    last_poll: Instant,
+}
+
+impl AssetWatcher {
+    pub fn new(info: AssetWatcherInfo) -> RisResult<Self> {
+        let canonical_source_directory = std::fs::canonicalize(&info.source_directory)?;
+
+        let mut watcher = Self {
+            info,
+            canonical_source_directory,
+            modified: HashMap::new(),
+            last_poll: Instant::now(),
+        };
+
+        // the first scan only records the current state of the directories. everything that
+        // exists at this point is considered up to date.
+        watcher.scan_source_directory()?;
+        watcher.scan_asset_directory()?;
+
+        Ok(watcher)
+    }
+
+    /// scans the directories, if the poll interval has passed since the last scan. returns the
+    /// ids of all assets that were changed since then. if it fails, the changes are reported by
+    /// the next poll that succeeds.
+    pub fn poll(&mut self) -> RisResult<Vec<AssetId>> {
+        let now = Instant::now();
+        if now - self.last_poll < self.info.poll_interval {
+            return Ok(Vec::new());
+        }
+
+        self.last_poll = now;
+
+        // raw assets must be imported first, such that their imported files are picked up by the
+        // scan of the asset directory below
+        let changed_raw_assets = self.scan_source_directory()?;
+        if !changed_raw_assets.is_empty() {
+            for raw_asset in changed_raw_assets.iter() {
+                ris_log::info!("raw asset changed \"{}\"", ris_io::path::to_str(raw_asset));
+            }
+
+            if let Err(e) = self.reimport() {
+                // forget the changed raw assets, such that the next poll imports them again
+                for raw_asset in changed_raw_assets.iter() {
+                    self.modified.remove(raw_asset);
+                }
+
+                return Err(e);
+            }
+        }
+
+        let changed_assets = self.scan_asset_directory()?;
+
+        let mut ids = Vec::with_capacity(changed_assets.len());
+        for asset in changed_assets {
+            let id = self.path_to_id(&asset)?;
+            ris_log::debug!("asset changed: {:?}", id);
+            ids.push(id);
+        }
+
+        Ok(ids)
+    }
+
+    fn reimport(&self) -> RisResult<()> {
+        // a changed file may be included by other raw assets. thus every raw asset is passed to
+        // the importer, and the import cache decides which ones actually need to be imported.
+        let source_directory = PathBuf::from(&self.info.source_directory);
+        let mut raw_assets = self
+            .modified
+            .keys()
+            .filter(|x| x.starts_with(&source_directory) && x.exists())
+            .collect::<Vec<_>>();
+        raw_assets.sort();
+
+        // the cache is loaded every time, because it may have been modified by
+        // `asset_importer::import_all()` since the last reimport
+        let target_directory = PathBuf::from(&self.info.target_directory);
+        let mut cache = ImportCache::load(&target_directory);
+
+        for raw_asset in raw_assets {
+            let result = asset_importer::import_file(
+                &self.info.source_directory,
+                &self.info.target_directory,
+                raw_asset,
+                self.info.temp_directory.as_deref(),
+                &mut cache,
+            );
+
+            // a failed import must not kill the running game. the raw asset is probably being
+            // edited and will be imported again on its next change.
+            if let Err(e) = result {
+                ris_log::error!(
+                    "failed to reimport \"{}\": {}",
+                    ris_io::path::to_str(raw_asset),
+                    e,
+                );
+            }
+        }
+
+        cache.save(&target_directory)
+    }
+
+    fn scan_source_directory(&mut self) -> RisResult<Vec<PathBuf>> {
+        let source_directory = PathBuf::from(&self.info.source_directory);
+        self.scan(source_directory, false)
+    }
+
+    fn scan_asset_directory(&mut self) -> RisResult<Vec<PathBuf>> {
+        let asset_directory = self.info.asset_directory.clone();
+        self.scan(asset_directory, true)
+    }
+
+    /// `modified` is only updated once the whole directory was scanned, such that a failed scan
+    /// is repeated by the next one
+    fn scan(&mut self, directory: PathBuf, ignore_source: bool) -> RisResult<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        let mut scanned = Vec::new();
+        let mut seen = HashSet::new();
+        let mut skipped_directories = Vec::new();
+        let mut directories = std::collections::VecDeque::new();
+        directories.push_back(directory.clone());
+
+        while let Some(current) = directories.pop_front() {
+            // files may be deleted while scanning, for example when all assets are reimported.
+            // entries that vanished are simply skipped.
+            let entries = match std::fs::read_dir(&current) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            for entry in entries {
+                let entry = entry?;
+                let entry_path = entry.path();
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e.into()),
+                };
+
+                if metadata.is_file() {
+                    if entry.file_name() == import_cache::FILE_NAME {
+                        continue;
+                    }
+
+                    let modified = metadata.modified()?;
+                    seen.insert(entry_path.clone());
+                    if self.modified.get(&entry_path) != Some(&modified) {
+                        changed.push(entry_path.clone());
+                    }
+                    scanned.push((entry_path, modified));
+                } else if metadata.is_dir() {
+                    if ignore_source && self.is_source_directory(&entry_path) {
+                        skipped_directories.push(entry_path);
+                        continue;
+                    }
+
+                    directories.push_back(entry_path);
+                }
+            }
+        }
+
+        self.modified.extend(scanned);
+
+        // forget deleted files, such that they are not checked again and are reported as
+        // changed, if they are recreated
+        self.modified.retain(|path, _| {
+            !path.starts_with(&directory)
+                || seen.contains(path)
+                || skipped_directories.iter().any(|x| path.starts_with(x))
+        });
+
+        Ok(changed)
+    }
+
+    fn is_source_directory(&self, path: &Path) -> bool {
+        match std::fs::canonicalize(path) {
+            Ok(path) => path == self.canonical_source_directory,
+            Err(_) => false,
+        }
+    }
+
+    fn path_to_id(&self, path: &Path) -> RisResult<AssetId> {
+        let relative = path.strip_prefix(&self.info.asset_directory)?;
+        let relative = relative.to_str().into_ris_error()?.replace('\\', "/");
+        Ok(AssetId::Path(relative))
+    }
+}
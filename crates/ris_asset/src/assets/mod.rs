@@ -1,3 +1,6 @@
 pub mod ris_god_asset;
 pub mod ris_header;
+pub mod ris_mesh;
 pub mod ris_scene;
+pub mod ris_shader_variants;
+pub mod ris_texture;
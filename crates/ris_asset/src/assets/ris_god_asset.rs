@@ -30,24 +30,25 @@ pub struct RisGodAsset {
 }
 
 impl RisGodAsset {
+    pub fn references(&self) -> Vec<AssetId> {
+        vec![
+            self.default_vert_spv.clone(),
+            self.default_frag_spv.clone(),
+            self.imgui_vert_spv.clone(),
+            self.imgui_frag_spv.clone(),
+            self.gizmo_segment_vert_spv.clone(),
+            self.gizmo_segment_geom_spv.clone(),
+            self.gizmo_segment_frag_spv.clone(),
+            self.gizmo_text_vert_spv.clone(),
+            self.gizmo_text_geom_spv.clone(),
+            self.gizmo_text_frag_spv.clone(),
+            self.debug_font_texture.clone(),
+            self.texture.clone(),
+        ]
+    }
+
     pub fn serialize(&self) -> RisResult<Vec<u8>> {
-        let header = RisHeader::new(
-            MAGIC,
-            vec![
-                self.default_vert_spv.clone(),
-                self.default_frag_spv.clone(),
-                self.imgui_vert_spv.clone(),
-                self.imgui_frag_spv.clone(),
-                self.gizmo_segment_vert_spv.clone(),
-                self.gizmo_segment_geom_spv.clone(),
-                self.gizmo_segment_frag_spv.clone(),
-                self.gizmo_text_vert_spv.clone(),
-                self.gizmo_text_geom_spv.clone(),
-                self.gizmo_text_frag_spv.clone(),
-                self.debug_font_texture.clone(),
-                self.texture.clone(),
-            ],
-        );
+        let header = RisHeader::new(MAGIC, self.references());
         let header_bytes = header.serialize()?;
 
         let mut stream = Cursor::new(Vec::new());
@@ -3,6 +3,7 @@ use std::io::SeekFrom;
 
 use ris_data::asset_id::AssetId;
 use ris_error::RisResult;
+use ris_io::BoundedReader;
 use ris_io::FatPtr;
 
 // # File Format
@@ -18,6 +19,9 @@ use ris_io::FatPtr;
 //   - [u32; sized String]: directory AssetIds
 // - [u8; ?]: content
 
+/// the most references a header may have, and the longest a directory `AssetId` may be
+pub const MAX_LEN: usize = 1 << 20;
+
 #[derive(Debug)]
 pub struct RisHeader {
     pub magic: [u8; 16],
@@ -75,7 +79,12 @@ impl RisHeader {
     }
 
     pub fn load(bytes: &[u8]) -> RisResult<Option<Self>> {
-        let f = &mut Cursor::new(bytes);
+        // too small to contain a magic, thus it cannot be a ris asset
+        if bytes.len() < 16 {
+            return Ok(None);
+        }
+
+        let f = &mut BoundedReader::new(Cursor::new(bytes), MAX_LEN)?;
         let mut magic = [0; 16];
         ris_io::read(f, &mut magic)?;
 
@@ -89,14 +98,15 @@ impl RisHeader {
         }
 
         let is_compiled = ris_io::read_bool(f)?;
-        let reference_count = ris_io::read_uint(f)?;
+        // a compiled id is an `u32`, a directory id is at least the `u32` len of its string
+        let reference_count = f.read_len(std::mem::size_of::<u32>())?;
         let mut references = Vec::with_capacity(reference_count);
         for _ in 0..reference_count {
             let reference = if is_compiled {
                 let id = ris_io::read_uint(f)?;
                 AssetId::Index(id)
             } else {
-                let id = ris_io::read_string(f)?;
+                let id = f.read_string()?;
                 AssetId::Path(id)
             };
 
@@ -0,0 +1,199 @@
+use std::io::Cursor;
+
+use ris_data::ecs::mesh::Mesh;
+use ris_data::ecs::mesh::Vertex;
+use ris_error::Extensions;
+use ris_error::RisResult;
+use ris_math::color::Rgb;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+use super::ris_header::RisHeader;
+
+// # File Format
+//
+// encoding: little-endian
+//
+// - RisHeader without references
+// - u32: vertex_count
+// - [Vec3; vertex_count]: positions
+// - bool: has_normals
+// - [Vec3; vertex_count]: normals, if has_normals
+// - bool: has_uvs
+// - [Vec2; vertex_count]: uvs, if has_uvs
+// - bool: has_colors
+// - [Rgb; vertex_count]: colors, if has_colors
+// - u32: index_count
+// - [u32; index_count]: indices, 3 per triangle
+
+// ris_mesh\0\0\0\0\0\0\0\0
+pub const MAGIC: [u8; 16] = [
+    0x72, 0x69, 0x73, 0x5f, 0x6d, 0x65, 0x73, 0x68, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+pub const EXTENSION: &str = "ris_mesh";
+
+/// the content of a ris_mesh asset. `normals`, `uvs` and `colors` are either empty or contain
+/// exactly one entry per position.
+#[derive(Debug, Default, Clone)]
+pub struct RisMesh {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<Vec2>,
+    pub colors: Vec<Rgb>,
+    pub indices: Vec<u32>,
+}
+
+impl RisMesh {
+    pub fn validate(&self) -> RisResult<()> {
+        let vertex_count = self.positions.len();
+
+        for (name, len) in [
+            ("normals", self.normals.len()),
+            ("uvs", self.uvs.len()),
+            ("colors", self.colors.len()),
+        ] {
+            if len != 0 && len != vertex_count {
+                return ris_error::new_result!(
+                    "expected {} {}, but found {}",
+                    vertex_count,
+                    name,
+                    len,
+                );
+            }
+        }
+
+        if self.indices.len() % 3 != 0 {
+            return ris_error::new_result!(
+                "index count {} is not a multiple of 3",
+                self.indices.len(),
+            );
+        }
+
+        if let Some(index) = self.indices.iter().find(|x| **x as usize >= vertex_count) {
+            return ris_error::new_result!(
+                "index {} is out of bounds. vertex count: {}",
+                index,
+                vertex_count,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn serialize(&self) -> RisResult<Vec<u8>> {
+        self.validate()?;
+
+        let mut stream = Cursor::new(Vec::new());
+        let f = &mut stream;
+
+        let header = RisHeader::new(MAGIC, Vec::new());
+        let header_bytes = header.serialize()?;
+        ris_io::write(f, &header_bytes)?;
+
+        ris_io::write_uint(f, self.positions.len())?;
+        for position in self.positions.iter() {
+            ris_io::write_vec3(f, *position)?;
+        }
+
+        ris_io::write_bool(f, !self.normals.is_empty())?;
+        for normal in self.normals.iter() {
+            ris_io::write_vec3(f, *normal)?;
+        }
+
+        ris_io::write_bool(f, !self.uvs.is_empty())?;
+        for uv in self.uvs.iter() {
+            ris_io::write_vec2(f, *uv)?;
+        }
+
+        ris_io::write_bool(f, !self.colors.is_empty())?;
+        for color in self.colors.iter() {
+            ris_io::write_vec3(f, Vec3(color.0, color.1, color.2))?;
+        }
+
+        ris_io::write_uint(f, self.indices.len())?;
+        for index in self.indices.iter() {
+            ris_io::write_uint(f, *index as usize)?;
+        }
+
+        Ok(stream.into_inner())
+    }
+
+    pub fn load(bytes: &[u8]) -> RisResult<Self> {
+        let header = RisHeader::load(bytes)?.into_ris_error()?;
+        header.assert_magic(MAGIC)?;
+
+        let content = header.content(bytes)?;
+        let f = &mut Cursor::new(content);
+
+        let vertex_count = ris_io::read_uint(f)?;
+        let mut positions = Vec::new();
+        for _ in 0..vertex_count {
+            positions.push(ris_io::read_vec3(f)?);
+        }
+
+        let mut normals = Vec::new();
+        if ris_io::read_bool(f)? {
+            for _ in 0..vertex_count {
+                normals.push(ris_io::read_vec3(f)?);
+            }
+        }
+
+        let mut uvs = Vec::new();
+        if ris_io::read_bool(f)? {
+            for _ in 0..vertex_count {
+                uvs.push(ris_io::read_vec2(f)?);
+            }
+        }
+
+        let mut colors = Vec::new();
+        if ris_io::read_bool(f)? {
+            for _ in 0..vertex_count {
+                let Vec3(r, g, b) = ris_io::read_vec3(f)?;
+                colors.push(Rgb(r, g, b));
+            }
+        }
+
+        let index_count = ris_io::read_uint(f)?;
+        let mut indices = Vec::new();
+        for _ in 0..index_count {
+            indices.push(ris_io::read_uint(f)? as u32);
+        }
+
+        let mesh = Self {
+            positions,
+            normals,
+            uvs,
+            colors,
+            indices,
+        };
+        mesh.validate()?;
+
+        Ok(mesh)
+    }
+
+    /// converts to the vertex layout used by the renderer. missing uvs default to zero and
+    /// missing colors default to white. normals are not used by the renderer and are dropped.
+    pub fn into_mesh(self) -> Mesh {
+        let vertices = self
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(i, pos)| Vertex {
+                pos: *pos,
+                color: self.colors.get(i).copied().unwrap_or(Rgb(1.0, 1.0, 1.0)),
+                uv: self.uvs.get(i).copied().unwrap_or_default(),
+            })
+            .collect();
+
+        Mesh {
+            vertices,
+            indices: self.indices,
+        }
+    }
+}
+
+/// decodes a ris_mesh asset into a mesh, which can be uploaded to the gpu
+pub fn load(bytes: &[u8]) -> RisResult<Mesh> {
+    let ris_mesh = RisMesh::load(bytes)?;
+    Ok(ris_mesh.into_mesh())
+}
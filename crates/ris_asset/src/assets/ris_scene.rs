@@ -19,6 +19,17 @@ pub const EXTENSION: &str = "ris_scene";
 
 pub const COMPRESSION_LEVEL: u8 = 6;
 
+/// the largest a decompressed scene may be
+pub const MAX_DECOMPRESSED_LEN: usize = 1 << 28;
+/// the largest a single length prefix in a scene may be
+pub const MAX_LEN: usize = 1 << 24;
+
+// the least bytes a serialized game object takes: name len, is_active, local_position,
+// local_rotation, local_scale, component count and child count
+const MIN_GAME_OBJECT_SIZE: usize = 4 + 1 + 12 + 16 + 4 + 4 + 4;
+const MIN_COMPONENT_SIZE: usize = ris_io::ADDR_SIZE * 2;
+const MIN_CHILD_SIZE: usize = 4;
+
 pub fn serialize(scene: &Scene, chunk_index: Option<usize>) -> RisResult<Vec<u8>> {
     let (handles, chunk_index) = match chunk_index {
         Some(chunk_index) => {
@@ -118,19 +129,30 @@ pub fn load(scene: &Scene, bytes: &[u8]) -> RisResult<Option<usize>> {
         return Ok(None);
     };
 
+    let result = load_into_chunk(scene, index, bytes);
+    if result.is_err() {
+        // corrupted data may fail after some game objects were already created
+        scene.clear_chunk(index);
+    }
+
+    result.map(Some)
+}
+
+fn load_into_chunk(scene: &Scene, index: usize, bytes: &[u8]) -> RisResult<usize> {
     let chunk = &scene.static_chunks[index];
 
     let header = RisHeader::load(bytes)?.into_ris_error()?;
     header.assert_magic(MAGIC)?;
 
     let content = header.content(bytes)?;
-    let uncompressed = miniz_oxide::inflate::decompress_to_vec(content)
-        .map_err(|e| ris_error::new!("failed to decompress: {:?}", e))?;
+    let uncompressed =
+        miniz_oxide::inflate::decompress_to_vec_with_limit(content, MAX_DECOMPRESSED_LEN)
+            .map_err(|e| ris_error::new!("failed to decompress: {:?}", e))?;
 
-    let mut stream = SceneReader::new(index, scene, uncompressed, header.references);
+    let mut stream = SceneReader::new(index, scene, uncompressed, header.references, MAX_LEN)?;
     let f = &mut stream;
 
-    let game_object_count = ris_io::read_uint(f)?;
+    let game_object_count = f.read_len(MIN_GAME_OBJECT_SIZE)?;
 
     f.lookup = Vec::with_capacity(game_object_count);
     let mut children_to_assign = Vec::with_capacity(game_object_count);
@@ -138,22 +160,22 @@ pub fn load(scene: &Scene, bytes: &[u8]) -> RisResult<Option<usize>> {
 
     // deserialize game objects
     for _ in 0..game_object_count {
-        let name = ris_io::read_string(f)?;
+        let name = f.read_string()?;
         let is_active = ris_io::read_bool(f)?;
         let local_position = ris_io::read_vec3(f)?;
         let local_rotation = ris_io::read_quat(f)?;
         let local_scale = ris_io::read_f32(f)?;
 
-        let component_count = ris_io::read_uint(f)?;
+        let component_count = f.read_len(MIN_COMPONENT_SIZE)?;
         let mut component_ptrs = Vec::with_capacity(component_count);
         for _ in 0..component_count {
-            let ptr = ris_io::read_fat_ptr(f)?;
+            let ptr = f.read_fat_ptr()?;
             component_ptrs.push(ptr);
 
             ris_io::seek(f, SeekFrom::Current(ptr.len.try_into()?))?;
         }
 
-        let child_count = ris_io::read_uint(f)?;
+        let child_count = f.read_len(MIN_CHILD_SIZE)?;
         let mut child_ids = Vec::with_capacity(child_count);
         for _ in 0..child_count {
             let child_id = ris_io::read_uint(f)?;
@@ -209,5 +231,5 @@ pub fn load(scene: &Scene, bytes: &[u8]) -> RisResult<Option<usize>> {
         }
     }
 
-    Ok(Some(index))
+    Ok(index)
 }
@@ -0,0 +1,171 @@
+use std::io::Cursor;
+
+use ris_data::asset_id::AssetId;
+use ris_error::Extensions;
+use ris_error::RisResult;
+
+use super::ris_header::RisHeader;
+
+// # File Format
+//
+// encoding: little-endian
+//
+// - RisHeader: references to the spirv of all variants, in the order they appear below
+// - u32: keyword_count
+// - [string; keyword_count]: keywords
+// - [u8; 1 << keyword_count]: stages of each variant, indexed by the mask of its keywords
+//   - bit 0: vertex, bit 1: geometry, bit 2: fragment
+
+// ris_variants\0\0\0\0
+pub const MAGIC: [u8; 16] = [
+    0x72, 0x69, 0x73, 0x5f, 0x76, 0x61, 0x72, 0x69, 0x61, 0x6e, 0x74, 0x73, 0x00, 0x00, 0x00, 0x00,
+];
+pub const EXTENSION: &str = "ris_variants";
+
+/// more keywords would result in an unreasonable amount of spirv files
+pub const MAX_KEYWORDS: usize = 8;
+
+const STAGE_VERTEX: u8 = 1 << 0;
+const STAGE_GEOMETRY: u8 = 1 << 1;
+const STAGE_FRAGMENT: u8 = 1 << 2;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShaderVariant {
+    pub vert: Option<AssetId>,
+    pub geom: Option<AssetId>,
+    pub frag: Option<AssetId>,
+}
+
+/// all permutations of a shader, which declares keywords with `#variant`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RisShaderVariants {
+    pub keywords: Vec<String>,
+    /// indexed by the mask of enabled keywords. bit `i` of the mask enables `keywords[i]`.
+    pub variants: Vec<ShaderVariant>,
+}
+
+impl RisShaderVariants {
+    /// returns the mask of a keyword set. the order of `keywords` does not matter.
+    pub fn mask(&self, keywords: &[&str]) -> RisResult<usize> {
+        let mut mask = 0;
+        for keyword in keywords {
+            let Some(i) = self.keywords.iter().position(|x| x == keyword) else {
+                return ris_error::new_result!(
+                    "unkown keyword \"{}\". available: {:?}",
+                    keyword,
+                    self.keywords,
+                );
+            };
+
+            mask |= 1 << i;
+        }
+
+        Ok(mask)
+    }
+
+    pub fn get(&self, keywords: &[&str]) -> RisResult<&ShaderVariant> {
+        let mask = self.mask(keywords)?;
+        self.variants.get(mask).into_ris_error()
+    }
+
+    pub fn serialize(&self) -> RisResult<Vec<u8>> {
+        self.validate()?;
+
+        let mut references = Vec::new();
+        let mut stages = Vec::with_capacity(self.variants.len());
+        for variant in self.variants.iter() {
+            let mut stage = 0;
+            for (id, bit) in [
+                (&variant.vert, STAGE_VERTEX),
+                (&variant.geom, STAGE_GEOMETRY),
+                (&variant.frag, STAGE_FRAGMENT),
+            ] {
+                if let Some(id) = id {
+                    references.push(id.clone());
+                    stage |= bit;
+                }
+            }
+
+            stages.push(stage);
+        }
+
+        let mut stream = Cursor::new(Vec::new());
+        let f = &mut stream;
+
+        let header = RisHeader::new(MAGIC, references);
+        let header_bytes = header.serialize()?;
+        ris_io::write(f, &header_bytes)?;
+
+        ris_io::write_uint(f, self.keywords.len())?;
+        for keyword in self.keywords.iter() {
+            ris_io::write_string(f, keyword)?;
+        }
+
+        ris_io::write(f, &stages)?;
+
+        Ok(stream.into_inner())
+    }
+
+    pub fn load(bytes: &[u8]) -> RisResult<Self> {
+        let header = RisHeader::load(bytes)?.into_ris_error()?;
+        header.assert_magic(MAGIC)?;
+
+        let content = header.content(bytes)?;
+        let f = &mut Cursor::new(content);
+
+        let keyword_count = ris_io::read_uint(f)?;
+        if keyword_count > MAX_KEYWORDS {
+            return ris_error::new_result!("too many keywords: {}", keyword_count);
+        }
+
+        let mut keywords = Vec::with_capacity(keyword_count);
+        for _ in 0..keyword_count {
+            keywords.push(ris_io::read_string(f)?);
+        }
+
+        let mut stages = vec![0; 1 << keyword_count];
+        ris_io::read(f, &mut stages)?;
+
+        let mut references = header.references.into_iter();
+        let mut variants = Vec::with_capacity(stages.len());
+        for stage in stages {
+            let mut next = |bit: u8| -> RisResult<Option<AssetId>> {
+                if stage & bit == 0 {
+                    Ok(None)
+                } else {
+                    Ok(Some(references.next().into_ris_error()?))
+                }
+            };
+
+            variants.push(ShaderVariant {
+                vert: next(STAGE_VERTEX)?,
+                geom: next(STAGE_GEOMETRY)?,
+                frag: next(STAGE_FRAGMENT)?,
+            });
+        }
+
+        if references.next().is_some() {
+            return ris_error::new_result!("more references than variants");
+        }
+
+        let result = Self { keywords, variants };
+        result.validate()?;
+        Ok(result)
+    }
+
+    fn validate(&self) -> RisResult<()> {
+        if self.keywords.len() > MAX_KEYWORDS {
+            return ris_error::new_result!("too many keywords: {}", self.keywords.len());
+        }
+
+        if self.variants.len() != 1 << self.keywords.len() {
+            return ris_error::new_result!(
+                "expected {} variants, but got {}",
+                1 << self.keywords.len(),
+                self.variants.len(),
+            );
+        }
+
+        Ok(())
+    }
+}
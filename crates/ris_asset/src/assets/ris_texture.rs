@@ -0,0 +1,195 @@
+use std::io::Cursor;
+
+use ris_error::Extensions;
+use ris_error::RisResult;
+
+use crate::codecs::qoi;
+use crate::codecs::qoi::Channels;
+use crate::codecs::qoi::QoiDesc;
+use crate::mipmap::MipLevel;
+
+use super::ris_header::RisHeader;
+
+// # File Format
+//
+// encoding: little-endian
+//
+// - RisHeader without references
+// - u8: color space (0 srgb, 1 linear)
+// - u8: wrap (0 repeat, 1 mirrored repeat, 2 clamp to edge)
+// - u8: filter (0 nearest, 1 linear)
+// - bool: premultiplied alpha
+// - u32: mip_count
+// - [mip; mip_count]: mip levels, starting with the largest
+//   - u32: size
+//   - [u8; size]: qoi encoded RGBA pixels
+
+// ris_texture\0\0\0\0\0
+pub const MAGIC: [u8; 16] = [
+    0x72, 0x69, 0x73, 0x5f, 0x74, 0x65, 0x78, 0x74, 0x75, 0x72, 0x65, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+pub const EXTENSION: &str = "ris_texture";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextureColorSpace {
+    #[default]
+    Srgb,
+    Linear,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextureWrap {
+    #[default]
+    Repeat,
+    MirroredRepeat,
+    ClampToEdge,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFilter {
+    Nearest,
+    #[default]
+    Linear,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RisTexture {
+    pub color_space: TextureColorSpace,
+    pub wrap: TextureWrap,
+    pub filter: TextureFilter,
+    pub premultiplied_alpha: bool,
+    pub mips: Vec<MipLevel>,
+}
+
+impl RisTexture {
+    pub fn width(&self) -> u32 {
+        self.mips.first().map(|x| x.width).unwrap_or(0)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.mips.first().map(|x| x.height).unwrap_or(0)
+    }
+
+    pub fn serialize(&self) -> RisResult<Vec<u8>> {
+        if self.mips.is_empty() {
+            return ris_error::new_result!("texture has no mip levels");
+        }
+
+        let mut stream = Cursor::new(Vec::new());
+        let f = &mut stream;
+
+        let header = RisHeader::new(MAGIC, Vec::new());
+        let header_bytes = header.serialize()?;
+        ris_io::write(f, &header_bytes)?;
+
+        let color_space = match self.color_space {
+            TextureColorSpace::Srgb => 0,
+            TextureColorSpace::Linear => 1,
+        };
+        let wrap = match self.wrap {
+            TextureWrap::Repeat => 0,
+            TextureWrap::MirroredRepeat => 1,
+            TextureWrap::ClampToEdge => 2,
+        };
+        let filter = match self.filter {
+            TextureFilter::Nearest => 0,
+            TextureFilter::Linear => 1,
+        };
+
+        ris_io::write_u8(f, color_space)?;
+        ris_io::write_u8(f, wrap)?;
+        ris_io::write_u8(f, filter)?;
+        ris_io::write_bool(f, self.premultiplied_alpha)?;
+
+        ris_io::write_uint(f, self.mips.len())?;
+        for mip in self.mips.iter() {
+            let desc = QoiDesc {
+                width: mip.width,
+                height: mip.height,
+                channels: Channels::RGBA,
+                color_space: match self.color_space {
+                    TextureColorSpace::Srgb => qoi::ColorSpace::SRGB,
+                    TextureColorSpace::Linear => qoi::ColorSpace::Linear,
+                },
+            };
+
+            let encoded = qoi::encode(&mip.pixels_rgba, desc)?;
+            ris_io::write_uint(f, encoded.len())?;
+            ris_io::write(f, &encoded)?;
+        }
+
+        Ok(stream.into_inner())
+    }
+
+    pub fn load(bytes: &[u8]) -> RisResult<Self> {
+        let header = RisHeader::load(bytes)?.into_ris_error()?;
+        header.assert_magic(MAGIC)?;
+
+        let content = header.content(bytes)?;
+        let f = &mut Cursor::new(content);
+
+        let color_space = match ris_io::read_u8(f)? {
+            0 => TextureColorSpace::Srgb,
+            1 => TextureColorSpace::Linear,
+            value => return ris_error::new_result!("invalid color space: {}", value),
+        };
+        let wrap = match ris_io::read_u8(f)? {
+            0 => TextureWrap::Repeat,
+            1 => TextureWrap::MirroredRepeat,
+            2 => TextureWrap::ClampToEdge,
+            value => return ris_error::new_result!("invalid wrap: {}", value),
+        };
+        let filter = match ris_io::read_u8(f)? {
+            0 => TextureFilter::Nearest,
+            1 => TextureFilter::Linear,
+            value => return ris_error::new_result!("invalid filter: {}", value),
+        };
+        let premultiplied_alpha = ris_io::read_bool(f)?;
+
+        let mip_count = ris_io::read_uint(f)?;
+        if mip_count == 0 {
+            return ris_error::new_result!("texture has no mip levels");
+        }
+
+        let mut mips = Vec::new();
+        for i in 0..mip_count {
+            let size = ris_io::read_uint(f)?;
+            let remaining = content.len() - f.position() as usize;
+            if size > remaining {
+                return ris_error::new_result!("mip {} is out of bounds", i);
+            }
+
+            let mut encoded = vec![0; size];
+            ris_io::read(f, &mut encoded)?;
+            let (pixels_rgba, desc) = qoi::decode(&encoded, Some(Channels::RGBA))?;
+
+            if let Some(previous) = mips.last() {
+                let MipLevel { width, height, .. } = previous;
+                if desc.width != (width / 2).max(1) || desc.height != (height / 2).max(1) {
+                    return ris_error::new_result!(
+                        "mip {} has an invalid size. {}x{} follows {}x{}",
+                        i,
+                        desc.width,
+                        desc.height,
+                        width,
+                        height,
+                    );
+                }
+            }
+
+            mips.push(MipLevel {
+                width: desc.width,
+                height: desc.height,
+                pixels_rgba,
+            });
+        }
+
+        Ok(Self {
+            color_space,
+            wrap,
+            filter,
+            premultiplied_alpha,
+            mips,
+        })
+    }
+}
@@ -0,0 +1,301 @@
+// minimal JSON parser, as specified by RFC 8259: https://www.rfc-editor.org/rfc/rfc8259
+// only parsing is supported, as it is only used to read files from other programs.
+
+use ris_error::RisResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// returns the value of `key`, if this is an object that contains it
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// returns the number, if it is a non negative integer
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(value) if *value >= 0.0 && value.fract() == 0.0 => {
+                Some(*value as usize)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// the parser recurses for each nested object and array. deeper input is rejected, instead of
+/// overflowing the stack.
+pub const MAX_DEPTH: usize = 128;
+
+pub fn parse(text: &str) -> RisResult<JsonValue> {
+    let mut parser = Parser {
+        bytes: text.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.position != parser.bytes.len() {
+        return parser.fail("unexpected trailing characters");
+    }
+
+    Ok(value)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn fail<T>(&self, message: &str) -> RisResult<T> {
+        ris_error::new_result!("failed to parse json: {} at {}", message, self.position)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> RisResult<u8> {
+        match self.peek() {
+            Some(byte) => {
+                self.position += 1;
+                Ok(byte)
+            }
+            None => self.fail("unexpected end"),
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> RisResult<()> {
+        let byte = self.next()?;
+        if byte == expected {
+            Ok(())
+        } else {
+            self.fail(&format!("expected '{}'", expected as char))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> RisResult<JsonValue> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.parse_nested(Self::parse_object),
+            Some(b'[') => self.parse_nested(Self::parse_array),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => self.fail("unexpected character"),
+            None => self.fail("unexpected end"),
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Self) -> RisResult<JsonValue>,
+    ) -> RisResult<JsonValue> {
+        if self.depth >= MAX_DEPTH {
+            return self.fail(&format!("nested deeper than {}", MAX_DEPTH));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> RisResult<JsonValue> {
+        let end = self.position + literal.len();
+        if self.bytes.get(self.position..end) == Some(literal.as_bytes()) {
+            self.position = end;
+            Ok(value)
+        } else {
+            self.fail(&format!("expected {}", literal))
+        }
+    }
+
+    fn parse_object(&mut self) -> RisResult<JsonValue> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+            members.push((key, value));
+
+            self.skip_whitespace();
+            match self.next()? {
+                b',' => continue,
+                b'}' => return Ok(JsonValue::Object(members)),
+                _ => return self.fail("expected ',' or '}'"),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> RisResult<JsonValue> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            let value = self.parse_value()?;
+            values.push(value);
+
+            self.skip_whitespace();
+            match self.next()? {
+                b',' => continue,
+                b']' => return Ok(JsonValue::Array(values)),
+                _ => return self.fail("expected ',' or ']'"),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> RisResult<String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.next()? {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.parse_unicode_escape()?,
+                        _ => return self.fail("invalid escape sequence"),
+                    };
+
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+                }
+                byte if byte < 0x20 => return self.fail("unescaped control character"),
+                byte => bytes.push(byte),
+            }
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(string) => Ok(string),
+            Err(_) => self.fail("invalid utf8"),
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> RisResult<char> {
+        let high = self.parse_hex4()?;
+
+        let code_point = if (0xd800..0xdc00).contains(&high) {
+            // surrogate pair
+            self.expect(b'\\')?;
+            self.expect(b'u')?;
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return self.fail("invalid low surrogate");
+            }
+
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+
+        match char::from_u32(code_point) {
+            Some(c) => Ok(c),
+            None => self.fail("invalid unicode escape"),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> RisResult<u32> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = match (self.next()? as char).to_digit(16) {
+                Some(digit) => digit,
+                None => return self.fail("invalid hex digit"),
+            };
+            value = value * 16 + digit;
+        }
+
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> RisResult<JsonValue> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+
+        let slice = &self.bytes[start..self.position];
+        let parsed = std::str::from_utf8(slice)
+            .ok()
+            .and_then(|x| x.parse::<f64>().ok());
+
+        match parsed {
+            Some(number) => Ok(JsonValue::Number(number)),
+            None => self.fail("invalid number"),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 // original format and C encoder/decoder by Dominic Szablewski: https://qoiformat.org/
 
 use std::io::Cursor;
+use std::io::Read;
+use std::io::Write;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct QoiDesc {
@@ -117,6 +119,8 @@ pub enum DecodeErrorKind {
     IncorrectMagic,
     DescWidthIsZero,
     DescHeightIsZero,
+    BufferDoesNotMatchDimensions,
+    IncorrectPadding,
     IoError(std::io::Error),
     InvalidCast(String),
 }
@@ -173,13 +177,17 @@ impl std::fmt::Display for DecodeError {
             DecodeErrorKind::IncorrectMagic => write!(f, "magic must be {:?}", MAGIC),
             DecodeErrorKind::DescWidthIsZero => write!(f, "decoded header width was 0"),
             DecodeErrorKind::DescHeightIsZero => write!(f, "decoded header height was 0"),
+            DecodeErrorKind::BufferDoesNotMatchDimensions => {
+                write!(f, "buffer must have length of width * height * channels")
+            }
+            DecodeErrorKind::IncorrectPadding => write!(f, "padding must be {:?}", PADDING),
             DecodeErrorKind::IoError(e) => write!(f, "io error occured: {}", e),
             DecodeErrorKind::InvalidCast(e) => write!(f, "invalid cast: {}", e),
         }
     }
 }
 
-pub fn encode(data: &[u8], desc: QoiDesc) -> Result<Vec<u8>, EncodeError> {
+fn validate_desc(desc: QoiDesc) -> Result<(), EncodeError> {
     if desc.width == 0 {
         return Err(EncodeError {
             kind: EncodeErrorKind::WidthIsZero,
@@ -198,15 +206,28 @@ pub fn encode(data: &[u8], desc: QoiDesc) -> Result<Vec<u8>, EncodeError> {
         });
     }
 
+    Ok(())
+}
+
+fn header_bytes(desc: QoiDesc) -> [u8; HEADER_SIZE as usize] {
+    let mut bytes = [0; HEADER_SIZE as usize];
+    bytes[0..4].copy_from_slice(&MAGIC);
+    bytes[4..8].copy_from_slice(&desc.width.to_be_bytes());
+    bytes[8..12].copy_from_slice(&desc.height.to_be_bytes());
+    bytes[12] = desc.channels as u8;
+    bytes[13] = desc.color_space as u8;
+    bytes
+}
+
+pub fn encode(data: &[u8], desc: QoiDesc) -> Result<Vec<u8>, EncodeError> {
+    validate_desc(desc)?;
+
     let max_size =
         desc.width * desc.height * (desc.channels as u32 + 1) + HEADER_SIZE + PADDING.len() as u32;
 
     let mut bytes = Cursor::new(Vec::with_capacity(max_size as usize));
 
-    ris_io::write(&mut bytes, &MAGIC)?;
-    ris_io::write(&mut bytes, &desc.width.to_be_bytes())?;
-    ris_io::write(&mut bytes, &desc.height.to_be_bytes())?;
-    ris_io::write(&mut bytes, &[desc.channels as u8, desc.color_space as u8])?;
+    ris_io::write(&mut bytes, &header_bytes(desc))?;
 
     let pixels = data;
 
@@ -292,21 +313,13 @@ pub fn encode(data: &[u8], desc: QoiDesc) -> Result<Vec<u8>, EncodeError> {
     Ok(result)
 }
 
-pub fn decode(data: &[u8], channels: Option<Channels>) -> Result<(Vec<u8>, QoiDesc), DecodeError> {
-    if data.len() < DATA_MIN {
-        return Err(DecodeError {
-            kind: DecodeErrorKind::DataToSmall,
-        });
-    }
-
-    let mut bytes = &mut Cursor::new(data);
-
+fn read_header(reader: &mut impl Read) -> Result<QoiDesc, DecodeError> {
     let mut header_magic_bytes = [0; 4];
     let mut width_bytes = [0; 4];
     let mut height_bytes = [0; 4];
-    ris_io::read(&mut bytes, &mut header_magic_bytes)?;
-    ris_io::read(&mut bytes, &mut width_bytes)?;
-    ris_io::read(&mut bytes, &mut height_bytes)?;
+    ris_io::read(reader, &mut header_magic_bytes)?;
+    ris_io::read(reader, &mut width_bytes)?;
+    ris_io::read(reader, &mut height_bytes)?;
 
     let width = u32::from_be_bytes(width_bytes);
     let height = u32::from_be_bytes(height_bytes);
@@ -329,12 +342,23 @@ pub fn decode(data: &[u8], channels: Option<Channels>) -> Result<(Vec<u8>, QoiDe
         });
     }
 
-    let desc = QoiDesc {
+    Ok(QoiDesc {
         width,
         height,
-        channels: ris_io::read_u8(bytes)?.try_into()?,
-        color_space: ris_io::read_u8(bytes)?.try_into()?,
-    };
+        channels: ris_io::read_u8(reader)?.try_into()?,
+        color_space: ris_io::read_u8(reader)?.try_into()?,
+    })
+}
+
+pub fn decode(data: &[u8], channels: Option<Channels>) -> Result<(Vec<u8>, QoiDesc), DecodeError> {
+    if data.len() < DATA_MIN {
+        return Err(DecodeError {
+            kind: DecodeErrorKind::DataToSmall,
+        });
+    }
+
+    let bytes = &mut Cursor::new(data);
+    let desc = read_header(bytes)?;
 
     let channels = match channels {
         Some(x) => x,
@@ -397,3 +421,410 @@ pub fn decode(data: &[u8], channels: Option<Channels>) -> Result<(Vec<u8>, QoiDe
     let result = pixels.into_inner();
     Ok((result, desc))
 }
+
+/// writes a qoi image incrementally. pixels may be passed in chunks of any size, even if a chunk
+/// ends in the middle of a pixel. produces the same bytes as `encode()`.
+pub struct QoiEncoder<W: Write> {
+    writer: W,
+    desc: QoiDesc,
+    index: [Rgba; 64],
+    px_prev: Rgba,
+    run: u8,
+    pixels_left: usize,
+    partial: [u8; 4],
+    partial_len: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> QoiEncoder<W> {
+    /// writes the header immediately
+    pub fn new(mut writer: W, desc: QoiDesc) -> Result<Self, EncodeError> {
+        validate_desc(desc)?;
+        writer.write_all(&header_bytes(desc))?;
+
+        Ok(Self {
+            writer,
+            desc,
+            index: [Rgba::default(); 64],
+            px_prev: Rgba::from_bytes(&[0, 0, 0, 255]),
+            run: 0,
+            pixels_left: desc.width as usize * desc.height as usize,
+            partial: [0; 4],
+            partial_len: 0,
+            buf: Vec::new(),
+        })
+    }
+
+    pub fn desc(&self) -> QoiDesc {
+        self.desc
+    }
+
+    /// the number of bytes that still must be written, before the encoder can be finished
+    pub fn bytes_left(&self) -> usize {
+        self.pixels_left * self.desc.channels as usize - self.partial_len
+    }
+
+    pub fn write_pixels(&mut self, data: &[u8]) -> Result<(), EncodeError> {
+        if data.len() > self.bytes_left() {
+            return Err(EncodeError {
+                kind: EncodeErrorKind::DataDoesNotMatchDimensions,
+            });
+        }
+
+        let channels = self.desc.channels as usize;
+        let mut data = data;
+
+        if self.partial_len > 0 {
+            let count = usize::min(channels - self.partial_len, data.len());
+            self.partial[self.partial_len..self.partial_len + count]
+                .copy_from_slice(&data[..count]);
+            self.partial_len += count;
+            data = &data[count..];
+
+            if self.partial_len == channels {
+                let partial = self.partial;
+                self.encode_pixel(&partial[..channels]);
+                self.partial_len = 0;
+            }
+        }
+
+        let mut chunks = data.chunks_exact(channels);
+        for chunk in chunks.by_ref() {
+            self.encode_pixel(chunk);
+        }
+
+        let remainder = chunks.remainder();
+        self.partial[..remainder.len()].copy_from_slice(remainder);
+        self.partial_len += remainder.len();
+
+        self.writer.write_all(&self.buf)?;
+        self.buf.clear();
+
+        Ok(())
+    }
+
+    /// writes the end marker and returns the inner writer. fails if not all pixels have been
+    /// written.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        if self.bytes_left() != 0 {
+            return Err(EncodeError {
+                kind: EncodeErrorKind::DataDoesNotMatchDimensions,
+            });
+        }
+
+        self.writer.write_all(&PADDING)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn encode_pixel(&mut self, bytes: &[u8]) {
+        let mut px = self.px_prev;
+        px.r = bytes[0];
+        px.g = bytes[1];
+        px.b = bytes[2];
+
+        if bytes.len() == 4 {
+            px.a = bytes[3];
+        }
+
+        self.pixels_left -= 1;
+        let px_prev = self.px_prev;
+        self.px_prev = px;
+
+        if px == px_prev {
+            self.run += 1;
+            if self.run == 62 || self.pixels_left == 0 {
+                self.buf.push(OP_RUN | (self.run - 1));
+                self.run = 0;
+            }
+
+            return;
+        }
+
+        if self.run > 0 {
+            self.buf.push(OP_RUN | (self.run - 1));
+            self.run = 0;
+        }
+
+        let index_pos = px.hash() % 64;
+
+        if self.index[index_pos as usize] == px {
+            self.buf.push(OP_INDEX | index_pos);
+            return;
+        }
+
+        self.index[index_pos as usize] = px;
+
+        if px.a != px_prev.a {
+            self.buf.extend([OP_RGBA, px.r, px.g, px.b, px.a]);
+            return;
+        }
+
+        let vr = (px.r as i8).wrapping_sub(px_prev.r as i8);
+        let vg = (px.g as i8).wrapping_sub(px_prev.g as i8);
+        let vb = (px.b as i8).wrapping_sub(px_prev.b as i8);
+
+        let vg_r = vr.wrapping_sub(vg);
+        let vg_b = vb.wrapping_sub(vg);
+
+        if vr > -3 && vr < 2 && vg > -3 && vg < 2 && vb > -3 && vb < 2 {
+            let dr = ((vr + 2) << 4) as u8;
+            let dg = ((vg + 2) << 2) as u8;
+            let db = (vb + 2) as u8;
+            self.buf.push(OP_DIFF | dr | dg | db);
+        } else if vg_r > -9 && vg_r < 8 && vg > -33 && vg < 32 && vg_b > -9 && vg_b < 8 {
+            let dr = ((vg_r + 8) << 4) as u8;
+            let dg = (vg + 32) as u8;
+            let db = (vg_b + 8) as u8;
+            self.buf.extend([OP_LUMA | dg, dr | db]);
+        } else {
+            self.buf.extend([OP_RGB, px.r, px.g, px.b]);
+        }
+    }
+}
+
+impl<W: Write> Write for QoiEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_pixels(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// reads a qoi image incrementally. the reader is read one op at a time, thus unbuffered readers
+/// like files should be wrapped in a `BufReader`.
+///
+/// unlike `decode()`, a stream which ends before all pixels are decoded is an error.
+pub struct QoiDecoder<R: Read> {
+    reader: R,
+    desc: QoiDesc,
+    channels: Channels,
+    index: [Rgba; 64],
+    px: Rgba,
+    run: u8,
+    pixels_left: usize,
+    partial: [u8; 4],
+    partial_start: usize,
+    partial_end: usize,
+}
+
+impl<R: Read> QoiDecoder<R> {
+    /// reads the header immediately. `channels` overrides the channels of the decoded pixels, like
+    /// in `decode()`.
+    pub fn new(mut reader: R, channels: Option<Channels>) -> Result<Self, DecodeError> {
+        let desc = read_header(&mut reader)?;
+        let channels = channels.unwrap_or(desc.channels);
+
+        Ok(Self {
+            reader,
+            desc,
+            channels,
+            index: [Rgba::default(); 64],
+            px: Rgba::from_bytes(&[0, 0, 0, 255]),
+            run: 0,
+            pixels_left: desc.width as usize * desc.height as usize,
+            partial: [0; 4],
+            partial_start: 0,
+            partial_end: 0,
+        })
+    }
+
+    pub fn desc(&self) -> QoiDesc {
+        self.desc
+    }
+
+    /// the channels of the decoded pixels
+    pub fn channels(&self) -> Channels {
+        self.channels
+    }
+
+    /// the number of bytes a row of decoded pixels takes
+    pub fn row_len(&self) -> usize {
+        self.desc.width as usize * self.channels as usize
+    }
+
+    /// the number of decoded bytes that are yet to be read
+    pub fn bytes_left(&self) -> usize {
+        self.pixels_left * self.channels as usize + self.partial_end - self.partial_start
+    }
+
+    /// decodes pixels into `buf` and returns how many bytes were written. `buf` may end in the
+    /// middle of a pixel, the rest of it is returned by the next call. returns 0 once all pixels
+    /// have been read.
+    pub fn read_pixels(&mut self, buf: &mut [u8]) -> Result<usize, DecodeError> {
+        let channels = self.channels as usize;
+        let mut written = 0;
+
+        let pending = usize::min(self.partial_end - self.partial_start, buf.len());
+        buf[..pending]
+            .copy_from_slice(&self.partial[self.partial_start..self.partial_start + pending]);
+        self.partial_start += pending;
+        written += pending;
+
+        while self.pixels_left > 0 && buf.len() - written >= channels {
+            let px = self.decode_pixel()?;
+            let bytes = [px.r, px.g, px.b, px.a];
+            buf[written..written + channels].copy_from_slice(&bytes[..channels]);
+            written += channels;
+        }
+
+        if self.pixels_left > 0 && written < buf.len() {
+            let px = self.decode_pixel()?;
+            self.partial = [px.r, px.g, px.b, px.a];
+            let count = buf.len() - written;
+            buf[written..].copy_from_slice(&self.partial[..count]);
+            self.partial_start = count;
+            self.partial_end = channels;
+            written += count;
+        }
+
+        Ok(written)
+    }
+
+    /// decodes the remaining pixels one row at a time and passes each row to `callback`, together
+    /// with its y coordinate. the row buffer is reused between calls. if pixels of the current row
+    /// have already been read, the first row passed is only the rest of it.
+    pub fn decode_rows<E: From<DecodeError>>(
+        &mut self,
+        mut callback: impl FnMut(u32, &[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let row_len = self.row_len();
+        let total_len = row_len * self.desc.height as usize;
+        let mut row = vec![0; row_len];
+
+        while self.bytes_left() > 0 {
+            let offset = total_len - self.bytes_left();
+            let y = (offset / row_len) as u32;
+            let row_start = offset % row_len;
+
+            let mut filled = row_start;
+            while filled < row_len {
+                filled += self.read_pixels(&mut row[filled..])?;
+            }
+
+            callback(y, &row[row_start..])?;
+        }
+
+        Ok(())
+    }
+
+    /// reads the end marker and returns the inner reader, which is positioned right after the
+    /// image. fails if not all pixels have been read.
+    pub fn finish(mut self) -> Result<R, DecodeError> {
+        if self.bytes_left() != 0 {
+            return Err(DecodeError {
+                kind: DecodeErrorKind::BufferDoesNotMatchDimensions,
+            });
+        }
+
+        let mut padding = [0; PADDING.len()];
+        ris_io::read(&mut self.reader, &mut padding)?;
+        if padding != PADDING {
+            return Err(DecodeError {
+                kind: DecodeErrorKind::IncorrectPadding,
+            });
+        }
+
+        Ok(self.reader)
+    }
+
+    fn decode_pixel(&mut self) -> Result<Rgba, DecodeError> {
+        self.pixels_left -= 1;
+
+        if self.run > 0 {
+            self.run -= 1;
+            return Ok(self.px);
+        }
+
+        let reader = &mut self.reader;
+        let px = &mut self.px;
+        let b1 = ris_io::read_u8(reader)?;
+
+        if b1 == OP_RGB {
+            px.r = ris_io::read_u8(reader)?;
+            px.g = ris_io::read_u8(reader)?;
+            px.b = ris_io::read_u8(reader)?;
+        } else if b1 == OP_RGBA {
+            px.r = ris_io::read_u8(reader)?;
+            px.g = ris_io::read_u8(reader)?;
+            px.b = ris_io::read_u8(reader)?;
+            px.a = ris_io::read_u8(reader)?;
+        } else if (b1 & MASK_2) == OP_INDEX {
+            *px = self.index[b1 as usize];
+        } else if (b1 & MASK_2) == OP_DIFF {
+            px.r = px.r.wrapping_add((b1 >> 4) & 0x03).wrapping_sub(2);
+            px.g = px.g.wrapping_add((b1 >> 2) & 0x03).wrapping_sub(2);
+            px.b = px.b.wrapping_add(b1 & 0x03).wrapping_sub(2);
+        } else if (b1 & MASK_2) == OP_LUMA {
+            let b2 = ris_io::read_u8(reader)?;
+            let vg = (b1 & 0x3f).wrapping_sub(32);
+            px.r =
+                px.r.wrapping_add(vg.wrapping_sub(8).wrapping_add((b2 >> 4) & 0x0f));
+            px.g = px.g.wrapping_add(vg);
+            px.b =
+                px.b.wrapping_add(vg.wrapping_sub(8).wrapping_add(b2 & 0x0f));
+        } else if (b1 & MASK_2) == OP_RUN {
+            self.run = b1 & 0x3f;
+        }
+
+        let index_pos = px.hash() % 64;
+        self.index[index_pos as usize] = *px;
+
+        Ok(*px)
+    }
+}
+
+impl<R: Read> Read for QoiDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_pixels(buf)?)
+    }
+}
+
+/// like `decode()`, but writes the pixels into `buf` instead of allocating. `buf` must have a
+/// length of exactly width * height * channels.
+pub fn decode_into(
+    data: &[u8],
+    channels: Option<Channels>,
+    buf: &mut [u8],
+) -> Result<QoiDesc, DecodeError> {
+    if data.len() < DATA_MIN {
+        return Err(DecodeError {
+            kind: DecodeErrorKind::DataToSmall,
+        });
+    }
+
+    let mut decoder = QoiDecoder::new(data, channels)?;
+    if buf.len() != decoder.bytes_left() {
+        return Err(DecodeError {
+            kind: DecodeErrorKind::BufferDoesNotMatchDimensions,
+        });
+    }
+
+    let mut written = 0;
+    while written < buf.len() {
+        written += decoder.read_pixels(&mut buf[written..])?;
+    }
+
+    Ok(decoder.desc())
+}
+
+impl From<EncodeError> for std::io::Error {
+    fn from(value: EncodeError) -> Self {
+        match value.kind {
+            EncodeErrorKind::IoError(e) => e,
+            kind => Self::new(std::io::ErrorKind::InvalidInput, EncodeError { kind }),
+        }
+    }
+}
+
+impl From<DecodeError> for std::io::Error {
+    fn from(value: DecodeError) -> Self {
+        match value.kind {
+            DecodeErrorKind::IoError(e) => e,
+            kind => Self::new(std::io::ErrorKind::InvalidData, DecodeError { kind }),
+        }
+    }
+}
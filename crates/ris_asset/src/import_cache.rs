@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ris_error::RisError;
+use ris_error::RisResult;
+
+// # File Format
+//
+// encoding: little-endian
+//
+// - [u8; 16]: magic `ris_import_cache`
+// - u32: entry_count
+// - [entry; entry_count]: entries
+//   - sized String: source path, relative to the source directory
+//   - u64: source hash
+//   - u32: importer version
+//   - u32: target_count
+//   - [sized String; target_count]: target paths, relative to the target directory
+//   - u32: dependency_count
+//   - [dependency; dependency_count]:
+//     - sized String: dependency path
+//     - u64: dependency hash
+
+pub const MAGIC: [u8; 16] = [
+    0x72, 0x69, 0x73, 0x5f, 0x69, 0x6d, 0x70, 0x6f, 0x72, 0x74, 0x5f, 0x63, 0x61, 0x63, 0x68, 0x65,
+];
+
+/// the name of the cache file. it is stored in the target directory of the importer.
+pub const FILE_NAME: &str = ".ris_import_cache";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCacheEntry {
+    pub source_hash: u64,
+    pub importer_version: u32,
+    pub targets: Vec<String>,
+    pub dependencies: Vec<(String, u64)>,
+}
+
+/// remembers which raw assets were imported with which importer, such that assets that have
+/// not changed since the last import can be skipped.
+#[derive(Debug, Default)]
+pub struct ImportCache {
+    entries: HashMap<String, ImportCacheEntry>,
+}
+
+impl ImportCache {
+    pub fn path(target_directory: &Path) -> PathBuf {
+        target_directory.join(FILE_NAME)
+    }
+
+    /// loads the cache from the target directory. a cache that is missing or cannot be read is
+    /// treated as empty, which simply causes all assets to be imported.
+    pub fn load(target_directory: &Path) -> Self {
+        let path = Self::path(target_directory);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        let result = std::fs::read(&path)
+            .map_err(RisError::from)
+            .and_then(|bytes| Self::deserialize(&bytes));
+
+        match result {
+            Ok(cache) => cache,
+            Err(e) => {
+                ris_log::warning!(
+                    "failed to load import cache \"{}\". importing everything. error: {}",
+                    ris_io::path::to_str(&path),
+                    e,
+                );
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, target_directory: &Path) -> RisResult<()> {
+        std::fs::create_dir_all(target_directory)?;
+
+        let path = Self::path(target_directory);
+        let bytes = self.serialize()?;
+        let mut file = File::create(path)?;
+        ris_io::write(&mut file, &bytes)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, source: &str) -> Option<&ImportCacheEntry> {
+        self.entries.get(source)
+    }
+
+    pub fn insert(&mut self, source: String, entry: ImportCacheEntry) {
+        self.entries.insert(source, entry);
+    }
+
+    pub fn remove(&mut self, source: &str) -> Option<ImportCacheEntry> {
+        self.entries.remove(source)
+    }
+
+    pub fn sources(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+
+    /// returns whether the entry of `source` is missing or out of date. an entry is out of date
+    /// when the source or one of its dependencies changed, the importer was updated or one of
+    /// the imported files was deleted.
+    pub fn is_stale(
+        &self,
+        source: &str,
+        source_hash: u64,
+        importer_version: u32,
+        target_directory: &Path,
+    ) -> bool {
+        let Some(entry) = self.entries.get(source) else {
+            return true;
+        };
+
+        if entry.source_hash != source_hash || entry.importer_version != importer_version {
+            return true;
+        }
+
+        for target in entry.targets.iter() {
+            if !target_directory.join(target).exists() {
+                return true;
+            }
+        }
+
+        for (dependency, dependency_hash) in entry.dependencies.iter() {
+            match hash_dependency(Path::new(dependency)) {
+                Ok(hash) if hash == *dependency_hash => (),
+                _ => return true,
+            }
+        }
+
+        false
+    }
+
+    pub fn serialize(&self) -> RisResult<Vec<u8>> {
+        let mut stream = Cursor::new(Vec::new());
+        let f = &mut stream;
+
+        // sort, such that the same cache always produces the same file
+        let mut sources = self.entries.keys().collect::<Vec<_>>();
+        sources.sort();
+
+        ris_io::write(f, &MAGIC)?;
+        ris_io::write_uint(f, sources.len())?;
+        for source in sources {
+            let entry = &self.entries[source];
+
+            ris_io::write_string(f, source)?;
+            ris_io::write_u64(f, entry.source_hash)?;
+            ris_io::write_uint(f, entry.importer_version as usize)?;
+            ris_io::write_uint(f, entry.targets.len())?;
+            for target in entry.targets.iter() {
+                ris_io::write_string(f, target)?;
+            }
+            ris_io::write_uint(f, entry.dependencies.len())?;
+            for (dependency, dependency_hash) in entry.dependencies.iter() {
+                ris_io::write_string(f, dependency)?;
+                ris_io::write_u64(f, *dependency_hash)?;
+            }
+        }
+
+        Ok(stream.into_inner())
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> RisResult<Self> {
+        let f = &mut Cursor::new(bytes);
+
+        let mut magic = [0; 16];
+        ris_io::read(f, &mut magic)?;
+        if magic != MAGIC {
+            return ris_error::new_result!("invalid magic: {:?}", magic);
+        }
+
+        let mut entries = HashMap::new();
+        let entry_count = ris_io::read_uint(f)?;
+        for _ in 0..entry_count {
+            let source = ris_io::read_string(f)?;
+            let source_hash = ris_io::read_u64(f)?;
+            let importer_version = ris_io::read_uint(f)? as u32;
+
+            let target_count = ris_io::read_uint(f)?;
+            let mut targets = Vec::new();
+            for _ in 0..target_count {
+                targets.push(ris_io::read_string(f)?);
+            }
+
+            let dependency_count = ris_io::read_uint(f)?;
+            let mut dependencies = Vec::new();
+            for _ in 0..dependency_count {
+                let dependency = ris_io::read_string(f)?;
+                let dependency_hash = ris_io::read_u64(f)?;
+                dependencies.push((dependency, dependency_hash));
+            }
+
+            let entry = ImportCacheEntry {
+                source_hash,
+                importer_version,
+                targets,
+                dependencies,
+            };
+            entries.insert(source, entry);
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+/// 64 bit FNV-1a. unlike the hashers of std, this hash is guaranteed to be stable between builds,
+/// which is required because it is persisted in the cache file.
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+pub fn hash_file(path: &Path) -> RisResult<u64> {
+    let bytes = std::fs::read(path)?;
+    Ok(hash(&bytes))
+}
+
+/// like `hash_file`, but a dependency that does not exist hashes to 0. this allows importers to
+/// depend on optional files, such that creating them causes an import.
+pub fn hash_dependency(path: &Path) -> RisResult<u64> {
+    if path.exists() {
+        hash_file(path)
+    } else {
+        Ok(0)
+    }
+}
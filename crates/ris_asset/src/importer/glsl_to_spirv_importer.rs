@@ -7,11 +7,20 @@ use std::path::PathBuf;
 
 use shaderc::CompilationArtifact;
 
+use ris_data::asset_id::AssetId;
 use ris_error::Extensions;
 use ris_error::RisResult;
 
+use crate::assets::ris_shader_variants;
+use crate::assets::ris_shader_variants::RisShaderVariants;
+use crate::assets::ris_shader_variants::ShaderVariant;
+use crate::spirv_reflection;
+
 pub const IN_EXT: &str = "glsl";
-pub const OUT_EXT: &[&str] = &["vert.spv", "geom.spv", "frag.spv"];
+/// the targets of a single variant. the last one is the reflection sidecar. see
+/// `spirv_reflection::serialize()`.
+pub const OUT_EXT: &[&str] = &["vert.spv", "geom.spv", "frag.spv", "reflection.ris_yaml"];
+pub const VERSION: u32 = 3;
 
 const PATH_PREFIX: &str = "assets/__raw/shaders";
 const NAME: &str = "glsl_to_spirv_importer";
@@ -28,6 +37,7 @@ const MACRO_FRAGMENT: &str = "#fragment";
 const MACRO_IO: &str = "#io";
 const MACRO_DEFINE: &str = "#define";
 const MACRO_INCLUDE: &str = "#include";
+const MACRO_VARIANT: &str = "#variant";
 
 const VERT: &str = "vert";
 const GEOM: &str = "geom";
@@ -61,9 +71,118 @@ impl std::fmt::Display for ShaderKind {
     }
 }
 
+/// a line in a file, which was fed into the preprocessor. lines start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: usize,
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// maps each line of a transpiled shader to the line it originated from
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    lines: Vec<SourceLocation>,
+}
+
+impl SourceMap {
+    pub fn push(&mut self, location: SourceLocation) {
+        self.lines.push(location);
+    }
+
+    /// `index` is 0 based
+    pub fn insert(&mut self, index: usize, location: SourceLocation) {
+        self.lines.insert(index, location);
+    }
+
+    /// `line` is 1 based, like in compiler messages
+    pub fn get(&self, line: usize) -> Option<&SourceLocation> {
+        let index = line.checked_sub(1)?;
+        self.lines.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// replaces every `transpiled_file:line` in `message` with the original location. locations
+    /// which are not in the map are left untouched.
+    pub fn rewrite(&self, transpiled_file: &str, message: &str) -> String {
+        let pattern = format!("{}:", transpiled_file);
+
+        let mut result = String::with_capacity(message.len());
+        let mut rest = message;
+        while let Some(index) = rest.find(&pattern) {
+            result.push_str(&rest[..index]);
+            let after = &rest[index + pattern.len()..];
+
+            let digit_count = after.chars().take_while(|x| x.is_ascii_digit()).count();
+            let digits = &after[..digit_count];
+            let location = digits.parse::<usize>().ok().and_then(|x| self.get(x));
+
+            match location {
+                Some(location) => result.push_str(&location.to_string()),
+                None => {
+                    result.push_str(&pattern);
+                    result.push_str(digits);
+                }
+            }
+
+            rest = &after[digit_count..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreprocessorErrorKind {
+    /// the files of the cycle. the first and the last file are the same.
+    IncludeCycle(Vec<String>),
+    /// an `#io` region between two stages, which are not declared or cannot be connected
+    UndefinedIo(String, String),
+    Other(String),
+}
+
+/// formatted like a compiler diagnostic: `file:line: error: message`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreprocessorError {
+    pub location: SourceLocation,
+    pub kind: PreprocessorErrorKind,
+}
+
+impl std::error::Error for PreprocessorError {}
+
+impl std::fmt::Display for PreprocessorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: error: ", self.location)?;
+
+        match &self.kind {
+            PreprocessorErrorKind::IncludeCycle(files) => {
+                write!(f, "include cycle: {}", files.join(" -> "))
+            }
+            PreprocessorErrorKind::UndefinedIo(i, o) => {
+                write!(f, "undefined io stage pair: {} -> {}", i, o)
+            }
+            PreprocessorErrorKind::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
 struct ShaderStage {
     kind: ShaderKind,
     source: Option<String>,
+    source_map: SourceMap,
 }
 
 struct Shader {
@@ -74,19 +193,38 @@ struct Shader {
 
 impl ShaderStage {
     pub fn new(kind: ShaderKind) -> Self {
-        Self { kind, source: None }
+        Self {
+            kind,
+            source: None,
+            source_map: SourceMap::default(),
+        }
     }
 
-    pub fn init(&mut self, version: &str) {
+    /// `location` is the line which declares the stage
+    pub fn init(&mut self, version: &str, location: &SourceLocation) {
         self.source = Some(format!(
             "#version {}\n#pragma shader_stage({})",
             version, self.kind,
         ));
+
+        self.source_map = SourceMap::default();
+        self.source_map.push(location.clone());
+        self.source_map.push(location.clone());
+    }
+
+    pub fn is_declared(&self) -> bool {
+        self.source.is_some()
     }
 
-    pub fn push(&mut self, line: &str, define_map: &HashMap<String, String>) {
+    pub fn push(
+        &mut self,
+        line: &str,
+        location: &SourceLocation,
+        define_map: &HashMap<String, String>,
+    ) {
         if let Some(shader) = &mut self.source {
             shader.push('\n');
+            self.source_map.push(location.clone());
 
             let mut to_push = line.to_string();
 
@@ -98,18 +236,32 @@ impl ShaderStage {
         }
     }
 
+    /// `defines` are the keywords of the variant to compile
     pub fn compile(
-        self,
+        &self,
         file: &str,
+        defines: &[&str],
         temp_dir: Option<&Path>,
         compiler: &shaderc::Compiler,
         options: &shaderc::CompileOptions,
     ) -> RisResult<Option<CompilationArtifact>> {
-        let source = match self.source {
+        let source = match &self.source {
             Some(source) => source,
             None => return Ok(None),
         };
 
+        // the defines must come after #version, which is the first line
+        let (version, rest) = source.split_once('\n').into_ris_error()?;
+        let mut source = String::from(version);
+        let mut source_map = self.source_map.clone();
+        let version_location = source_map.get(1).into_ris_error()?.clone();
+        for (i, define) in defines.iter().enumerate() {
+            source.push_str(&format!("\n#define {}", define));
+            source_map.insert(i + 1, version_location.clone());
+        }
+        source.push('\n');
+        source.push_str(rest);
+
         let file_path = PathBuf::from(file);
         let file_stem = file_path.file_stem().into_ris_error()?;
         let file_stem = file_stem.to_str().into_ris_error()?;
@@ -158,21 +310,100 @@ impl ShaderStage {
             .map_err(|e| {
                 let mut log_source = String::new();
                 for (i, line) in source.lines().enumerate() {
-                    log_source.push_str(&format!("{:>8} {}\n", i + 1, line));
+                    let location = match source_map.get(i + 1) {
+                        Some(location) => location.to_string(),
+                        None => String::new(),
+                    };
+                    log_source.push_str(&format!("{:>8} {:<48} {}\n", i + 1, location, line));
                 }
 
                 let base_message = format!("failed to compile shader \"{}\"", file);
-
-                ris_log::error!("{}\n\nsource:\n{}\nerror:\n{}", base_message, log_source, e,);
-
-                ris_error::new!("{}. check log for more infos.", base_message)
+                let diagnostics = source_map.rewrite(&file, &e.to_string());
+
+                ris_log::error!(
+                    "{}\n\nsource:\n{}\nerror:\n{}",
+                    base_message,
+                    log_source,
+                    diagnostics,
+                );
+
+                ris_error::new!(
+                    "{}:\n{}\ncheck log for more infos.",
+                    base_message,
+                    diagnostics.trim_end(),
+                )
             })?;
 
+        if artifact.get_num_warnings() > 0 {
+            let warnings = artifact.get_warning_messages();
+            ris_log::warning!(
+                "shader \"{}\" compiled with warnings:\n{}",
+                file,
+                source_map.rewrite(&file, &warnings),
+            );
+        }
+
         Ok(Some(artifact))
     }
 }
 
-pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -> RisResult<()> {
+/// returns the extensions of all targets of `source`: `OUT_EXT` for each variant, followed by
+/// the variant table. see `variant_stem()` for how variants are named.
+pub fn target_extensions(source: &Path) -> RisResult<Vec<String>> {
+    let file = ris_io::path::to_str(source);
+    let source_text = std::fs::read_to_string(source)?;
+    let keywords = parse_variants(&source_text, &file)?;
+
+    let mut result = Vec::new();
+    for mask in 0..1 << keywords.len() {
+        let suffix = variant_stem("", &keywords, mask);
+        for out_ext in OUT_EXT {
+            let extension = format!("{}.{}", suffix, out_ext);
+            result.push(extension.trim_start_matches('.').to_string());
+        }
+    }
+
+    result.push(ris_shader_variants::EXTENSION.to_string());
+    Ok(result)
+}
+
+/// the name of a variant is the stem of the shader, followed by its enabled keywords in the order
+/// they were declared, in lowercase. the variant of `default.glsl` with `#variant TEXTURED
+/// VERTEX_COLOR` and only `VERTEX_COLOR` enabled is `default.vertex_color`. the variant
+/// without keywords keeps the name of the shader.
+pub fn variant_stem(stem: &str, keywords: &[String], mask: usize) -> String {
+    let mut result = stem.to_string();
+    for (i, keyword) in keywords.iter().enumerate() {
+        if mask & (1 << i) != 0 {
+            result.push('.');
+            result.push_str(&keyword.to_lowercase());
+        }
+    }
+
+    result
+}
+
+/// returns the keywords declared by all `#variant` directives of a shader
+pub fn parse_variants(source_text: &str, file: &str) -> RisResult<Vec<String>> {
+    let mut keywords = Vec::new();
+    for (i, input_line) in source_text.lines().enumerate() {
+        let splits = input_line.split(' ').collect::<Vec<_>>();
+        if splits[0] == MACRO_VARIANT {
+            add_variant(&mut keywords, &splits, file, i + 1)?;
+        }
+    }
+
+    Ok(keywords)
+}
+
+/// returns the paths of all included files. `asset_root` is the directory, which the asset ids
+/// in the variant table are relative to.
+pub fn import(
+    source: PathBuf,
+    targets: Vec<PathBuf>,
+    asset_root: &Path,
+    temp_dir: Option<&Path>,
+) -> RisResult<Vec<PathBuf>> {
     // read file
     let file = source.to_str().into_ris_error()?;
 
@@ -193,14 +424,14 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
         first_line.starts_with(MAGIC),
         &format!("expected shader to start with \"{}\"", MAGIC),
         file,
-        0,
+        1,
     )?;
 
     let splits = first_line.split(' ').collect::<Vec<_>>();
     let second_paramter = splits.get(1);
     if let Some(parameter) = second_paramter {
         if *parameter == HEADER {
-            return Ok(());
+            return Ok(Vec::new());
         }
     }
 
@@ -208,10 +439,14 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
         splits.len() > 2,
         "ris_glsl must have 2 or more argument: one glsl version and which shaders this file contains",
         file,
-        0,
+        1,
     )?;
 
     let version = splits[1];
+    let first_location = SourceLocation {
+        file: file.to_string(),
+        line: 1,
+    };
 
     let mut shader = Shader {
         vert: ShaderStage::new(ShaderKind::Vertex),
@@ -221,11 +456,11 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
 
     for split in splits.iter().skip(2) {
         match *split {
-            VERTEX => shader.vert.init(version),
-            GEOMETRY => shader.geom.init(version),
-            FRAGMENT => shader.frag.init(version),
+            VERTEX => shader.vert.init(version, &first_location),
+            GEOMETRY => shader.geom.init(version, &first_location),
+            FRAGMENT => shader.frag.init(version, &first_location),
             value => {
-                return preproc_fail(&format!("invalid shaderkind value \"{}\"", value), file, 0)
+                return preproc_fail(&format!("invalid shaderkind value \"{}\"", value), file, 1)
             }
         }
     }
@@ -234,9 +469,14 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
     let mut current_region = Region::None;
     let mut already_included = Vec::new();
     let mut define_map = HashMap::new();
+    let mut keywords = Vec::new();
     let mut line = 1; // start at 1, because we skip the first line
     for input_line in source_text.lines().skip(1) {
         line += 1;
+        let location = SourceLocation {
+            file: file.to_string(),
+            line,
+        };
 
         let splits = input_line.split(' ').collect::<Vec<_>>();
         let first_split = splits[0];
@@ -249,12 +489,16 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
                 preproc_assert_arg_count(splits.len(), 3, file, line)?;
                 let i = string_to_region_kind(splits[1], file, line)?;
                 let o = string_to_region_kind(splits[2], file, line)?;
+                assert_io_is_defined(&i, &o, &shader, &location)?;
 
                 current_region = Region::IO(i, o);
             }
             MACRO_DEFINE => {
                 add_define(&mut define_map, &splits, file, line)?;
             }
+            MACRO_VARIANT => {
+                add_variant(&mut keywords, &splits, file, line)?;
+            }
             MACRO_INCLUDE => {
                 let file_path = PathBuf::from(file);
                 let root_dir = file_path.parent().into_ris_error()?;
@@ -268,27 +512,26 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
                     &mut already_included,
                     &mut dependency_history,
                     &mut define_map,
-                    file,
-                    line,
+                    &location,
                 )?;
 
-                add_content(
-                    &include_content,
-                    &current_region,
-                    &mut shader,
-                    &define_map,
-                    file,
-                    line,
-                )?;
+                for (content, content_location) in include_content {
+                    add_content(
+                        &content,
+                        &content_location,
+                        &current_region,
+                        &mut shader,
+                        &define_map,
+                    )?;
+                }
             }
             _ => {
                 add_content(
                     input_line,
+                    &location,
                     &current_region,
                     &mut shader,
                     &define_map,
-                    file,
-                    line,
                 )?;
             }
         }
@@ -300,32 +543,140 @@ pub fn import(source: PathBuf, targets: Vec<PathBuf>, temp_dir: Option<&Path>) -
     options.set_warnings_as_errors();
     options.set_optimization_level(shaderc::OptimizationLevel::Performance);
 
-    let mut artifacts = Vec::new();
+    let variant_count = 1 << keywords.len();
+    ris_error::assert!(targets.len() == variant_count * OUT_EXT.len() + 1)?;
+
+    let file_path = PathBuf::from(file);
+    let file_stem = file_path.file_stem().into_ris_error()?;
+    let file_stem = file_stem.to_str().into_ris_error()?;
+
+    let mut variants = Vec::with_capacity(variant_count);
+    for mask in 0..variant_count {
+        let defines = keywords
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, x)| x.as_str())
+            .collect::<Vec<_>>();
+
+        let variant_file = file_path.with_file_name(format!(
+            "{}.{}",
+            variant_stem(file_stem, &keywords, mask),
+            IN_EXT,
+        ));
+        let variant_file = ris_io::path::to_str(variant_file);
+        let variant_targets = &targets[mask * OUT_EXT.len()..(mask + 1) * OUT_EXT.len()];
+
+        // save to file
+        let mut ids = Vec::new();
+        let mut reflections = Vec::new();
+        for (stage, target) in [&shader.vert, &shader.geom, &shader.frag]
+            .into_iter()
+            .zip(variant_targets)
+        {
+            let artifact = stage.compile(&variant_file, &defines, temp_dir, &compiler, &options)?;
+            let Some(artifact) = artifact else {
+                ids.push(None);
+                continue;
+            };
+
+            let mut output = crate::asset_importer::create_file(target)?;
+            let bytes = artifact.as_binary_u8();
 
-    let vert_artifact = shader.vert.compile(file, temp_dir, &compiler, &options)?;
-    artifacts.push(vert_artifact);
+            ris_io::write(&mut output, bytes)?;
 
-    let geom_artifact = shader.geom.compile(file, temp_dir, &compiler, &options)?;
-    artifacts.push(geom_artifact);
+            let reflection = spirv_reflection::reflect(bytes)?;
+            reflections.push(reflection);
 
-    let frag_artifact = shader.frag.compile(file, temp_dir, &compiler, &options)?;
-    artifacts.push(frag_artifact);
+            ids.push(Some(asset_id(asset_root, target)?));
+        }
 
-    // save to file
-    debug_assert_eq!(artifacts.len(), targets.len());
-    for i in 0..artifacts.len() {
-        let artifact = &artifacts[i];
-        let target = &targets[i];
+        let reflection_yaml = spirv_reflection::serialize(&reflections)?;
+        let mut output = crate::asset_importer::create_file(&variant_targets[OUT_EXT.len() - 1])?;
+        ris_io::write(&mut output, reflection_yaml.as_bytes())?;
 
-        if let Some(artifact) = artifact {
-            let mut output = crate::asset_importer::create_file(target)?;
-            let bytes = artifact.as_binary_u8();
+        variants.push(ShaderVariant {
+            vert: ids[0].clone(),
+            geom: ids[1].clone(),
+            frag: ids[2].clone(),
+        });
+    }
 
-            ris_io::write(&mut output, bytes)?;
+    let table = RisShaderVariants { keywords, variants };
+    let bytes = table.serialize()?;
+    let mut output = crate::asset_importer::create_file(targets.last().into_ris_error()?)?;
+    ris_io::write(&mut output, &bytes)?;
+
+    Ok(already_included)
+}
+
+fn asset_id(asset_root: &Path, target: &Path) -> RisResult<AssetId> {
+    let relative = match target.strip_prefix(asset_root) {
+        Ok(relative) => relative,
+        Err(_) => {
+            return ris_error::new_result!(
+                "\"{}\" is not in \"{}\"",
+                ris_io::path::to_str(target),
+                ris_io::path::to_str(asset_root),
+            )
+        }
+    };
+
+    let id = ris_io::path::to_str(relative).replace('\\', "/");
+    Ok(AssetId::Path(id))
+}
+
+fn add_variant(
+    keywords: &mut Vec<String>,
+    splits: &[&str],
+    file: &str,
+    line: usize,
+) -> RisResult<()> {
+    preproc_assert(
+        splits.len() > 1,
+        "to few arguments for #variant",
+        file,
+        line,
+    )?;
+
+    for keyword in splits.iter().skip(1) {
+        if keyword.is_empty() {
+            continue;
         }
+
+        let is_valid = keyword
+            .chars()
+            .all(|x| x.is_ascii_uppercase() || x.is_ascii_digit() || x == '_')
+            && !keyword.starts_with(|x: char| x.is_ascii_digit());
+        preproc_assert(
+            is_valid,
+            &format!(
+                "variant keyword \"{}\" may only contain A-Z, 0-9 and _",
+                keyword
+            ),
+            file,
+            line,
+        )?;
+
+        preproc_assert(
+            !keywords.iter().any(|x| x == keyword),
+            &format!("variant keyword \"{}\" was already declared", keyword),
+            file,
+            line,
+        )?;
+
+        keywords.push(keyword.to_string());
     }
 
-    Ok(())
+    preproc_assert(
+        keywords.len() <= ris_shader_variants::MAX_KEYWORDS,
+        &format!(
+            "no more than {} variant keywords are supported",
+            ris_shader_variants::MAX_KEYWORDS
+        ),
+        file,
+        line,
+    )
 }
 
 fn string_to_region_kind(value: &str, file: &str, line: usize) -> RisResult<ShaderKind> {
@@ -360,49 +711,86 @@ fn preproc_assert(value: bool, message: &str, file: &str, line: usize) -> RisRes
 }
 
 fn preproc_fail<T>(message: &str, file: &str, line: usize) -> RisResult<T> {
-    ris_error::new_result!("preproc assert failed: {} in {}:{}", message, file, line,)
+    let location = SourceLocation {
+        file: file.to_string(),
+        line,
+    };
+    let kind = PreprocessorErrorKind::Other(message.to_string());
+    Err(PreprocessorError { location, kind }.into())
+}
+
+fn assert_io_is_defined(
+    i: &ShaderKind,
+    o: &ShaderKind,
+    shader: &Shader,
+    location: &SourceLocation,
+) -> RisResult<()> {
+    let is_declared = |kind: &ShaderKind| match kind {
+        ShaderKind::Vertex => shader.vert.is_declared(),
+        ShaderKind::Geometry => shader.geom.is_declared(),
+        ShaderKind::Fragment => shader.frag.is_declared(),
+    };
+
+    let can_connect = matches!(
+        (i, o),
+        (ShaderKind::Vertex, ShaderKind::Geometry)
+            | (ShaderKind::Vertex, ShaderKind::Fragment)
+            | (ShaderKind::Geometry, ShaderKind::Fragment)
+    );
+
+    if can_connect && is_declared(i) && is_declared(o) {
+        return Ok(());
+    }
+
+    let kind = PreprocessorErrorKind::UndefinedIo(i.to_string(), o.to_string());
+    Err(PreprocessorError {
+        location: location.clone(),
+        kind,
+    }
+    .into())
 }
 
+/// returns the lines of the included file, together with their location
 fn resolve_include(
     splits: &[&str],
     root_dir: &Path,
     already_included: &mut Vec<PathBuf>,
     dependency_history: &mut Vec<PathBuf>,
     define_map: &mut HashMap<String, String>,
-    file: &str,
-    line: usize,
-) -> RisResult<String> {
+    location: &SourceLocation,
+) -> RisResult<Vec<(String, SourceLocation)>> {
     // create path
-    preproc_assert_arg_count(splits.len(), 2, file, line)?;
+    preproc_assert_arg_count(splits.len(), 2, &location.file, location.line)?;
     let to_include = splits[1];
 
     let mut include_path = PathBuf::new();
     include_path.push(root_dir);
     include_path.push(to_include);
 
-    for path in already_included.iter() {
-        if include_path == *path {
-            return Ok(String::new());
+    // check for circular dependency. this must happen before skipping already included files,
+    // otherwise the cycle would go unnoticed.
+    if let Some(start) = dependency_history.iter().position(|x| *x == include_path) {
+        let files = dependency_history[start..]
+            .iter()
+            .chain(std::iter::once(&include_path))
+            .map(|x| ris_io::path::to_str(x).replace('\\', "/"))
+            .collect();
+
+        return Err(PreprocessorError {
+            location: location.clone(),
+            kind: PreprocessorErrorKind::IncludeCycle(files),
         }
+        .into());
     }
 
-    already_included.push(include_path.clone());
-
-    let file = include_path.to_str().into_ris_error()?;
-
-    // check for circular dependency
-    if dependency_history.iter().any(|x| *x == include_path) {
-        let mut error_message = String::from("circular dependency detected. history: \n");
-        error_message.push_str(&format!("0 {:?}", include_path));
-
-        for (i, dependency) in dependency_history.iter().rev().enumerate() {
-            error_message.push_str(&format!("\n{} {:?}", i + 1, dependency));
-        }
-
-        return ris_error::new_result!("{}", error_message);
+    if already_included.iter().any(|x| *x == include_path) {
+        return Ok(Vec::new());
     }
 
-    dependency_history.push(include_path.clone());
+    already_included.push(include_path.clone());
+
+    let file = ris_io::path::to_str(&include_path).replace('\\', "/");
+    let file = file.as_str();
 
     // read file
     let mut include_file = std::fs::File::open(&include_path)?;
@@ -417,16 +805,21 @@ fn resolve_include(
         first_line == magic,
         &format!("included headers must start with {}", magic),
         file,
-        0,
+        1,
     )?;
 
+    dependency_history.push(include_path.clone());
+
     // parse content
-    let include_path_comment = include_path.to_str().into_ris_error()?.replace('\\', "/");
-    let mut result = format!("//////// INCLUDE {}", include_path_comment);
+    let mut result = vec![(format!("//////// INCLUDE {}", file), location.clone())];
 
-    let mut line = 0;
+    let mut line = 1; // start at 1, because we skip the first line
     for input_line in file_content.lines().skip(1) {
         line += 1;
+        let input_location = SourceLocation {
+            file: file.to_string(),
+            line,
+        };
 
         let splits = input_line.split(' ').collect::<Vec<_>>();
         let first_split = splits[0];
@@ -435,6 +828,9 @@ fn resolve_include(
             MACRO_DEFINE => {
                 add_define(define_map, &splits, file, line)?;
             }
+            MACRO_VARIANT => {
+                return preproc_fail("#variant may not be used in headers", file, line);
+            }
             MACRO_INCLUDE => {
                 let include_content = resolve_include(
                     &splits,
@@ -442,21 +838,20 @@ fn resolve_include(
                     already_included,
                     dependency_history,
                     define_map,
-                    file,
-                    line,
+                    &input_location,
                 )?;
 
-                result.push('\n');
-                result.push_str(&include_content);
+                result.extend(include_content);
             }
             _ => {
-                result.push('\n');
-                result.push_str(input_line);
+                result.push((input_line.to_string(), input_location));
             }
         }
     }
 
-    result.push_str(&format!("\n//////// END {}", include_path_comment));
+    dependency_history.pop();
+
+    result.push((format!("//////// END {}", file), location.clone()));
     Ok(result)
 }
 
@@ -484,43 +879,46 @@ fn add_define(
 
 fn add_content(
     content: &str,
+    location: &SourceLocation,
     current_region: &Region,
     shader: &mut Shader,
     define_map: &HashMap<String, String>,
-    file: &str,
-    line: usize,
 ) -> RisResult<()> {
     match &current_region {
         Region::None => {
-            shader.vert.push(content, define_map);
-            shader.geom.push(content, define_map);
-            shader.frag.push(content, define_map);
+            shader.vert.push(content, location, define_map);
+            shader.geom.push(content, location, define_map);
+            shader.frag.push(content, location, define_map);
         }
-        Region::Shader(ShaderKind::Vertex) => shader.vert.push(content, define_map),
-        Region::Shader(ShaderKind::Geometry) => shader.geom.push(content, define_map),
-        Region::Shader(ShaderKind::Fragment) => shader.frag.push(content, define_map),
+        Region::Shader(ShaderKind::Vertex) => shader.vert.push(content, location, define_map),
+        Region::Shader(ShaderKind::Geometry) => shader.geom.push(content, location, define_map),
+        Region::Shader(ShaderKind::Fragment) => shader.frag.push(content, location, define_map),
         Region::IO(ShaderKind::Vertex, ShaderKind::Fragment) => {
             let vert_line = resolve_in_out(content, OUT, false);
             let frag_line = resolve_in_out(content, IN, false);
 
-            shader.vert.push(&vert_line, define_map);
-            shader.frag.push(&frag_line, define_map);
+            shader.vert.push(&vert_line, location, define_map);
+            shader.frag.push(&frag_line, location, define_map);
         }
         Region::IO(ShaderKind::Vertex, ShaderKind::Geometry) => {
             let vert_line = resolve_in_out(content, OUT, false);
             let geom_line = resolve_in_out(content, IN, true);
 
-            shader.vert.push(&vert_line, define_map);
-            shader.geom.push(&geom_line, define_map);
+            shader.vert.push(&vert_line, location, define_map);
+            shader.geom.push(&geom_line, location, define_map);
         }
         Region::IO(ShaderKind::Geometry, ShaderKind::Fragment) => {
             let geom_line = resolve_in_out(content, OUT, false);
             let frag_line = resolve_in_out(content, IN, false);
 
-            shader.geom.push(&geom_line, define_map);
-            shader.frag.push(&frag_line, define_map);
+            shader.geom.push(&geom_line, location, define_map);
+            shader.frag.push(&frag_line, location, define_map);
         }
-        region => preproc_fail(&format!("invalid region: {:?}", region), file, line)?,
+        region => preproc_fail(
+            &format!("invalid region: {:?}", region),
+            &location.file,
+            location.line,
+        )?,
     };
 
     Ok(())
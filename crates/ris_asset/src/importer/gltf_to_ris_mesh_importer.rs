@@ -0,0 +1,495 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use ris_error::Extensions;
+use ris_error::RisResult;
+use ris_math::color::Rgb;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+use crate::assets::ris_mesh::RisMesh;
+use crate::codecs::json;
+use crate::codecs::json::JsonValue;
+
+pub const IN_EXT_GLTF: &str = "gltf";
+pub const IN_EXT_GLB: &str = "glb";
+/// external buffers are read by the gltf file that references them, not imported on their own
+pub const BUFFER_EXT: &str = "bin";
+pub const OUT_EXT: &[&str] = &["ris_mesh"];
+pub const VERSION: u32 = 1;
+
+// glTF 2.0: https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html
+const GLB_MAGIC: u32 = 0x46546c67; // glTF
+const GLB_CHUNK_JSON: u32 = 0x4e4f534a; // JSON
+const GLB_CHUNK_BIN: u32 = 0x004e4942; // BIN\0
+
+const COMPONENT_TYPE_U8: usize = 5121;
+const COMPONENT_TYPE_U16: usize = 5123;
+const COMPONENT_TYPE_U32: usize = 5125;
+const COMPONENT_TYPE_F32: usize = 5126;
+
+const MODE_TRIANGLES: usize = 4;
+
+/// returns the paths of all files the import depended on, which are the external buffers
+/// referenced by the gltf file.
+pub fn import(source: PathBuf, targets: Vec<PathBuf>) -> RisResult<Vec<PathBuf>> {
+    let bytes = std::fs::read(&source)?;
+    let directory = source.parent().into_ris_error()?;
+    let (mesh, dependencies) = parse(&bytes, directory)?;
+    let bytes = mesh.serialize()?;
+
+    let mut output = crate::asset_importer::create_file(&targets[0])?;
+    ris_io::write(&mut output, &bytes)?;
+
+    Ok(dependencies)
+}
+
+/// parses a .gltf or .glb file. all triangle primitives of all meshes are merged into a single
+/// mesh. node transforms are not applied, thus the vertices are in the space of their meshes.
+/// `directory` is used to resolve external buffers. returns the mesh and the paths of the
+/// external buffers that were read.
+pub fn parse(bytes: &[u8], directory: &Path) -> RisResult<(RisMesh, Vec<PathBuf>)> {
+    let (document, glb_buffer) = if bytes.starts_with(&GLB_MAGIC.to_le_bytes()) {
+        parse_glb(bytes)?
+    } else {
+        let text = std::str::from_utf8(bytes)?;
+        (json::parse(text)?, None)
+    };
+
+    let mut dependencies = Vec::new();
+    let mut buffers = Vec::new();
+    for (i, buffer) in array(&document, "buffers").iter().enumerate() {
+        let byte_length = usize_field(buffer, "byteLength")?;
+
+        let data = match buffer.get("uri").and_then(JsonValue::as_str) {
+            Some(uri) => match uri.strip_prefix("data:") {
+                Some(data_uri) => {
+                    let Some((_, data)) = data_uri.split_once(";base64,") else {
+                        return ris_error::new_result!(
+                            "buffer {} has an unsupported data uri. only base64 is supported",
+                            i,
+                        );
+                    };
+                    decode_base64(data)?
+                }
+                None => {
+                    let path = directory.join(uri);
+                    let data = std::fs::read(&path)?;
+                    dependencies.push(path);
+                    data
+                }
+            },
+            // the first buffer of a glb may omit the uri and refer to the binary chunk instead
+            None => match (i, &glb_buffer) {
+                (0, Some(glb_buffer)) => glb_buffer.to_vec(),
+                _ => return ris_error::new_result!("buffer {} has no data", i),
+            },
+        };
+
+        if data.len() < byte_length {
+            return ris_error::new_result!(
+                "buffer {} is too small. expected {} bytes but got {}",
+                i,
+                byte_length,
+                data.len(),
+            );
+        }
+
+        buffers.push(data);
+    }
+
+    let gltf = Gltf {
+        document: &document,
+        buffers,
+    };
+
+    let mut mesh = RisMesh::default();
+    for gltf_mesh in array(&document, "meshes") {
+        for primitive in array(gltf_mesh, "primitives") {
+            let mode = match primitive.get("mode") {
+                Some(mode) => mode.as_usize().into_ris_error()?,
+                None => MODE_TRIANGLES,
+            };
+            if mode != MODE_TRIANGLES {
+                ris_log::warning!("skipped primitive with unsupported mode {}", mode);
+                continue;
+            }
+
+            gltf.append_primitive(primitive, &mut mesh)?;
+        }
+    }
+
+    mesh.validate()?;
+    Ok((mesh, dependencies))
+}
+
+struct Gltf<'a> {
+    document: &'a JsonValue,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl<'a> Gltf<'a> {
+    fn append_primitive(&self, primitive: &JsonValue, mesh: &mut RisMesh) -> RisResult<()> {
+        let attributes = primitive.get("attributes").into_ris_error()?;
+        let Some(position_accessor) = attributes.get("POSITION") else {
+            return ris_error::new_result!("primitive has no POSITION attribute");
+        };
+
+        let positions = self.read_floats(position_accessor.as_usize().into_ris_error()?, 3)?;
+        let vertex_count = positions.len() / 3;
+        let offset = mesh.positions.len();
+
+        let normals = self.read_attribute(attributes, "NORMAL", 3, vertex_count)?;
+        let uvs = self.read_attribute(attributes, "TEXCOORD_0", 2, vertex_count)?;
+        let colors = match attributes.get("COLOR_0") {
+            Some(accessor) => {
+                let colors = self.read_colors(accessor.as_usize().into_ris_error()?)?;
+                if colors.len() != vertex_count * 3 {
+                    return ris_error::new_result!(
+                        "attribute COLOR_0 has {} elements, but expected {}",
+                        colors.len() / 3,
+                        vertex_count,
+                    );
+                }
+
+                Some(colors)
+            }
+            None => None,
+        };
+
+        // a merged mesh has an attribute only if all its primitives have it
+        merge(&mut mesh.normals, normals, 3, offset, |x| {
+            Vec3(x[0], x[1], x[2])
+        });
+        merge(&mut mesh.uvs, uvs, 2, offset, |x| Vec2(x[0], x[1]));
+        merge(&mut mesh.colors, colors, 3, offset, |x| {
+            Rgb(x[0], x[1], x[2])
+        });
+
+        for position in positions.chunks_exact(3) {
+            mesh.positions
+                .push(Vec3(position[0], position[1], position[2]));
+        }
+
+        match primitive.get("indices") {
+            Some(accessor) => {
+                let indices = self.read_indices(accessor.as_usize().into_ris_error()?)?;
+                for index in indices {
+                    if index as usize >= vertex_count {
+                        return ris_error::new_result!(
+                            "index {} is out of bounds. vertex count: {}",
+                            index,
+                            vertex_count,
+                        );
+                    }
+
+                    mesh.indices.push(offset as u32 + index);
+                }
+            }
+            None => {
+                for index in 0..vertex_count {
+                    mesh.indices.push((offset + index) as u32);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_attribute(
+        &self,
+        attributes: &JsonValue,
+        name: &str,
+        components: usize,
+        vertex_count: usize,
+    ) -> RisResult<Option<Vec<f32>>> {
+        let Some(accessor) = attributes.get(name) else {
+            return Ok(None);
+        };
+
+        let values = self.read_floats(accessor.as_usize().into_ris_error()?, components)?;
+        if values.len() != vertex_count * components {
+            return ris_error::new_result!(
+                "attribute {} has {} elements, but expected {}",
+                name,
+                values.len() / components,
+                vertex_count,
+            );
+        }
+
+        Ok(Some(values))
+    }
+
+    fn read_floats(&self, accessor: usize, components: usize) -> RisResult<Vec<f32>> {
+        let view = self.accessor(accessor)?;
+        if view.components != components || view.component_type != COMPONENT_TYPE_F32 {
+            return ris_error::new_result!(
+                "accessor {} must contain {} floats per element",
+                accessor,
+                components,
+            );
+        }
+
+        let mut values = Vec::with_capacity(view.count * components);
+        for element in view.elements() {
+            for i in 0..components {
+                values.push(read_f32(element, i * 4));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// returns 3 floats per vertex. the alpha channel is dropped.
+    fn read_colors(&self, accessor: usize) -> RisResult<Vec<f32>> {
+        let view = self.accessor(accessor)?;
+        if view.components != 3 && view.components != 4 {
+            return ris_error::new_result!("accessor {} must contain VEC3 or VEC4", accessor);
+        }
+
+        let read: fn(&[u8]) -> f32 = match view.component_type {
+            COMPONENT_TYPE_F32 => |x| read_f32(x, 0),
+            COMPONENT_TYPE_U8 => |x| x[0] as f32 / u8::MAX as f32,
+            COMPONENT_TYPE_U16 => |x| u16::from_le_bytes([x[0], x[1]]) as f32 / u16::MAX as f32,
+            component_type => {
+                return ris_error::new_result!(
+                    "unsupported color component type {}",
+                    component_type,
+                )
+            }
+        };
+
+        let size = view.element_size / view.components;
+        let mut colors = Vec::with_capacity(view.count * 3);
+        for element in view.elements() {
+            for component in element.chunks_exact(size).take(3) {
+                colors.push(read(component));
+            }
+        }
+
+        Ok(colors)
+    }
+
+    fn read_indices(&self, accessor: usize) -> RisResult<Vec<u32>> {
+        let view = self.accessor(accessor)?;
+        if view.components != 1 {
+            return ris_error::new_result!("accessor {} must contain SCALAR", accessor);
+        }
+
+        let read: fn(&[u8]) -> u32 = match view.component_type {
+            COMPONENT_TYPE_U8 => |x| x[0] as u32,
+            COMPONENT_TYPE_U16 => |x| u16::from_le_bytes([x[0], x[1]]) as u32,
+            COMPONENT_TYPE_U32 => |x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]),
+            component_type => {
+                return ris_error::new_result!(
+                    "unsupported index component type {}",
+                    component_type,
+                )
+            }
+        };
+
+        Ok(view.elements().map(read).collect())
+    }
+
+    fn accessor(&self, index: usize) -> RisResult<AccessorView<'_>> {
+        let Some(accessor) = array(self.document, "accessors").get(index) else {
+            return ris_error::new_result!("accessor {} does not exist", index);
+        };
+
+        let count = usize_field(accessor, "count")?;
+        let component_type = usize_field(accessor, "componentType")?;
+        let components = match accessor.get("type").and_then(JsonValue::as_str) {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") => 4,
+            accessor_type => {
+                return ris_error::new_result!("unsupported accessor type {:?}", accessor_type)
+            }
+        };
+        let component_size = match component_type {
+            COMPONENT_TYPE_U8 => 1,
+            COMPONENT_TYPE_U16 => 2,
+            COMPONENT_TYPE_U32 | COMPONENT_TYPE_F32 => 4,
+            _ => return ris_error::new_result!("unsupported component type {}", component_type),
+        };
+        let element_size = components * component_size;
+
+        let Some(buffer_view_index) = accessor.get("bufferView") else {
+            return ris_error::new_result!("sparse or empty accessors are not supported");
+        };
+        let buffer_view_index = buffer_view_index.as_usize().into_ris_error()?;
+        let Some(buffer_view) = array(self.document, "bufferViews").get(buffer_view_index) else {
+            return ris_error::new_result!("buffer view {} does not exist", buffer_view_index);
+        };
+
+        let buffer_index = usize_field(buffer_view, "buffer")?;
+        let Some(buffer) = self.buffers.get(buffer_index) else {
+            return ris_error::new_result!("buffer {} does not exist", buffer_index);
+        };
+
+        let view_offset = optional_usize_field(buffer_view, "byteOffset")?;
+        let view_length = usize_field(buffer_view, "byteLength")?;
+        let accessor_offset = optional_usize_field(accessor, "byteOffset")?;
+        let stride = match optional_usize_field(buffer_view, "byteStride")? {
+            0 => element_size,
+            stride => stride,
+        };
+
+        // the values come from the file, thus the arithmetic must not overflow
+        let view_bytes = view_offset
+            .checked_add(view_length)
+            .and_then(|view_end| buffer.get(view_offset..view_end));
+        let Some(view_bytes) = view_bytes else {
+            return ris_error::new_result!("buffer view {} is out of bounds", buffer_view_index);
+        };
+
+        let required = match count {
+            0 => Some(0),
+            count => stride
+                .checked_mul(count - 1)
+                .and_then(|x| x.checked_add(accessor_offset))
+                .and_then(|x| x.checked_add(element_size)),
+        };
+        match required {
+            Some(required) if required <= view_bytes.len() => (),
+            _ => return ris_error::new_result!("accessor {} is out of bounds", index),
+        }
+
+        Ok(AccessorView {
+            bytes: &view_bytes[accessor_offset.min(view_bytes.len())..],
+            count,
+            stride,
+            element_size,
+            components,
+            component_type,
+        })
+    }
+}
+
+struct AccessorView<'a> {
+    bytes: &'a [u8],
+    count: usize,
+    stride: usize,
+    element_size: usize,
+    components: usize,
+    component_type: usize,
+}
+
+impl<'a> AccessorView<'a> {
+    fn elements(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.count).map(|i| {
+            let start = i * self.stride;
+            &self.bytes[start..start + self.element_size]
+        })
+    }
+}
+
+/// appends `values` to `target`, if all previous primitives had this attribute as well.
+/// otherwise the attribute is dropped from the merged mesh.
+fn merge<T>(
+    target: &mut Vec<T>,
+    values: Option<Vec<f32>>,
+    components: usize,
+    offset: usize,
+    convert: impl Fn(&[f32]) -> T,
+) {
+    match values {
+        Some(values) if target.len() == offset => {
+            target.extend(values.chunks_exact(components).map(convert));
+        }
+        _ => target.clear(),
+    }
+}
+
+fn parse_glb(bytes: &[u8]) -> RisResult<(JsonValue, Option<&[u8]>)> {
+    let read_u32 = |offset: usize| -> RisResult<u32> {
+        match bytes.get(offset..offset + 4) {
+            Some(x) => Ok(u32::from_le_bytes([x[0], x[1], x[2], x[3]])),
+            None => ris_error::new_result!("unexpected end of glb"),
+        }
+    };
+
+    let version = read_u32(4)?;
+    if version != 2 {
+        return ris_error::new_result!("unsupported glb version {}", version);
+    }
+
+    let length = (read_u32(8)? as usize).min(bytes.len());
+
+    let mut document = None;
+    let mut buffer = None;
+    let mut offset = 12;
+    while offset + 8 <= length {
+        let chunk_length = read_u32(offset)? as usize;
+        let chunk_type = read_u32(offset + 4)?;
+        let start = offset + 8;
+        let Some(chunk) = bytes.get(start..start + chunk_length) else {
+            return ris_error::new_result!("glb chunk is out of bounds");
+        };
+
+        match chunk_type {
+            GLB_CHUNK_JSON => document = Some(json::parse(std::str::from_utf8(chunk)?)?),
+            GLB_CHUNK_BIN => buffer = Some(chunk),
+            // unknown chunks must be ignored
+            _ => (),
+        }
+
+        offset = start + chunk_length;
+    }
+
+    match document {
+        Some(document) => Ok((document, buffer)),
+        None => ris_error::new_result!("glb has no json chunk"),
+    }
+}
+
+fn array<'a>(value: &'a JsonValue, key: &str) -> &'a [JsonValue] {
+    value.get(key).and_then(JsonValue::as_array).unwrap_or(&[])
+}
+
+fn usize_field(value: &JsonValue, key: &str) -> RisResult<usize> {
+    match value.get(key).and_then(JsonValue::as_usize) {
+        Some(value) => Ok(value),
+        None => ris_error::new_result!("expected \"{}\" to be a non negative integer", key),
+    }
+}
+
+fn optional_usize_field(value: &JsonValue, key: &str) -> RisResult<usize> {
+    match value.get(key) {
+        Some(_) => usize_field(value, key),
+        None => Ok(0),
+    }
+}
+
+fn read_f32(bytes: &[u8], offset: usize) -> f32 {
+    let x = &bytes[offset..offset + 4];
+    f32::from_le_bytes([x[0], x[1], x[2], x[3]])
+}
+
+fn decode_base64(data: &str) -> RisResult<Vec<u8>> {
+    let mut result = Vec::with_capacity(data.len() / 4 * 3);
+    let mut accumulator = 0u32;
+    let mut bits = 0;
+
+    for c in data.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return ris_error::new_result!("invalid base64 character '{}'", c as char),
+        };
+
+        accumulator = (accumulator << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((accumulator >> bits) as u8);
+        }
+    }
+
+    Ok(result)
+}
@@ -1,2 +1,4 @@
 pub mod glsl_to_spirv_importer;
-pub mod png_to_qoi_importer;
+pub mod gltf_to_ris_mesh_importer;
+pub mod obj_to_ris_mesh_importer;
+pub mod png_to_ris_texture_importer;
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use ris_error::RisResult;
+use ris_math::color::Rgb;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+use crate::assets::ris_mesh::RisMesh;
+
+pub const IN_EXT: &str = "obj";
+/// material libraries are ignored, but usually lie next to the obj file
+pub const MATERIAL_EXT: &str = "mtl";
+pub const OUT_EXT: &[&str] = &["ris_mesh"];
+pub const VERSION: u32 = 1;
+
+/// returns the paths of all files the import depended on. materials are not imported, thus
+/// this is always empty.
+pub fn import(source: PathBuf, targets: Vec<PathBuf>) -> RisResult<Vec<PathBuf>> {
+    let text = std::fs::read_to_string(&source)?;
+    let file = ris_io::path::to_str(&source);
+    let mesh = parse(&text, &file)?;
+    let bytes = mesh.serialize()?;
+
+    let mut output = crate::asset_importer::create_file(&targets[0])?;
+    ris_io::write(&mut output, &bytes)?;
+
+    Ok(Vec::new())
+}
+
+/// parses the geometry of a wavefront obj file. supports positions with optional vertex colors,
+/// normals, texture coordinates and polygonal faces, which are triangulated as a fan. all
+/// objects and groups are merged into a single mesh. `file` is only used for error messages.
+pub fn parse(text: &str, file: &str) -> RisResult<RisMesh> {
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+
+    let mut mesh = RisMesh::default();
+    let mut vertex_lookup = HashMap::new();
+    let mut has_uvs = None;
+    let mut has_normals = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+
+        let line = match line.find('#') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+
+        let mut splits = line.split_whitespace();
+        let Some(keyword) = splits.next() else {
+            continue;
+        };
+        let args = splits.collect::<Vec<_>>();
+
+        match keyword {
+            "v" => {
+                let values = parse_floats(&args, file, line_number)?;
+                match values.len() {
+                    3 | 4 => positions.push(Vec3(values[0], values[1], values[2])),
+                    6 => {
+                        positions.push(Vec3(values[0], values[1], values[2]));
+                        colors.push(Rgb(values[3], values[4], values[5]));
+                    }
+                    count => {
+                        return parse_fail(
+                            &format!("v expects 3 or 6 values, but got {}", count),
+                            file,
+                            line_number,
+                        )
+                    }
+                }
+            }
+            "vn" => {
+                let values = parse_floats(&args, file, line_number)?;
+                if values.len() != 3 {
+                    return parse_fail("vn expects 3 values", file, line_number);
+                }
+                normals.push(Vec3(values[0], values[1], values[2]));
+            }
+            "vt" => {
+                let values = parse_floats(&args, file, line_number)?;
+                if values.is_empty() || values.len() > 3 {
+                    return parse_fail("vt expects 1 to 3 values", file, line_number);
+                }
+
+                // obj uvs start at the bottom, vulkan uvs start at the top
+                let u = values[0];
+                let v = values.get(1).copied().unwrap_or(0.0);
+                uvs.push(Vec2(u, 1.0 - v));
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return parse_fail("f expects at least 3 vertices", file, line_number);
+                }
+
+                let mut face = Vec::with_capacity(args.len());
+                for arg in args {
+                    let mut indices = arg.split('/');
+                    let position = indices.next().unwrap_or("");
+                    let uv = indices.next().filter(|x| !x.is_empty());
+                    let normal = indices.next().filter(|x| !x.is_empty());
+
+                    let position = resolve_index(position, positions.len(), file, line_number)?;
+                    let uv = uv
+                        .map(|x| resolve_index(x, uvs.len(), file, line_number))
+                        .transpose()?;
+                    let normal = normal
+                        .map(|x| resolve_index(x, normals.len(), file, line_number))
+                        .transpose()?;
+
+                    // either all or no vertices must have uvs and normals
+                    if *has_uvs.get_or_insert(uv.is_some()) != uv.is_some() {
+                        return parse_fail(
+                            "some vertices have uvs, others don't",
+                            file,
+                            line_number,
+                        );
+                    }
+
+                    if *has_normals.get_or_insert(normal.is_some()) != normal.is_some() {
+                        return parse_fail(
+                            "some vertices have normals, others don't",
+                            file,
+                            line_number,
+                        );
+                    }
+
+                    let key = (position, uv, normal);
+                    let index = match vertex_lookup.get(&key) {
+                        Some(index) => *index,
+                        None => {
+                            let index = mesh.positions.len() as u32;
+                            mesh.positions.push(positions[position]);
+                            if let Some(color) = colors.get(position) {
+                                mesh.colors.push(*color);
+                            }
+                            if let Some(uv) = uv {
+                                mesh.uvs.push(uvs[uv]);
+                            }
+                            if let Some(normal) = normal {
+                                mesh.normals.push(normals[normal]);
+                            }
+
+                            vertex_lookup.insert(key, index);
+                            index
+                        }
+                    };
+
+                    face.push(index);
+                }
+
+                for i in 1..face.len() - 1 {
+                    mesh.indices.push(face[0]);
+                    mesh.indices.push(face[i]);
+                    mesh.indices.push(face[i + 1]);
+                }
+            }
+            // objects, groups, smoothing groups and materials are ignored
+            "o" | "g" | "s" | "mtllib" | "usemtl" => (),
+            keyword => {
+                ris_log::warning!(
+                    "unsupported obj keyword \"{}\" in {}:{}",
+                    keyword,
+                    file,
+                    line_number,
+                );
+            }
+        }
+    }
+
+    if !colors.is_empty() && colors.len() != positions.len() {
+        return ris_error::new_result!(
+            "failed to parse obj: either all or no positions must have colors in {}",
+            file,
+        );
+    }
+
+    mesh.validate()?;
+    Ok(mesh)
+}
+
+fn parse_floats(args: &[&str], file: &str, line: usize) -> RisResult<Vec<f32>> {
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        match arg.parse::<f32>() {
+            Ok(value) => values.push(value),
+            Err(_) => return parse_fail(&format!("invalid number \"{}\"", arg), file, line),
+        }
+    }
+
+    Ok(values)
+}
+
+/// obj indices start at 1. negative indices are relative to the end of the list.
+fn resolve_index(value: &str, len: usize, file: &str, line: usize) -> RisResult<usize> {
+    let Ok(index) = value.parse::<isize>() else {
+        return parse_fail(&format!("invalid index \"{}\"", value), file, line);
+    };
+
+    let resolved = if index > 0 {
+        index - 1
+    } else {
+        len as isize + index
+    };
+
+    if index == 0 || resolved < 0 || resolved as usize >= len {
+        return parse_fail(&format!("index {} is out of bounds", index), file, line);
+    }
+
+    Ok(resolved as usize)
+}
+
+fn parse_fail<T>(message: &str, file: &str, line: usize) -> RisResult<T> {
+    ris_error::new_result!("failed to parse obj: {} in {}:{}", message, file, line)
+}
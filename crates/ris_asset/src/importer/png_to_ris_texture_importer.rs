@@ -0,0 +1,210 @@
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+
+use png::ColorType;
+
+use ris_data::settings::ris_yaml::RisYaml;
+use ris_error::Extensions;
+use ris_error::RisResult;
+
+use crate::assets::ris_texture::RisTexture;
+use crate::assets::ris_texture::TextureColorSpace;
+use crate::assets::ris_texture::TextureFilter;
+use crate::assets::ris_texture::TextureWrap;
+use crate::mipmap;
+use crate::mipmap::MipFilter;
+use crate::mipmap::MipInfo;
+
+pub const IN_EXT: &str = "png";
+pub const OUT_EXT: &[&str] = &["ris_texture"];
+pub const VERSION: u32 = 2;
+
+/// extension of the optional sidecar file, which configures how an image is imported. the
+/// sidecar of `image.png` is `image.png.ris_meta`.
+pub const META_EXT: &str = "ris_meta";
+
+pub const KEY_COLOR_SPACE: &str = "color_space";
+pub const KEY_WRAP: &str = "wrap";
+pub const KEY_FILTER: &str = "filter";
+pub const KEY_MIPS: &str = "mips";
+pub const KEY_PREMULTIPLY_ALPHA: &str = "premultiply_alpha";
+
+/// the content of a sidecar file. missing keys keep their default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureMeta {
+    pub color_space: TextureColorSpace,
+    pub wrap: TextureWrap,
+    pub filter: TextureFilter,
+    pub mips: Option<MipFilter>,
+    pub premultiply_alpha: bool,
+}
+
+impl Default for TextureMeta {
+    fn default() -> Self {
+        Self {
+            color_space: TextureColorSpace::Srgb,
+            wrap: TextureWrap::Repeat,
+            filter: TextureFilter::Linear,
+            mips: Some(MipFilter::Box),
+            premultiply_alpha: false,
+        }
+    }
+}
+
+impl TextureMeta {
+    pub fn path(source: &Path) -> RisResult<PathBuf> {
+        let file_name = source.file_name().into_ris_error()?;
+        let file_name = file_name.to_str().into_ris_error()?;
+        Ok(source.with_file_name(format!("{}.{}", file_name, META_EXT)))
+    }
+
+    pub fn serialize(&self) -> RisResult<String> {
+        let color_space = match self.color_space {
+            TextureColorSpace::Srgb => "srgb",
+            TextureColorSpace::Linear => "linear",
+        };
+        let wrap = match self.wrap {
+            TextureWrap::Repeat => "repeat",
+            TextureWrap::MirroredRepeat => "mirrored_repeat",
+            TextureWrap::ClampToEdge => "clamp_to_edge",
+        };
+        let filter = match self.filter {
+            TextureFilter::Nearest => "nearest",
+            TextureFilter::Linear => "linear",
+        };
+        let mips = match self.mips {
+            None => "none",
+            Some(MipFilter::Box) => "box",
+            Some(MipFilter::Kaiser) => "kaiser",
+        };
+
+        let mut yaml = RisYaml::default();
+        yaml.add_key_value_and_comment(KEY_COLOR_SPACE, color_space, "srgb, linear");
+        yaml.add_key_value_and_comment(KEY_WRAP, wrap, "repeat, mirrored_repeat, clamp_to_edge");
+        yaml.add_key_value_and_comment(KEY_FILTER, filter, "nearest, linear");
+        yaml.add_key_value_and_comment(KEY_MIPS, mips, "none, box, kaiser");
+        yaml.add_key_value(KEY_PREMULTIPLY_ALPHA, &self.premultiply_alpha.to_string());
+        yaml.to_string()
+    }
+
+    pub fn deserialize(text: &str) -> RisResult<Self> {
+        let yaml = RisYaml::try_from(text)?;
+        let mut result = Self::default();
+
+        for entry in yaml.entries.iter() {
+            let Some((key, value)) = entry.key_value.as_ref() else {
+                continue;
+            };
+
+            let line = entry.line;
+            match (key.as_str(), value.as_str()) {
+                (KEY_COLOR_SPACE, "srgb") => result.color_space = TextureColorSpace::Srgb,
+                (KEY_COLOR_SPACE, "linear") => result.color_space = TextureColorSpace::Linear,
+                (KEY_WRAP, "repeat") => result.wrap = TextureWrap::Repeat,
+                (KEY_WRAP, "mirrored_repeat") => result.wrap = TextureWrap::MirroredRepeat,
+                (KEY_WRAP, "clamp_to_edge") => result.wrap = TextureWrap::ClampToEdge,
+                (KEY_FILTER, "nearest") => result.filter = TextureFilter::Nearest,
+                (KEY_FILTER, "linear") => result.filter = TextureFilter::Linear,
+                (KEY_MIPS, "none") => result.mips = None,
+                (KEY_MIPS, "box") => result.mips = Some(MipFilter::Box),
+                (KEY_MIPS, "kaiser") => result.mips = Some(MipFilter::Kaiser),
+                (KEY_PREMULTIPLY_ALPHA, value) => match value.parse() {
+                    Ok(value) => result.premultiply_alpha = value,
+                    Err(_) => {
+                        return ris_error::new_result!(
+                            "invalid value \"{}\" at line {}",
+                            value,
+                            line,
+                        )
+                    }
+                },
+                (KEY_COLOR_SPACE | KEY_WRAP | KEY_FILTER | KEY_MIPS, value) => {
+                    return ris_error::new_result!("invalid value \"{}\" at line {}", value, line)
+                }
+                (key, _) => {
+                    return ris_error::new_result!("unkown key \"{}\" at line {}", key, line)
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// loads the sidecar of `source`. returns the default, if `source` has no sidecar.
+    pub fn load(source: &Path) -> RisResult<Self> {
+        let path = Self::path(source)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(&path)?;
+        match Self::deserialize(&text) {
+            Ok(meta) => Ok(meta),
+            Err(e) => {
+                let path = ris_io::path::to_str(&path);
+                ris_error::new_result!("failed to parse \"{}\": {}", path, e)
+            }
+        }
+    }
+}
+
+/// returns the paths of all files the import depended on, which is the sidecar. it is returned
+/// even if it does not exist, such that creating it causes the image to be imported again.
+pub fn import(source: PathBuf, targets: Vec<PathBuf>) -> RisResult<Vec<PathBuf>> {
+    let meta = TextureMeta::load(&source)?;
+    let meta_path = TextureMeta::path(&source)?;
+
+    // open file
+    let input = File::open(source)?;
+
+    // decode png
+    let mut decoder = png::Decoder::new(input);
+    // expand palettes and bit depths below 8, strip bit depths above 8
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels)?;
+    pixels.truncate(info.buffer_size());
+
+    let pixels_rgba = to_rgba(&pixels, info.color_type)?;
+
+    // generate mips
+    let mip_info = MipInfo {
+        filter: meta.mips,
+        srgb: meta.color_space == TextureColorSpace::Srgb,
+        premultiply_alpha: meta.premultiply_alpha,
+    };
+    let mips = mipmap::generate(info.width, info.height, &pixels_rgba, mip_info)?;
+
+    let texture = RisTexture {
+        color_space: meta.color_space,
+        wrap: meta.wrap,
+        filter: meta.filter,
+        premultiplied_alpha: meta.premultiply_alpha,
+        mips,
+    };
+    let bytes = texture.serialize()?;
+
+    let mut output = crate::asset_importer::create_file(&targets[0])?;
+    ris_io::write(&mut output, &bytes)?;
+
+    Ok(vec![meta_path])
+}
+
+fn to_rgba(pixels: &[u8], color_type: ColorType) -> RisResult<Vec<u8>> {
+    let pixels_rgba = match color_type {
+        ColorType::Rgba => pixels.to_vec(),
+        ColorType::Rgb => crate::util::add_alpha_channel(pixels)?,
+        ColorType::Grayscale => pixels.iter().flat_map(|&x| [x, x, x, u8::MAX]).collect(),
+        ColorType::GrayscaleAlpha => pixels
+            .chunks_exact(2)
+            .flat_map(|x| [x[0], x[0], x[0], x[1]])
+            .collect(),
+        color_type => {
+            return ris_error::new_result!("unsupported color type: {:?}", color_type);
+        }
+    };
+
+    Ok(pixels_rgba)
+}
@@ -3,10 +3,15 @@ pub mod codecs;
 pub mod importer;
 
 pub mod asset_compiler;
+pub mod asset_graph;
 pub mod asset_importer;
 pub mod asset_loader;
 pub mod asset_loader_compiled;
 pub mod asset_loader_directory;
+pub mod asset_watcher;
+pub mod import_cache;
+pub mod mipmap;
+pub mod spirv_reflection;
 
 pub mod util;
 
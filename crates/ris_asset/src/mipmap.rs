@@ -0,0 +1,310 @@
+use ris_error::RisResult;
+
+/// the filter used to downsample one mip level to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipFilter {
+    /// averages 2x2 pixels. fast, but slightly blurry.
+    Box,
+    /// windowed sinc. keeps more detail, but may cause ringing at hard edges.
+    Kaiser,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MipLevel {
+    pub width: u32,
+    pub height: u32,
+    pub pixels_rgba: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MipInfo {
+    /// `None` only prepares the first level
+    pub filter: Option<MipFilter>,
+    /// whether rgb is encoded with the srgb transfer function. filtering happens in linear space.
+    pub srgb: bool,
+    pub premultiply_alpha: bool,
+}
+
+/// the number of mip levels of a full chain, down to 1x1
+pub fn level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// returns all mip levels, starting with the image itself. each level is half the size of the
+/// previous one, rounded down, until a 1x1 level is reached.
+pub fn generate(
+    width: u32,
+    height: u32,
+    pixels_rgba: &[u8],
+    info: MipInfo,
+) -> RisResult<Vec<MipLevel>> {
+    let expected_len = width as usize * height as usize * 4;
+    if width == 0 || height == 0 || pixels_rgba.len() != expected_len {
+        return ris_error::new_result!(
+            "invalid image. width: {} height: {} len: {}",
+            width,
+            height,
+            pixels_rgba.len(),
+        );
+    }
+
+    if info.filter.is_none() && !info.premultiply_alpha {
+        return Ok(vec![MipLevel {
+            width,
+            height,
+            pixels_rgba: pixels_rgba.to_vec(),
+        }]);
+    }
+
+    let mut image = Image::decode(width, height, pixels_rgba, info);
+    let mut levels = vec![image.encode(info)];
+
+    let Some(filter) = info.filter else {
+        return Ok(levels);
+    };
+
+    while image.width > 1 || image.height > 1 {
+        image = match filter {
+            MipFilter::Box => image.downsample_box(),
+            MipFilter::Kaiser => image.downsample_kaiser(),
+        };
+        levels.push(image.encode(info));
+    }
+
+    Ok(levels)
+}
+
+/// rgba in linear space
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    fn decode(width: u32, height: u32, pixels_rgba: &[u8], info: MipInfo) -> Self {
+        let pixels = pixels_rgba
+            .chunks_exact(4)
+            .map(|x| {
+                let mut pixel = [0.0; 4];
+                for i in 0..4 {
+                    let value = x[i] as f32 / 255.0;
+                    pixel[i] = if info.srgb && i < 3 {
+                        srgb_to_linear(value)
+                    } else {
+                        value
+                    };
+                }
+
+                if info.premultiply_alpha {
+                    let alpha = pixel[3];
+                    for channel in pixel.iter_mut().take(3) {
+                        *channel *= alpha;
+                    }
+                }
+
+                pixel
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn encode(&self, info: MipInfo) -> MipLevel {
+        let mut pixels_rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in self.pixels.iter() {
+            for (i, value) in pixel.iter().enumerate() {
+                let value = value.clamp(0.0, 1.0);
+                let value = if info.srgb && i < 3 {
+                    linear_to_srgb(value)
+                } else {
+                    value
+                };
+
+                pixels_rgba.push((value * 255.0).round() as u8);
+            }
+        }
+
+        MipLevel {
+            width: self.width,
+            height: self.height,
+            pixels_rgba,
+        }
+    }
+
+    fn get(&self, x: u32, y: u32) -> [f32; 4] {
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    fn downsample_box(&self) -> Self {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let samples = [
+                    self.get(x * 2, y * 2),
+                    self.get(x * 2 + 1, y * 2),
+                    self.get(x * 2, y * 2 + 1),
+                    self.get(x * 2 + 1, y * 2 + 1),
+                ];
+
+                let mut pixel = [0.0; 4];
+                for sample in samples {
+                    for i in 0..4 {
+                        pixel[i] += sample[i] * 0.25;
+                    }
+                }
+                pixels.push(pixel);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn downsample_kaiser(&self) -> Self {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+
+        // separable: first horizontally, then vertically
+        let horizontal_weights = kaiser_weights(self.width, width);
+        let mut horizontal = Vec::with_capacity((width * self.height) as usize);
+        for y in 0..self.height {
+            for weights in horizontal_weights.iter() {
+                horizontal.push(convolve(weights, |x| self.get(x, y)));
+            }
+        }
+
+        let horizontal = Self {
+            width,
+            height: self.height,
+            pixels: horizontal,
+        };
+
+        let vertical_weights = kaiser_weights(self.height, height);
+        let mut pixels = vec![[0.0; 4]; (width * height) as usize];
+        for x in 0..width {
+            for (y, weights) in vertical_weights.iter().enumerate() {
+                pixels[y * width as usize + x as usize] =
+                    convolve(weights, |y| horizontal.get(x, y));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+// radius of the kaiser filter, in destination pixels
+const KAISER_SUPPORT: f32 = 3.0;
+const KAISER_BETA: f32 = 4.0;
+
+/// returns the source pixels and their weights, for each destination pixel
+fn kaiser_weights(source_size: u32, destination_size: u32) -> Vec<Vec<(u32, f32)>> {
+    let scale = source_size as f32 / destination_size as f32;
+    let radius = KAISER_SUPPORT * scale;
+
+    let mut result = Vec::with_capacity(destination_size as usize);
+    for i in 0..destination_size {
+        let center = (i as f32 + 0.5) * scale;
+        let first = (center - radius).floor().max(0.0) as u32;
+        let last = ((center + radius).ceil() as u32).min(source_size - 1);
+
+        let mut weights = Vec::new();
+        let mut sum = 0.0;
+        for j in first..=last {
+            let t = (j as f32 + 0.5 - center) / scale;
+            let weight = sinc(t) * kaiser_window(t / KAISER_SUPPORT);
+            if weight != 0.0 {
+                weights.push((j, weight));
+                sum += weight;
+            }
+        }
+
+        for (_, weight) in weights.iter_mut() {
+            *weight /= sum;
+        }
+
+        result.push(weights);
+    }
+
+    result
+}
+
+fn convolve(weights: &[(u32, f32)], sample: impl Fn(u32) -> [f32; 4]) -> [f32; 4] {
+    let mut pixel = [0.0; 4];
+    for (j, weight) in weights {
+        let value = sample(*j);
+        for i in 0..4 {
+            pixel[i] += value[i] * weight;
+        }
+    }
+
+    pixel
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        1.0
+    } else {
+        let x = x * std::f32::consts::PI;
+        x.sin() / x
+    }
+}
+
+fn kaiser_window(x: f32) -> f32 {
+    if x.abs() > 1.0 {
+        0.0
+    } else {
+        bessel_i0(KAISER_BETA * (1.0 - x * x).sqrt()) / bessel_i0(KAISER_BETA)
+    }
+}
+
+/// modified bessel function of the first kind, order 0
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x = x * 0.5;
+
+    for k in 1..32 {
+        term *= half_x / k as f32;
+        let squared = term * term;
+        sum += squared;
+
+        if squared < sum * 1e-8 {
+            break;
+        }
+    }
+
+    sum
+}
+
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
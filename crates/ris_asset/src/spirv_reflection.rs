@@ -0,0 +1,803 @@
+use std::collections::HashMap;
+
+use ris_data::settings::ris_yaml::RisYaml;
+use ris_error::Extensions;
+use ris_error::RisResult;
+
+// spirv specification: https://registry.khronos.org/SPIR-V/specs/unified1/SPIRV.html
+const MAGIC: u32 = 0x0723_0203;
+const HEADER_WORDS: usize = 5;
+
+const OP_NAME: u32 = 5;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_SAMPLER: u32 = 26;
+const OP_TYPE_SAMPLED_IMAGE: u32 = 27;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u32 = 29;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+
+const DECORATION_BLOCK: u32 = 2;
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+const DECORATION_OFFSET: u32 = 35;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_UNIFORM: u32 = 2;
+const STORAGE_CLASS_OUTPUT: u32 = 3;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+const EXECUTION_MODEL_VERTEX: u32 = 0;
+const EXECUTION_MODEL_GEOMETRY: u32 = 3;
+const EXECUTION_MODEL_FRAGMENT: u32 = 4;
+const EXECUTION_MODEL_COMPUTE: u32 = 5;
+
+const STAGE_VERTEX: &str = "vertex";
+const STAGE_GEOMETRY: &str = "geometry";
+const STAGE_FRAGMENT: &str = "fragment";
+const STAGE_COMPUTE: &str = "compute";
+
+const KEY_STAGE: &str = "stage";
+const KEY_INPUT: &str = "input";
+const KEY_OUTPUT: &str = "output";
+const KEY_BINDING: &str = "binding";
+const KEY_PUSH_CONSTANT: &str = "push_constant";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderStage {
+    Vertex,
+    Geometry,
+    Fragment,
+    Compute,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarKind {
+    Bool,
+    Int,
+    Uint,
+    Float,
+}
+
+/// the format of a shader input or output. a `vec3` is a 32 bit float with 3 components and 1
+/// column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+    pub scalar: ScalarKind,
+    pub bits: u32,
+    pub components: u32,
+    pub columns: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+    pub location: u32,
+    pub format: Format,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorKind {
+    UniformBuffer,
+    StorageBuffer,
+    CombinedImageSampler,
+    SampledImage,
+    StorageImage,
+    Sampler,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    pub set: u32,
+    pub binding: u32,
+    pub kind: DescriptorKind,
+    /// the number of descriptors, if the binding is an array. 0 if it is a runtime array.
+    pub count: u32,
+    /// the size in bytes of a buffer. 0 for all other kinds.
+    pub size: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushConstantBlock {
+    pub size: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderReflection {
+    pub stage: ShaderStage,
+    /// sorted by location
+    pub inputs: Vec<InterfaceVariable>,
+    /// sorted by location
+    pub outputs: Vec<InterfaceVariable>,
+    /// sorted by set and binding
+    pub descriptor_bindings: Vec<DescriptorBinding>,
+    pub push_constants: Option<PushConstantBlock>,
+}
+
+impl std::fmt::Display for ShaderStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderStage::Vertex => write!(f, "{}", STAGE_VERTEX),
+            ShaderStage::Geometry => write!(f, "{}", STAGE_GEOMETRY),
+            ShaderStage::Fragment => write!(f, "{}", STAGE_FRAGMENT),
+            ShaderStage::Compute => write!(f, "{}", STAGE_COMPUTE),
+        }
+    }
+}
+
+impl std::str::FromStr for ShaderStage {
+    type Err = ris_error::RisError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            STAGE_VERTEX => Ok(ShaderStage::Vertex),
+            STAGE_GEOMETRY => Ok(ShaderStage::Geometry),
+            STAGE_FRAGMENT => Ok(ShaderStage::Fragment),
+            STAGE_COMPUTE => Ok(ShaderStage::Compute),
+            value => ris_error::new_result!("invalid shader stage \"{}\"", value),
+        }
+    }
+}
+
+/// formats like `f32`, `f32x3` or `f32x4x4`, which is scalar, components and columns
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scalar = match self.scalar {
+            ScalarKind::Bool => "b",
+            ScalarKind::Int => "i",
+            ScalarKind::Uint => "u",
+            ScalarKind::Float => "f",
+        };
+
+        write!(f, "{}{}", scalar, self.bits)?;
+        if self.components > 1 || self.columns > 1 {
+            write!(f, "x{}", self.components)?;
+        }
+        if self.columns > 1 {
+            write!(f, "x{}", self.columns)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = ris_error::RisError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut splits = value.split('x');
+        let scalar_str = splits.next().into_ris_error()?;
+
+        let scalar = match scalar_str.get(..1) {
+            Some("b") => ScalarKind::Bool,
+            Some("i") => ScalarKind::Int,
+            Some("u") => ScalarKind::Uint,
+            Some("f") => ScalarKind::Float,
+            _ => return ris_error::new_result!("invalid format \"{}\"", value),
+        };
+
+        let bits = parse_u32(&scalar_str[1..])?;
+        let components = splits.next().map(parse_u32).unwrap_or(Ok(1))?;
+        let columns = splits.next().map(parse_u32).unwrap_or(Ok(1))?;
+
+        if splits.next().is_some() {
+            return ris_error::new_result!("invalid format \"{}\"", value);
+        }
+
+        Ok(Self {
+            scalar,
+            bits,
+            components,
+            columns,
+        })
+    }
+}
+
+impl std::fmt::Display for DescriptorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DescriptorKind::UniformBuffer => write!(f, "uniform_buffer"),
+            DescriptorKind::StorageBuffer => write!(f, "storage_buffer"),
+            DescriptorKind::CombinedImageSampler => write!(f, "combined_image_sampler"),
+            DescriptorKind::SampledImage => write!(f, "sampled_image"),
+            DescriptorKind::StorageImage => write!(f, "storage_image"),
+            DescriptorKind::Sampler => write!(f, "sampler"),
+        }
+    }
+}
+
+impl std::str::FromStr for DescriptorKind {
+    type Err = ris_error::RisError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "uniform_buffer" => Ok(DescriptorKind::UniformBuffer),
+            "storage_buffer" => Ok(DescriptorKind::StorageBuffer),
+            "combined_image_sampler" => Ok(DescriptorKind::CombinedImageSampler),
+            "sampled_image" => Ok(DescriptorKind::SampledImage),
+            "storage_image" => Ok(DescriptorKind::StorageImage),
+            "sampler" => Ok(DescriptorKind::Sampler),
+            value => ris_error::new_result!("invalid descriptor kind \"{}\"", value),
+        }
+    }
+}
+
+fn parse_u32(value: &str) -> RisResult<u32> {
+    match value.parse() {
+        Ok(value) => Ok(value),
+        Err(_) => ris_error::new_result!("expected a number, but got \"{}\"", value),
+    }
+}
+
+enum Type {
+    Scalar(ScalarKind, u32),
+    Vector(u32, u32),
+    Matrix(u32, u32),
+    Image { sampled: u32 },
+    Sampler,
+    SampledImage,
+    Array(u32, u32),
+    RuntimeArray(u32),
+    Struct(Vec<u32>),
+    Pointer(u32),
+}
+
+#[derive(Default)]
+struct Decorations {
+    block: bool,
+    buffer_block: bool,
+    built_in: bool,
+    array_stride: Option<u32>,
+    location: Option<u32>,
+    binding: Option<u32>,
+    set: Option<u32>,
+}
+
+#[derive(Default)]
+struct MemberDecorations {
+    built_in: bool,
+    offset: Option<u32>,
+    matrix_stride: Option<u32>,
+}
+
+#[derive(Default)]
+struct Module {
+    stage: Option<ShaderStage>,
+    names: HashMap<u32, String>,
+    decorations: HashMap<u32, Decorations>,
+    member_decorations: HashMap<(u32, u32), MemberDecorations>,
+    constants: HashMap<u32, u32>,
+    types: HashMap<u32, Type>,
+    // id, type, storage class
+    variables: Vec<(u32, u32, u32)>,
+}
+
+/// parses the decorations of a compiled spirv module. only the first entry point is reflected.
+/// built-ins like `gl_Position` are skipped.
+pub fn reflect(spirv: &[u8]) -> RisResult<ShaderReflection> {
+    let chunks = spirv.chunks_exact(4);
+    if !chunks.remainder().is_empty() || spirv.len() < HEADER_WORDS * 4 {
+        return ris_error::new_result!("invalid spirv size: {}", spirv.len());
+    }
+
+    let mut words = chunks
+        .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+        .collect::<Vec<_>>();
+
+    if words[0] == MAGIC.swap_bytes() {
+        for word in words.iter_mut() {
+            *word = word.swap_bytes();
+        }
+    }
+
+    if words[0] != MAGIC {
+        return ris_error::new_result!("incorrect spirv magic: {:#x}", words[0]);
+    }
+
+    let module = parse_module(&words[HEADER_WORDS..])?;
+    module.reflect()
+}
+
+fn parse_module(mut words: &[u32]) -> RisResult<Module> {
+    let mut module = Module::default();
+
+    while !words.is_empty() {
+        let word_count = (words[0] >> 16) as usize;
+        let opcode = words[0] & 0xffff;
+        if word_count == 0 || word_count > words.len() {
+            return ris_error::new_result!("invalid instruction word count: {}", word_count);
+        }
+
+        let operands = &words[1..word_count];
+        words = &words[word_count..];
+
+        let operand = |i: usize| -> RisResult<u32> {
+            match operands.get(i) {
+                Some(value) => Ok(*value),
+                None => ris_error::new_result!("opcode {} is missing operand {}", opcode, i),
+            }
+        };
+
+        match opcode {
+            OP_NAME => {
+                let target = operand(0)?;
+                let name = parse_string(&operands[1..]);
+                module.names.insert(target, name);
+            }
+            OP_ENTRY_POINT => {
+                if module.stage.is_some() {
+                    continue;
+                }
+
+                module.stage = Some(match operand(0)? {
+                    EXECUTION_MODEL_VERTEX => ShaderStage::Vertex,
+                    EXECUTION_MODEL_GEOMETRY => ShaderStage::Geometry,
+                    EXECUTION_MODEL_FRAGMENT => ShaderStage::Fragment,
+                    EXECUTION_MODEL_COMPUTE => ShaderStage::Compute,
+                    model => {
+                        return ris_error::new_result!("unsupported execution model {}", model)
+                    }
+                });
+            }
+            OP_TYPE_BOOL => {
+                let ty = Type::Scalar(ScalarKind::Bool, 32);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_INT => {
+                let scalar = match operand(2)? {
+                    0 => ScalarKind::Uint,
+                    _ => ScalarKind::Int,
+                };
+                let ty = Type::Scalar(scalar, operand(1)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_FLOAT => {
+                let ty = Type::Scalar(ScalarKind::Float, operand(1)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_VECTOR => {
+                let ty = Type::Vector(operand(1)?, operand(2)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_MATRIX => {
+                let ty = Type::Matrix(operand(1)?, operand(2)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_IMAGE => {
+                let ty = Type::Image {
+                    sampled: operand(6)?,
+                };
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_SAMPLER => {
+                module.types.insert(operand(0)?, Type::Sampler);
+            }
+            OP_TYPE_SAMPLED_IMAGE => {
+                module.types.insert(operand(0)?, Type::SampledImage);
+            }
+            OP_TYPE_ARRAY => {
+                let ty = Type::Array(operand(1)?, operand(2)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_RUNTIME_ARRAY => {
+                let ty = Type::RuntimeArray(operand(1)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_TYPE_STRUCT => {
+                let id = operand(0)?;
+                let ty = Type::Struct(operands[1..].to_vec());
+                module.types.insert(id, ty);
+            }
+            OP_TYPE_POINTER => {
+                let ty = Type::Pointer(operand(2)?);
+                module.types.insert(operand(0)?, ty);
+            }
+            OP_CONSTANT => {
+                // only the low word is needed for array lengths
+                module.constants.insert(operand(1)?, operand(2)?);
+            }
+            OP_VARIABLE => {
+                module
+                    .variables
+                    .push((operand(1)?, operand(0)?, operand(2)?));
+            }
+            OP_DECORATE => {
+                let decorations = module.decorations.entry(operand(0)?).or_default();
+                match operand(1)? {
+                    DECORATION_BLOCK => decorations.block = true,
+                    DECORATION_BUFFER_BLOCK => decorations.buffer_block = true,
+                    DECORATION_BUILT_IN => decorations.built_in = true,
+                    DECORATION_ARRAY_STRIDE => decorations.array_stride = Some(operand(2)?),
+                    DECORATION_LOCATION => decorations.location = Some(operand(2)?),
+                    DECORATION_BINDING => decorations.binding = Some(operand(2)?),
+                    DECORATION_DESCRIPTOR_SET => decorations.set = Some(operand(2)?),
+                    _ => (),
+                }
+            }
+            OP_MEMBER_DECORATE => {
+                let key = (operand(0)?, operand(1)?);
+                let decorations = module.member_decorations.entry(key).or_default();
+                match operand(2)? {
+                    DECORATION_BUILT_IN => decorations.built_in = true,
+                    DECORATION_OFFSET => decorations.offset = Some(operand(3)?),
+                    DECORATION_MATRIX_STRIDE => decorations.matrix_stride = Some(operand(3)?),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(module)
+}
+
+fn parse_string(words: &[u32]) -> String {
+    let bytes = words
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .take_while(|x| *x != 0)
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+impl Module {
+    fn reflect(&self) -> RisResult<ShaderReflection> {
+        let stage = self.stage.into_ris_error()?;
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        let mut descriptor_bindings = Vec::new();
+        let mut push_constants = None;
+
+        for &(id, pointer_id, storage_class) in self.variables.iter() {
+            let Some(Type::Pointer(type_id)) = self.types.get(&pointer_id) else {
+                return ris_error::new_result!("variable {} is not a pointer", id);
+            };
+            let type_id = *type_id;
+
+            let decorations = self.decorations.get(&id);
+            if decorations.is_some_and(|x| x.built_in) || self.is_built_in_block(type_id) {
+                continue;
+            }
+
+            match storage_class {
+                STORAGE_CLASS_INPUT | STORAGE_CLASS_OUTPUT => {
+                    let Some(location) = decorations.and_then(|x| x.location) else {
+                        continue;
+                    };
+
+                    // per vertex inputs of geometry shaders are arrays
+                    let type_id = match self.types.get(&type_id) {
+                        Some(Type::Array(element, _)) => *element,
+                        _ => type_id,
+                    };
+
+                    let variable = InterfaceVariable {
+                        location,
+                        format: self.format(type_id)?,
+                        name: self.name(id),
+                    };
+
+                    if storage_class == STORAGE_CLASS_INPUT {
+                        inputs.push(variable);
+                    } else {
+                        outputs.push(variable);
+                    }
+                }
+                STORAGE_CLASS_UNIFORM
+                | STORAGE_CLASS_UNIFORM_CONSTANT
+                | STORAGE_CLASS_STORAGE_BUFFER => {
+                    let set = decorations.and_then(|x| x.set).unwrap_or(0);
+                    let Some(binding) = decorations.and_then(|x| x.binding) else {
+                        return ris_error::new_result!(
+                            "resource \"{}\" has no binding",
+                            self.name(id),
+                        );
+                    };
+
+                    let (type_id, count) = match self.types.get(&type_id) {
+                        Some(Type::Array(element, length)) => (*element, self.constant(*length)?),
+                        Some(Type::RuntimeArray(element)) => (*element, 0),
+                        _ => (type_id, 1),
+                    };
+
+                    let type_decorations = self.decorations.get(&type_id);
+                    let kind = match (storage_class, self.types.get(&type_id)) {
+                        (STORAGE_CLASS_STORAGE_BUFFER, _) => DescriptorKind::StorageBuffer,
+                        (STORAGE_CLASS_UNIFORM, _)
+                            if type_decorations.is_some_and(|x| x.buffer_block) =>
+                        {
+                            DescriptorKind::StorageBuffer
+                        }
+                        (STORAGE_CLASS_UNIFORM, _) => DescriptorKind::UniformBuffer,
+                        (_, Some(Type::SampledImage)) => DescriptorKind::CombinedImageSampler,
+                        (_, Some(Type::Image { sampled: 2 })) => DescriptorKind::StorageImage,
+                        (_, Some(Type::Image { .. })) => DescriptorKind::SampledImage,
+                        (_, Some(Type::Sampler)) => DescriptorKind::Sampler,
+                        _ => {
+                            return ris_error::new_result!(
+                                "resource \"{}\" has an unsupported type",
+                                self.name(id),
+                            )
+                        }
+                    };
+
+                    let size = match kind {
+                        DescriptorKind::UniformBuffer | DescriptorKind::StorageBuffer => {
+                            self.size(type_id)?
+                        }
+                        _ => 0,
+                    };
+
+                    descriptor_bindings.push(DescriptorBinding {
+                        set,
+                        binding,
+                        kind,
+                        count,
+                        size,
+                        name: self.block_name(id, type_id),
+                    });
+                }
+                STORAGE_CLASS_PUSH_CONSTANT => {
+                    push_constants = Some(PushConstantBlock {
+                        size: self.size(type_id)?,
+                        name: self.block_name(id, type_id),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        inputs.sort_by_key(|x| x.location);
+        outputs.sort_by_key(|x| x.location);
+        descriptor_bindings.sort_by_key(|x| (x.set, x.binding));
+
+        Ok(ShaderReflection {
+            stage,
+            inputs,
+            outputs,
+            descriptor_bindings,
+            push_constants,
+        })
+    }
+
+    fn name(&self, id: u32) -> String {
+        self.names.get(&id).cloned().unwrap_or_default()
+    }
+
+    /// the instance name of the block, or the name of its type if it has none
+    fn block_name(&self, id: u32, type_id: u32) -> String {
+        let name = self.name(id);
+        if name.is_empty() {
+            self.name(type_id)
+        } else {
+            name
+        }
+    }
+
+    fn constant(&self, id: u32) -> RisResult<u32> {
+        self.constants.get(&id).copied().into_ris_error()
+    }
+
+    fn is_built_in_block(&self, type_id: u32) -> bool {
+        let type_id = match self.types.get(&type_id) {
+            Some(Type::Array(element, _)) => *element,
+            _ => type_id,
+        };
+
+        match self.types.get(&type_id) {
+            Some(Type::Struct(members)) => (0..members.len() as u32).any(|i| {
+                self.member_decorations
+                    .get(&(type_id, i))
+                    .is_some_and(|x| x.built_in)
+            }),
+            _ => false,
+        }
+    }
+
+    fn format(&self, type_id: u32) -> RisResult<Format> {
+        let (scalar_id, components, columns) = match self.types.get(&type_id) {
+            Some(Type::Scalar(..)) => (type_id, 1, 1),
+            Some(Type::Vector(component, count)) => (*component, *count, 1),
+            Some(Type::Matrix(column, count)) => match self.types.get(column) {
+                Some(Type::Vector(component, components)) => (*component, *components, *count),
+                _ => return ris_error::new_result!("invalid matrix type {}", type_id),
+            },
+            _ => return ris_error::new_result!("type {} has no format", type_id),
+        };
+
+        let Some(Type::Scalar(scalar, bits)) = self.types.get(&scalar_id) else {
+            return ris_error::new_result!("invalid component type {}", scalar_id);
+        };
+
+        Ok(Format {
+            scalar: *scalar,
+            bits: *bits,
+            components,
+            columns,
+        })
+    }
+
+    /// the size of a type in a buffer, according to its offset and stride decorations. the
+    /// padding after the last member of a struct is not included.
+    fn size(&self, type_id: u32) -> RisResult<u32> {
+        self.size_with_matrix_stride(type_id, None)
+    }
+
+    fn size_with_matrix_stride(&self, type_id: u32, matrix_stride: Option<u32>) -> RisResult<u32> {
+        match self.types.get(&type_id) {
+            Some(Type::Scalar(_, bits)) => Ok(bits / 8),
+            Some(Type::Vector(component, count)) => Ok(self.size(*component)? * count),
+            Some(Type::Matrix(column, count)) => match matrix_stride {
+                Some(stride) => Ok(stride * count),
+                None => Ok(self.size(*column)? * count),
+            },
+            Some(Type::Array(element, length)) => {
+                let length = self.constant(*length)?;
+                let stride = self.decorations.get(&type_id).and_then(|x| x.array_stride);
+                match stride {
+                    Some(stride) => Ok(stride * length),
+                    None => Ok(self.size_with_matrix_stride(*element, matrix_stride)? * length),
+                }
+            }
+            // the size of a runtime array is only known when the buffer is bound
+            Some(Type::RuntimeArray(_)) => Ok(0),
+            Some(Type::Struct(members)) => {
+                let mut size = 0;
+                for (i, member) in members.iter().enumerate() {
+                    let decorations = self.member_decorations.get(&(type_id, i as u32));
+                    let offset = decorations.and_then(|x| x.offset).unwrap_or(size);
+                    let matrix_stride = decorations.and_then(|x| x.matrix_stride);
+                    let member_size = self.size_with_matrix_stride(*member, matrix_stride)?;
+                    size = u32::max(size, offset + member_size);
+                }
+
+                Ok(size)
+            }
+            _ => ris_error::new_result!("type {} has no size", type_id),
+        }
+    }
+}
+
+/// the sidecar of a shader contains the reflections of all of its stages. it is a ris_yaml file
+/// with entries like:
+///
+/// ```text
+/// stage: vertex
+/// vertex.input.0: f32x3 in_position
+/// vertex.binding.0.0: uniform_buffer 1 128 ubo
+/// vertex.push_constant: 64 pc
+/// ```
+pub fn serialize(reflections: &[ShaderReflection]) -> RisResult<String> {
+    let mut yaml = RisYaml::default();
+
+    for (i, reflection) in reflections.iter().enumerate() {
+        if i != 0 {
+            yaml.add_empty();
+        }
+
+        let stage = reflection.stage;
+        yaml.add_key_value(KEY_STAGE, &stage.to_string());
+
+        for input in reflection.inputs.iter() {
+            yaml.add_key_value(
+                &format!("{}.{}.{}", stage, KEY_INPUT, input.location),
+                &format!("{} {}", input.format, input.name),
+            );
+        }
+
+        for output in reflection.outputs.iter() {
+            yaml.add_key_value(
+                &format!("{}.{}.{}", stage, KEY_OUTPUT, output.location),
+                &format!("{} {}", output.format, output.name),
+            );
+        }
+
+        for binding in reflection.descriptor_bindings.iter() {
+            yaml.add_key_value(
+                &format!(
+                    "{}.{}.{}.{}",
+                    stage, KEY_BINDING, binding.set, binding.binding
+                ),
+                &format!(
+                    "{} {} {} {}",
+                    binding.kind, binding.count, binding.size, binding.name,
+                ),
+            );
+        }
+
+        if let Some(push_constants) = reflection.push_constants.as_ref() {
+            yaml.add_key_value(
+                &format!("{}.{}", stage, KEY_PUSH_CONSTANT),
+                &format!("{} {}", push_constants.size, push_constants.name),
+            );
+        }
+    }
+
+    yaml.to_string()
+}
+
+pub fn deserialize(text: &str) -> RisResult<Vec<ShaderReflection>> {
+    let yaml = RisYaml::try_from(text)?;
+    let mut reflections: Vec<ShaderReflection> = Vec::new();
+
+    for entry in yaml.entries.iter() {
+        let Some((key, value)) = entry.key_value.as_ref() else {
+            continue;
+        };
+
+        let line = entry.line;
+        let keys = key.split('.').collect::<Vec<_>>();
+        let values = value.split_whitespace().collect::<Vec<_>>();
+        let name = |i: usize| values.get(i).map(|x| x.to_string()).unwrap_or_default();
+
+        if key == KEY_STAGE {
+            reflections.push(ShaderReflection {
+                stage: value.parse()?,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                descriptor_bindings: Vec::new(),
+                push_constants: None,
+            });
+            continue;
+        }
+
+        let stage = keys[0].parse::<ShaderStage>()?;
+        let Some(reflection) = reflections.last_mut().filter(|x| x.stage == stage) else {
+            return ris_error::new_result!("stage \"{}\" was not declared at line {}", stage, line);
+        };
+
+        match (&keys[1..], values.len()) {
+            ([KEY_INPUT, location], 1..=2) | ([KEY_OUTPUT, location], 1..=2) => {
+                let variable = InterfaceVariable {
+                    location: parse_u32(location)?,
+                    format: values[0].parse()?,
+                    name: name(1),
+                };
+
+                if keys[1] == KEY_INPUT {
+                    reflection.inputs.push(variable);
+                } else {
+                    reflection.outputs.push(variable);
+                }
+            }
+            ([KEY_BINDING, set, binding], 3..=4) => {
+                reflection.descriptor_bindings.push(DescriptorBinding {
+                    set: parse_u32(set)?,
+                    binding: parse_u32(binding)?,
+                    kind: values[0].parse()?,
+                    count: parse_u32(values[1])?,
+                    size: parse_u32(values[2])?,
+                    name: name(3),
+                });
+            }
+            ([KEY_PUSH_CONSTANT], 1..=2) => {
+                reflection.push_constants = Some(PushConstantBlock {
+                    size: parse_u32(values[0])?,
+                    name: name(1),
+                });
+            }
+            _ => return ris_error::new_result!("invalid entry \"{}\" at line {}", key, line),
+        }
+    }
+
+    Ok(reflections)
+}
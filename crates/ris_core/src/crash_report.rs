@@ -0,0 +1,193 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::thread::ThreadId;
+use std::time::Duration;
+
+use chrono::Local;
+
+use ris_error::RisResult;
+use ris_jobs::job_system;
+
+use crate::log_appenders::ring_buffer_appender;
+
+pub const DIRECTORY_NAME: &str = "crash_reports";
+pub const EXTENSION: &str = ".txt";
+
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+static FRAME_NUMBER: AtomicUsize = AtomicUsize::new(0);
+
+/// the last panic of each thread, see `set_panic_hook()`
+static PANICS: Mutex<Vec<PanicRecord>> = Mutex::new(Vec::new());
+
+/// panics of threads that ended are never taken, thus the oldest are forgotten beyond this
+const MAX_PANIC_RECORDS: usize = 64;
+
+struct PanicRecord {
+    thread: ThreadId,
+    message: String,
+    /// to find the record of a payload, which was resumed on another thread
+    payload_message: String,
+    backtrace: Backtrace,
+}
+
+/// the frame number that is written to crash reports
+pub fn set_frame_number(value: usize) {
+    FRAME_NUMBER.store(value, Ordering::Relaxed);
+}
+
+pub fn frame_number() -> usize {
+    FRAME_NUMBER.load(Ordering::Relaxed)
+}
+
+/// extends the current panic hook, such that it keeps the message and the backtrace of the
+/// panic for `report()`. a hook cannot tell whether the panic will be caught, thus it does not
+/// write a crash report itself. see `catch_unwind()` and `report_unresumed_panics()`.
+pub fn set_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        previous_hook(info);
+
+        let record = PanicRecord {
+            thread: std::thread::current().id(),
+            message: info.to_string(),
+            payload_message: payload_message(info.payload()),
+            backtrace: Backtrace::force_capture(),
+        };
+
+        let mut panics = lock_panics();
+        panics.retain(|x| x.thread != record.thread);
+        if panics.len() >= MAX_PANIC_RECORDS {
+            panics.remove(0);
+        }
+        panics.push(record);
+    }));
+}
+
+/// runs `f`. if a panic escapes it, it is reported and resumed. panics caught within `f` are
+/// not reported.
+pub fn catch_unwind<T>(directory: &Path, app_info: &impl Display, f: impl FnOnce() -> T) -> T {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            report(directory, app_info, payload.as_ref());
+            std::panic::resume_unwind(payload)
+        }
+    }
+}
+
+/// reports the panics that the job system cannot resume on another thread, like panics that
+/// end a worker thread. see `ris_jobs::job_system::set_panic_handler()`
+pub fn report_unresumed_panics(directory: PathBuf, app_info: impl Display + Send + Sync + 'static) {
+    job_system::set_panic_handler(Some(Arc::new(move |payload| {
+        report(&directory, &app_info, payload)
+    })));
+}
+
+/// writes a crash report for the panic of `payload` to `directory`. the report contains the
+/// messages of the `RingBufferAppender`, after the log has been flushed.
+pub fn report(directory: &Path, app_info: &impl Display, payload: &(dyn Any + Send)) {
+    ris_log::log::flush(FLUSH_TIMEOUT);
+
+    let (panic_message, backtrace) = match take_panic_record(payload) {
+        Some(record) => (record.message, record.backtrace.to_string()),
+        None => (
+            payload_message(payload),
+            String::from("unavailable, the panic hook did not record this panic"),
+        ),
+    };
+    let report = generate(app_info, &panic_message, &backtrace);
+
+    match write(directory, &report) {
+        Ok(path) => eprintln!("crash report written to {:?}", path),
+        Err(e) => eprintln!("failed to write crash report: {}\n{}", e, report),
+    }
+}
+
+pub fn generate(app_info: &impl Display, panic_message: &str, backtrace: &str) -> String {
+    let mut report = String::new();
+
+    let _ = writeln!(report, "crash report");
+    let _ = writeln!(report, "time:  {}", Local::now().to_rfc3339());
+    let _ = writeln!(report, "frame: {}", frame_number());
+    let _ = writeln!(report);
+    let _ = writeln!(report, "panic:\n{}", panic_message);
+    let _ = writeln!(report);
+    let _ = writeln!(report, "backtrace:\n{}", backtrace);
+    let _ = writeln!(report);
+    let _ = writeln!(report, "app info:\n{}", app_info);
+    let _ = writeln!(report);
+
+    match ring_buffer_appender::messages() {
+        Some(messages) => {
+            let _ = writeln!(report, "last {} log messages:", messages.len());
+            for message in messages.iter() {
+                let _ = writeln!(report, "\n{}", message.fmt(false));
+            }
+        }
+        None => {
+            let _ = writeln!(report, "log messages are unavailable");
+        }
+    }
+
+    report
+}
+
+/// writes the report into a new file in `directory`. returns the path of the file.
+pub fn write(directory: &Path, report: &str) -> RisResult<PathBuf> {
+    std::fs::create_dir_all(directory)?;
+
+    let filename = format!("{}{}", Local::now().to_rfc3339(), EXTENSION);
+    let mut path = PathBuf::from(directory);
+    path.push(ris_io::path::sanitize(&filename, true));
+
+    ris_io::fallback_file::write_atomic(&path, report.as_bytes())?;
+
+    Ok(path)
+}
+
+/// the record of the panic with the same payload. usually, the panic happened on the calling
+/// thread. otherwise it was resumed from another thread, like a panicking scoped job, and the
+/// newest record of any thread is taken. an older panic of the calling thread, which was
+/// caught, thus never takes the place of the reported one.
+fn take_panic_record(payload: &(dyn Any + Send)) -> Option<PanicRecord> {
+    let payload_message = payload_message(payload);
+    let current = std::thread::current().id();
+
+    let mut panics = lock_panics();
+    let index = panics
+        .iter()
+        .position(|x| x.thread == current && x.payload_message == payload_message)
+        .or_else(|| {
+            panics
+                .iter()
+                .rposition(|x| x.payload_message == payload_message)
+        })?;
+
+    Some(panics.remove(index))
+}
+
+fn lock_panics() -> MutexGuard<'static, Vec<PanicRecord>> {
+    PANICS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("panic payload is not a string")
+    }
+}
@@ -1,5 +1,6 @@
 use ris_data::ecs::script_prelude::*;
 use ris_data::gameloop::gameloop_state::GameloopState;
+use ris_data::settings::schema;
 use ris_jobs::job_system;
 
 use crate::god_object::GodObject;
@@ -15,6 +16,7 @@ pub fn run(mut god_object: GodObject) -> RisResult<WantsTo> {
     loop {
         ris_debug::profiler::new_frame()?;
         let frame = frame_calculator.bump_and_create_frame();
+        crate::crash_report::set_frame_number(frame.number());
 
         // reset events
         let mut r = ris_debug::new_record!("main loop");
@@ -22,6 +24,31 @@ pub fn run(mut god_object: GodObject) -> RisResult<WantsTo> {
         let previous_state = god_object.state.clone();
         god_object.state.reset_events();
 
+        // hot reload
+        ris_debug::add_record!(r, "poll asset watcher")?;
+        if let Some(asset_watcher) = god_object.asset_watcher.as_mut() {
+            // hot reload is a convenience. files may be locked or deleted while they are
+            // scanned, thus a failed poll is only logged and repeated by the next one.
+            let changed_assets = match asset_watcher.poll() {
+                Ok(changed_assets) => changed_assets,
+                Err(e) => {
+                    ris_log::error!("failed to poll asset watcher: {}", e);
+                    Vec::new()
+                }
+            };
+
+            let god_asset_references = god_object.god_asset.references();
+            let rebuild_renderers = changed_assets
+                .iter()
+                .any(|x| god_asset_references.contains(x));
+            if rebuild_renderers {
+                ris_log::debug!("god asset references changed. rebuilding renderers...");
+                god_object.state.event_rebuild_renderers = true;
+            }
+
+            god_object.state.event_assets_changed = changed_assets;
+        }
+
         // game loop
         ris_debug::add_record!(r, "submit save settings future")?;
         let save_settings_future = job_system::submit(move || {
@@ -67,7 +94,7 @@ pub fn run(mut god_object: GodObject) -> RisResult<WantsTo> {
         ris_debug::add_record!(r, "restart job system")?;
 
         let settings = &god_object.state.settings;
-        if settings.job().changed() {
+        if settings.setting_changed(&schema::JOB_WORKERS) {
             ris_log::debug!("job workers changed. restarting job system...");
             drop(god_object.job_system_guard);
 
@@ -85,9 +112,32 @@ pub fn run(mut god_object: GodObject) -> RisResult<WantsTo> {
             ris_log::debug!("job system restarted!");
         }
 
+        // update log filter
+        ris_debug::add_record!(r, "update log filter")?;
+
+        if settings.log_filter_changed() {
+            let log_filter = settings.log_filter();
+            ris_log::debug!("log filter changed to \"{}\"", log_filter);
+            ris_log::log::set_filter(log_filter);
+        }
+
+        // apply window settings
+        ris_debug::add_record!(r, "apply window settings")?;
+        let apply_window_settings_result = god_object.output_frame.apply_settings(settings);
+
+        // apply rebinds
+        ris_debug::add_record!(r, "apply rebinds")?;
+        let rebinds_changed = settings.setting_changed(&schema::INPUT_MOUSE_REBINDS)
+            || settings.setting_changed(&schema::INPUT_KEYBOARD_REBINDS)
+            || settings.setting_changed(&schema::INPUT_GAMEPAD_REBINDS);
+        if rebinds_changed {
+            crate::apply_rebinds(settings, &mut god_object.state.input);
+        }
+
         // handle errors
         ris_debug::add_record!(r, "handle errors")?;
 
+        apply_window_settings_result?;
         save_settings_result?;
         let logic_state = logic_result?;
         let output_state = output_result?;
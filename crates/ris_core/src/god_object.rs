@@ -1,13 +1,18 @@
+use std::path::PathBuf;
+
 use sdl2::keyboard::Scancode;
 
 use ris_asset::asset_loader;
 use ris_asset::asset_loader::AssetLoaderGuard;
+use ris_asset::asset_watcher::AssetWatcher;
 use ris_asset::RisGodAsset;
+use ris_data::asset_id::AssetId;
 use ris_data::ecs::registry::Registry;
 use ris_data::ecs::scene::SceneCreateInfo;
 use ris_data::gameloop::frame::FrameCalculator;
 use ris_data::god_state::GodState;
 use ris_data::info::app_info::AppInfo;
+use ris_data::settings::schema;
 use ris_data::settings::serializer::SettingsSerializer;
 use ris_data::settings::Settings;
 use ris_debug::gizmo::GizmoGuard;
@@ -37,6 +42,7 @@ fn import_assets() -> RisResult<()> {
         asset_importer::DEFAULT_SOURCE_DIRECTORY,
         asset_importer::DEFAULT_TARGET_DIRECTORY,
         Some("temp"),
+        asset_importer::ImportOptions::default(),
     )?;
 
     ris_log::debug!("assets imported!");
@@ -48,6 +54,40 @@ fn import_assets() -> RisResult<()> {
     Ok(())
 }
 
+#[cfg(debug_assertions)]
+fn create_asset_watcher(
+    app_info: &AppInfo,
+    god_asset_id: &AssetId,
+) -> RisResult<Option<AssetWatcher>> {
+    use ris_asset::asset_importer;
+    use ris_asset::asset_watcher;
+    use ris_asset::asset_watcher::AssetWatcherInfo;
+
+    // compiled assets cannot be hot reloaded
+    let AssetId::Path(_) = god_asset_id else {
+        return Ok(None);
+    };
+
+    let info = AssetWatcherInfo {
+        asset_directory: app_info.asset_path()?,
+        source_directory: String::from(asset_importer::DEFAULT_SOURCE_DIRECTORY),
+        target_directory: String::from(asset_importer::DEFAULT_TARGET_DIRECTORY),
+        temp_directory: Some(PathBuf::from("temp")),
+        poll_interval: asset_watcher::DEFAULT_POLL_INTERVAL,
+    };
+    let asset_watcher = AssetWatcher::new(info)?;
+
+    Ok(Some(asset_watcher))
+}
+
+#[cfg(not(debug_assertions))]
+fn create_asset_watcher(
+    _app_info: &AppInfo,
+    _god_asset_id: &AssetId,
+) -> RisResult<Option<AssetWatcher>> {
+    Ok(None)
+}
+
 pub struct GodObject {
     pub app_info: AppInfo,
     pub settings_serializer: SettingsSerializer,
@@ -55,6 +95,7 @@ pub struct GodObject {
     pub logic_frame: LogicFrame,
     pub output_frame: OutputFrame,
     pub god_asset: RisGodAsset,
+    pub asset_watcher: Option<AssetWatcher>,
     pub state: GodState,
 
     // guards, must be dropped last.
@@ -69,15 +110,19 @@ impl GodObject {
     pub fn new(app_info: AppInfo, registry: Registry) -> RisResult<Self> {
         // settings
         let settings_serializer = SettingsSerializer::new(&app_info);
-        let settings = match settings_serializer.deserialize(&app_info) {
+        let settings = match settings_serializer.deserialize() {
             Some(settings) => settings,
             None => {
-                let new_settings = Settings::new(&app_info);
+                let new_settings = Settings::new();
                 settings_serializer.serialize(&new_settings)?;
                 new_settings
             }
         };
 
+        // log
+        let log_filter = crate::determine_log_filter(&app_info, &settings);
+        ris_log::log::set_filter(log_filter);
+
         // job system
         let cpu_count = app_info.cpu.cpu_count;
         let workers = crate::determine_thread_count(&app_info, &settings);
@@ -91,6 +136,7 @@ impl GodObject {
         // assets
         import_assets()?;
         let asset_loader_guard = asset_loader::init(&app_info)?;
+        let asset_watcher = create_asset_watcher(&app_info, &asset_loader_guard.god_asset_id)?;
 
         // profiling
         let profiler_guard = ris_debug::profiler::init()?;
@@ -115,15 +161,20 @@ impl GodObject {
             .video()
             .map_err(|e| ris_error::new!("failed to get video subsystem: {}", e))?;
 
-        let window = video_subsystem
-            .window("ris_engine", 640, 480)
-            .resizable()
-            .maximized()
-            .position_centered()
-            .vulkan()
-            .build()?;
+        let window_width = settings.get_int(&schema::WINDOW_WIDTH) as u32;
+        let window_height = settings.get_int(&schema::WINDOW_HEIGHT) as u32;
+        let mut window_builder = video_subsystem.window("ris_engine", window_width, window_height);
+        window_builder.resizable().position_centered().vulkan();
+        if settings.get_bool(&schema::WINDOW_MAXIMIZED) {
+            window_builder.maximized();
+        }
+        if settings.get_bool(&schema::WINDOW_FULLSCREEN) {
+            window_builder.fullscreen_desktop();
+        }
+        let window = window_builder.build()?;
 
-        let vulkan_core = unsafe { VulkanCore::alloc(&app_info.package.name, &window) }?;
+        let vsync = settings.get_bool(&schema::WINDOW_VSYNC);
+        let vulkan_core = unsafe { VulkanCore::alloc(&app_info.package.name, &window, vsync) }?;
 
         // scene renderer
         let scene_renderer = unsafe { SceneRenderer::alloc(&vulkan_core, &god_asset) }?;
@@ -184,6 +235,8 @@ impl GodObject {
             input.keyboard.keymask[29] = Scancode::Kp2;
             input.keyboard.keymask[30] = Scancode::Kp4;
             input.keyboard.keymask[31] = Scancode::Kp6;
+
+            crate::apply_rebinds(&state.settings, input);
         }
 
         // god object
@@ -194,6 +247,7 @@ impl GodObject {
             logic_frame,
             output_frame,
             god_asset,
+            asset_watcher,
             state,
 
             // guards
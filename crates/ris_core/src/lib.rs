@@ -1,3 +1,4 @@
+pub mod crash_report;
 pub mod god_job;
 pub mod god_object;
 pub mod logic_frame;
@@ -7,12 +8,32 @@ pub mod log_appenders;
 pub mod ui_helper;
 
 use ris_data::info::app_info::AppInfo;
+use ris_data::input::Input;
+use ris_data::settings::schema;
 use ris_data::settings::Settings;
+use ris_log::log_filter::LogFilter;
 
 pub fn determine_thread_count(app_info: &AppInfo, settings: &Settings) -> usize {
     if let Some(workers) = app_info.args.workers {
         workers
     } else {
-        settings.job().get_workers()
+        match settings.get_int(&schema::JOB_WORKERS) {
+            0 => app_info.cpu.cpu_count / 2,
+            workers => workers as usize,
+        }
     }
 }
+
+pub fn determine_log_filter(app_info: &AppInfo, settings: &Settings) -> LogFilter {
+    if let Some(log_filter) = &app_info.args.log_filter {
+        log_filter.clone()
+    } else {
+        settings.log_filter()
+    }
+}
+
+pub fn apply_rebinds(settings: &Settings, input: &mut Input) {
+    input.mouse.rebind_matrix = settings.rebind_matrix(&schema::INPUT_MOUSE_REBINDS);
+    input.keyboard.rebind_matrix = settings.rebind_matrix(&schema::INPUT_KEYBOARD_REBINDS);
+    input.gamepad.rebind_matrix = settings.rebind_matrix(&schema::INPUT_GAMEPAD_REBINDS);
+}
@@ -1,37 +1,73 @@
 use std::io::Write;
 use std::path::Path;
+use std::time::Duration;
 
 use sdl2::messagebox::MessageBoxFlag;
 
 use ris_error::RisResult;
 use ris_io::fallback_file::FallbackFileAppend;
+use ris_io::fallback_file::Rotation;
 use ris_log::log::IAppender;
 use ris_log::log_message::LogMessage;
 
 const LOG_EXTENSION: &str = ".log";
-const OLD_LOG_COUNT: usize = 10;
+const OLD_LOG_COUNT: usize = 32;
+const MAX_LOG_SIZE: u64 = 16 * 1024 * 1024;
+const MAX_LOG_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_TOTAL_LOG_SIZE: u64 = 256 * 1024 * 1024;
+const MAX_BUFFER_SIZE: usize = 64 * 1024;
 
+/// buffers messages and writes them in one go, when the log thread finished a batch
 pub struct FileAppender {
     fallback_file: FallbackFileAppend,
+    buffer: String,
 }
 
 impl FileAppender {
     pub fn new(directory: &Path) -> RisResult<Self> {
-        let fallback_file = FallbackFileAppend::new(directory, LOG_EXTENSION, OLD_LOG_COUNT)?;
+        let rotation = Rotation {
+            max_file_size: Some(MAX_LOG_SIZE),
+            max_file_age: Some(MAX_LOG_AGE),
+            compress: true,
+            max_total_size: Some(MAX_TOTAL_LOG_SIZE),
+        };
+        let fallback_file =
+            FallbackFileAppend::with_rotation(directory, LOG_EXTENSION, OLD_LOG_COUNT, rotation)?;
 
-        Ok(Self { fallback_file })
+        Ok(Self {
+            fallback_file,
+            buffer: String::new(),
+        })
+    }
+}
+
+impl Drop for FileAppender {
+    fn drop(&mut self) {
+        self.flush();
     }
 }
 
 impl IAppender for FileAppender {
     fn print(&mut self, message: &LogMessage) {
-        let to_log = message.fmt(false);
+        self.buffer.push('\n');
+        self.buffer.push_str(&message.fmt(false));
+        self.buffer.push('\n');
+
+        if self.buffer.len() >= MAX_BUFFER_SIZE {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
 
         let file = self.fallback_file.current();
-        let result = writeln!(file, "\n{}", to_log);
+        let result = file.write_all(self.buffer.as_bytes());
 
         if result.is_err() {
-            let error_message = format!("failed to log the following message: {}", to_log);
+            let error_message = format!("failed to log the following messages: {}", self.buffer);
             let _ = sdl2::messagebox::show_simple_message_box(
                 MessageBoxFlag::ERROR,
                 "log failed",
@@ -39,5 +75,11 @@ impl IAppender for FileAppender {
                 None,
             );
         }
+
+        self.buffer.clear();
+
+        if let Err(e) = self.fallback_file.rotate_if_required() {
+            eprintln!("failed to rotate log file: {}", e);
+        }
     }
 }
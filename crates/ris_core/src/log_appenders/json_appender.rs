@@ -0,0 +1,48 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use ris_error::RisResult;
+use ris_log::log::IAppender;
+use ris_log::log_message::LogMessage;
+
+/// writes every message as a single line json object, such that logs can be parsed by tools.
+/// messages are buffered until the log thread finished a batch.
+pub struct JsonAppender {
+    writer: Box<dyn Write + Send>,
+}
+
+impl JsonAppender {
+    /// creates or truncates the file at `path`
+    pub fn new(path: &Path) -> RisResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = File::create(path)?;
+        Ok(Self::from_writer(BufWriter::new(file)))
+    }
+
+    pub fn from_writer(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+}
+
+impl IAppender for JsonAppender {
+    fn print(&mut self, message: &LogMessage) {
+        let to_log = message.to_json();
+
+        if let Err(e) = writeln!(self.writer, "{}", to_log) {
+            eprintln!("failed to log the following message: {}\n{}", to_log, e);
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(e) = self.writer.flush() {
+            eprintln!("failed to flush json log: {}", e);
+        }
+    }
+}
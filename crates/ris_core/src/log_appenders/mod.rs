@@ -1,3 +1,5 @@
 pub mod console_appender;
 pub mod file_appender;
+pub mod json_appender;
+pub mod ring_buffer_appender;
 pub mod ui_helper_appender;
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::TryLockError;
+
+use ris_error::RisResult;
+use ris_log::log::IAppender;
+use ris_log::log_message::LogMessage;
+
+static MESSAGES: Mutex<Option<VecDeque<LogMessage>>> = Mutex::new(None);
+
+/// keeps the last `capacity` messages in memory, such that they can be written to a crash report
+pub struct RingBufferAppender {
+    capacity: usize,
+}
+
+impl RingBufferAppender {
+    pub fn new(capacity: usize) -> RisResult<Self> {
+        let mut messages = MESSAGES.lock()?;
+        *messages = Some(VecDeque::with_capacity(capacity));
+
+        Ok(Self { capacity })
+    }
+}
+
+impl Drop for RingBufferAppender {
+    fn drop(&mut self) {
+        match MESSAGES.lock() {
+            Err(e) => eprintln!("error while dropping ring buffer appender: {}", e),
+            Ok(mut messages) => {
+                messages.take();
+            }
+        }
+    }
+}
+
+impl IAppender for RingBufferAppender {
+    fn print(&mut self, message: &LogMessage) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut mutex_guard = match MESSAGES.lock() {
+            Ok(mutex_guard) => mutex_guard,
+            Err(e) => e.into_inner(),
+        };
+
+        let Some(messages) = mutex_guard.as_mut() else {
+            return;
+        };
+
+        if messages.len() >= self.capacity {
+            messages.pop_front();
+        }
+
+        messages.push_back(message.clone());
+    }
+}
+
+/// the buffered messages, oldest first. `None` if no appender exists.
+///
+/// this does not block, as it may be called while panicking. if the messages are locked, for
+/// example because the appender itself panicked, `None` is returned as well.
+pub fn messages() -> Option<Vec<LogMessage>> {
+    let mutex_guard = match MESSAGES.try_lock() {
+        Ok(mutex_guard) => mutex_guard,
+        Err(TryLockError::Poisoned(e)) => e.into_inner(),
+        Err(TryLockError::WouldBlock) => return None,
+    };
+
+    mutex_guard
+        .as_ref()
+        .map(|messages| messages.iter().cloned().collect())
+}
@@ -1,6 +1,7 @@
 use std::ptr;
 
 use ash::vk;
+use sdl2::video::FullscreenType;
 use sdl2::video::Window;
 use sdl2_sys::SDL_WindowFlags;
 
@@ -8,6 +9,8 @@ use ris_asset::RisGodAsset;
 use ris_data::gameloop::frame::Frame;
 use ris_data::gameloop::gameloop_state::GameloopState;
 use ris_data::god_state::GodState;
+use ris_data::settings::schema;
+use ris_data::settings::Settings;
 use ris_error::Extensions;
 use ris_error::RisResult;
 use ris_video_data::core::VulkanCore;
@@ -70,6 +73,53 @@ impl OutputFrame {
         Ok(())
     }
 
+    /// applies the window settings that changed and differ from the state of the window. the
+    /// swapchain is only recreated when vsync changed. sdl may reject a size or fullscreen, like
+    /// when it exceeds the display. this is logged and the window is kept as is, only failing to
+    /// recreate the swapchain is an error.
+    pub fn apply_settings(&mut self, settings: &Settings) -> RisResult<()> {
+        let size_changed = settings.setting_changed(&schema::WINDOW_WIDTH)
+            || settings.setting_changed(&schema::WINDOW_HEIGHT);
+        let size = (
+            settings.get_int(&schema::WINDOW_WIDTH) as u32,
+            settings.get_int(&schema::WINDOW_HEIGHT) as u32,
+        );
+        if size_changed && size != self.window.size() {
+            if let Err(e) = self.window.set_size(size.0, size.1) {
+                ris_log::error!("failed to set window size to {}x{}: {}", size.0, size.1, e);
+            }
+        }
+
+        if settings.setting_changed(&schema::WINDOW_MAXIMIZED) {
+            if settings.get_bool(&schema::WINDOW_MAXIMIZED) {
+                self.window.maximize();
+            } else {
+                self.window.restore();
+            }
+        }
+
+        let fullscreen_type = if settings.get_bool(&schema::WINDOW_FULLSCREEN) {
+            FullscreenType::Desktop
+        } else {
+            FullscreenType::Off
+        };
+        if settings.setting_changed(&schema::WINDOW_FULLSCREEN)
+            && fullscreen_type != self.window.fullscreen_state()
+        {
+            if let Err(e) = self.window.set_fullscreen(fullscreen_type) {
+                ris_log::error!("failed to set fullscreen to {:?}: {}", fullscreen_type, e);
+            }
+        }
+
+        let vsync = settings.get_bool(&schema::WINDOW_VSYNC);
+        if vsync != self.core.vsync {
+            self.core
+                .set_vsync(vsync, self.window.vulkan_drawable_size())?;
+        }
+
+        Ok(())
+    }
+
     pub fn run(
         &mut self,
         frame: Frame,
@@ -641,6 +641,7 @@ fn reimport_assets(import_asset_future: &mut Option<JobFuture<()>>) -> RisResult
             asset_importer::DEFAULT_SOURCE_DIRECTORY,
             asset_importer::DEFAULT_TARGET_DIRECTORY,
             Some("temp"),
+            asset_importer::ImportOptions::default(),
         );
 
         if let Err(error) = result {
@@ -2,6 +2,7 @@ use std::ffi::CString;
 
 use ris_error::Extensions;
 use ris_error::RisResult;
+use ris_log::log_filter::LogFilter;
 use ris_log::log_level::LogLevel;
 use ris_log::log_message::LogMessage;
 
@@ -13,6 +14,8 @@ pub struct LogModule {
     log_level: LogLevel,
     filter: String,
     log_plain: bool,
+    log_filter: String,
+    log_filter_error: Option<String>,
 }
 
 impl IUiHelperModule for LogModule {
@@ -25,6 +28,8 @@ impl IUiHelperModule for LogModule {
             log_level: LogLevel::Debug,
             filter: String::new(),
             log_plain: false,
+            log_filter: ris_log::log::filter().to_string(),
+            log_filter_error: None,
         })
     }
 
@@ -72,6 +77,28 @@ impl IUiHelperModule for LogModule {
         data.ui.same_line();
         data.ui.input_text("filter", &mut self.filter).build();
 
+        // changes the levels that are logged, not only the ones that are displayed
+        let apply_log_filter = data
+            .ui
+            .input_text("log filter", &mut self.log_filter)
+            .hint("info,ris_asset=debug,ris_jobs=warning")
+            .enter_returns_true(true)
+            .build();
+        if apply_log_filter {
+            match self.log_filter.parse::<LogFilter>() {
+                Ok(log_filter) => {
+                    self.log_filter = log_filter.to_string();
+                    self.log_filter_error = None;
+                    data.state.settings.set_log_filter(&log_filter)?;
+                }
+                Err(e) => self.log_filter_error = Some(e.to_string()),
+            }
+        }
+
+        if let Some(log_filter_error) = &self.log_filter_error {
+            data.ui.text_colored([1.0, 0.3, 0.3, 1.0], log_filter_error);
+        }
+
         data.ui.separator();
 
         let result = data
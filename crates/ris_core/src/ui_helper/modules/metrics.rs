@@ -5,6 +5,10 @@ use std::time::Instant;
 use ris_data::gameloop::frame::Frame;
 use ris_debug::profiler::ProfilerState;
 use ris_error::RisResult;
+use ris_jobs::job_system;
+use ris_jobs::job_system::FullBufferPolicy;
+use ris_jobs::job_system::JobPriority;
+use ris_jobs::job_trace::WorkerUtilization;
 
 use crate::ui_helper::IUiHelperModule;
 use crate::ui_helper::SharedStateWeakPtr;
@@ -14,6 +18,7 @@ const PLOT_MIN_FPS: f32 = 0.0;
 const PLOT_MAX_FPS: f32 = 3_000.0;
 const PLOT_SAMPLE_WINDOW_IN_SECS: u64 = 3;
 const AVERAGE_SAMPLE_WINDOW_IN_SECS: u64 = 1;
+const UTILIZATION_SAMPLE_WINDOW_IN_MILLIS: u64 = 250;
 
 pub struct MetricsModule {
     shared_state: SharedStateWeakPtr,
@@ -22,6 +27,9 @@ pub struct MetricsModule {
     instant_since_last_average_calculation: Instant,
     last_average: Duration,
     frames_to_record: usize,
+    instant_since_last_utilization_calculation: Instant,
+    last_utilization: Vec<WorkerUtilization>,
+    busy_fractions: Vec<f32>,
 }
 
 impl IUiHelperModule for MetricsModule {
@@ -37,6 +45,9 @@ impl IUiHelperModule for MetricsModule {
             instant_since_last_average_calculation: Instant::now(),
             last_average: Duration::ZERO,
             frames_to_record: 60,
+            instant_since_last_utilization_calculation: Instant::now(),
+            last_utilization: Vec::new(),
+            busy_fractions: Vec::new(),
         })
     }
 
@@ -167,6 +178,95 @@ impl IUiHelperModule for MetricsModule {
             }
         }
 
+        if ui.collapsing_header("job system", header_flags) {
+            let policy = job_system::full_buffer_policy();
+            let policy_items = FullBufferPolicy::ALL.map(|x| x.to_string());
+            let mut current_policy_item = FullBufferPolicy::ALL
+                .iter()
+                .position(|x| *x == policy)
+                .unwrap_or_default();
+            ui.combo_simple_string(
+                "full buffer policy",
+                &mut current_policy_item,
+                &policy_items,
+            );
+            let new_policy = FullBufferPolicy::ALL[current_policy_item];
+            if new_policy != policy {
+                job_system::set_full_buffer_policy(new_policy);
+            }
+
+            let stats = job_system::stats();
+            for (i, priority) in JobPriority::ALL.iter().enumerate() {
+                ui.label_text(
+                    format!("{}", priority),
+                    format!(
+                        "submitted {} invoked {} overflow {}",
+                        stats.submitted[i], stats.invoked[i], stats.overflow[i],
+                    ),
+                );
+            }
+
+            ui.label_text("stolen", stats.stolen.to_string());
+            ui.label_text("run inline", stats.run_inline.to_string());
+            ui.label_text("blocked", stats.blocked.to_string());
+            ui.label_text("grown", stats.grown.to_string());
+
+            ui.separator();
+
+            let mut is_tracing = job_system::is_tracing();
+            if ui.checkbox("trace", &mut is_tracing) {
+                job_system::set_tracing(is_tracing);
+                self.last_utilization.clear();
+                self.busy_fractions.clear();
+            }
+
+            if is_tracing {
+                let utilization_sample_window =
+                    Duration::from_millis(UTILIZATION_SAMPLE_WINDOW_IN_MILLIS);
+                let diff = now - self.instant_since_last_utilization_calculation;
+                if diff > utilization_sample_window {
+                    self.instant_since_last_utilization_calculation = now;
+
+                    let utilization = job_system::utilization();
+                    self.busy_fractions = utilization
+                        .iter()
+                        .enumerate()
+                        .map(|(i, x)| match self.last_utilization.get(i) {
+                            Some(last) => (*x - *last).busy_fraction(),
+                            None => x.busy_fraction(),
+                        })
+                        .collect();
+                    self.last_utilization = utilization;
+                }
+
+                for (i, busy_fraction) in self.busy_fractions.iter().enumerate() {
+                    imgui::ProgressBar::new(*busy_fraction)
+                        .overlay_text(format!("worker {}: {:.0}%", i, busy_fraction * 100.0))
+                        .build(ui);
+                }
+            }
+
+            let pref_path = &self.shared_state.borrow().app_info.file.pref_path;
+            let dir = PathBuf::from(pref_path).join("job_trace");
+
+            if ui.button("export trace") {
+                let json = job_system::trace().to_chrome_json();
+
+                let filename = ris_io::path::sanitize(&chrono::Local::now().to_rfc3339(), true);
+                let filename = format!("{}.json", filename);
+                let filepath = PathBuf::from(&dir).join(filename);
+
+                std::fs::create_dir_all(&dir)?;
+                let mut file = std::fs::File::create(&filepath)?;
+
+                ris_io::write(&mut file, json.as_bytes())?;
+                ris_log::info!(
+                    "successfully written job trace to \"{}\"",
+                    ris_io::path::to_str(filepath),
+                );
+            }
+        }
+
         Ok(())
     }
 }
@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+
+use imgui::Ui;
+
+use ris_data::settings::schema;
+use ris_data::settings::schema::Setting;
+use ris_data::settings::schema::SettingKind;
+use ris_data::settings::schema::SettingValue;
 use ris_data::settings::serializer::SettingsSerializer;
 use ris_data::settings::Settings;
 use ris_error::RisResult;
 
+use crate::ui_helper::util;
 use crate::ui_helper::IUiHelperModule;
 use crate::ui_helper::SharedStateWeakPtr;
 use crate::ui_helper::UiHelperDrawData;
@@ -9,9 +18,108 @@ use crate::ui_helper::UiHelperDrawData;
 pub struct SettingsModule {
     shared_state: SharedStateWeakPtr,
     saved: bool,
+    texts: HashMap<&'static str, TextInput>,
+    sliders: HashMap<&'static str, SettingValue>,
+    errors: HashMap<&'static str, String>,
+}
+
+/// text settings are applied when enter is pressed, thus the edited text is stored separately
+#[derive(Default)]
+struct TextInput {
+    edited: String,
+    applied: String,
 }
 
-impl SettingsModule {}
+impl SettingsModule {
+    /// draws the widget that matches the kind of `setting`. returns whether the setting changed.
+    fn draw_setting(
+        &mut self,
+        ui: &Ui,
+        settings: &mut Settings,
+        setting: &'static Setting,
+    ) -> RisResult<bool> {
+        let label = setting.name();
+
+        let new_value = match setting.kind {
+            SettingKind::Bool { .. } => {
+                let mut value = settings.get_bool(setting);
+                ui.checkbox(label, &mut value)
+                    .then_some(SettingValue::Bool(value))
+            }
+            SettingKind::Int { min, max, .. } => {
+                let mut value = match self.sliders.get(setting.key) {
+                    Some(SettingValue::Int(value)) => *value,
+                    _ => settings.get_int(setting),
+                };
+                if ui.slider(label, min, max, &mut value) {
+                    self.sliders.insert(setting.key, SettingValue::Int(value));
+                }
+                self.released_slider(ui, setting)
+            }
+            SettingKind::Float { min, max, .. } => {
+                let mut value = match self.sliders.get(setting.key) {
+                    Some(SettingValue::Float(value)) => *value,
+                    _ => settings.get_float(setting),
+                };
+                if ui.slider(label, min, max, &mut value) {
+                    self.sliders.insert(setting.key, SettingValue::Float(value));
+                }
+                self.released_slider(ui, setting)
+            }
+            SettingKind::Choice { options, .. } => {
+                let value = settings.get_text(setting);
+                let mut index = options.iter().position(|x| *x == value).unwrap_or(0);
+                ui.combo_simple_string(label, &mut index, options)
+                    .then(|| SettingValue::Text(options[index].to_string()))
+            }
+            SettingKind::Text { .. } => {
+                let value = settings.get_text(setting);
+                let text = self.texts.entry(setting.key).or_default();
+                if text.applied != value {
+                    text.edited = value.to_string();
+                    text.applied = value.to_string();
+                }
+
+                ui.input_text(label, &mut text.edited)
+                    .enter_returns_true(true)
+                    .build()
+                    .then(|| SettingValue::Text(text.edited.clone()))
+            }
+        };
+
+        ui.same_line();
+        util::help_marker(ui, &setting.comment());
+
+        let mut changed = false;
+        if let Some(new_value) = new_value {
+            match settings.set(setting, new_value) {
+                Ok(()) => {
+                    self.errors.remove(setting.key);
+                    changed = true;
+                }
+                Err(e) => {
+                    self.errors.insert(setting.key, e.to_string());
+                }
+            }
+        }
+
+        if let Some(error) = self.errors.get(setting.key) {
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], error);
+        }
+
+        Ok(changed)
+    }
+
+    /// sliders are applied when they are released, otherwise dragging the window size would
+    /// resize the window every frame. until then, the dragged value is stored separately.
+    fn released_slider(&mut self, ui: &Ui, setting: &Setting) -> Option<SettingValue> {
+        if ui.is_item_deactivated_after_edit() {
+            self.sliders.remove(setting.key)
+        } else {
+            None
+        }
+    }
+}
 
 impl IUiHelperModule for SettingsModule {
     fn name() -> &'static str {
@@ -22,6 +130,9 @@ impl IUiHelperModule for SettingsModule {
         Box::new(Self {
             shared_state,
             saved: true,
+            texts: HashMap::new(),
+            sliders: HashMap::new(),
+            errors: HashMap::new(),
         })
     }
 
@@ -29,12 +140,17 @@ impl IUiHelperModule for SettingsModule {
         let ui = data.ui;
         let settings = &mut data.state.settings;
 
-        if ui.collapsing_header("jobs", imgui::TreeNodeFlags::empty()) {
-            let mut workers = settings.job().get_workers();
+        // one header per section, in the order of the schema
+        let mut previous_section = None;
+        let mut section_is_open = false;
+        for setting in schema::ALL.iter() {
+            let section = setting.section();
+            if previous_section != Some(section) {
+                previous_section = Some(section);
+                section_is_open = ui.collapsing_header(section, imgui::TreeNodeFlags::empty());
+            }
 
-            let cpu_count = self.shared_state.borrow().app_info.cpu.cpu_count;
-            if ui.slider("workers", 1, cpu_count, &mut workers) {
-                settings.job_mut().set_workers(workers);
+            if section_is_open && self.draw_setting(ui, settings, setting)? {
                 self.saved = false;
             }
         }
@@ -55,9 +171,11 @@ impl IUiHelperModule for SettingsModule {
                 if ui.button("load") {
                     let app_info = &self.shared_state.borrow().app_info;
                     let serializer = SettingsSerializer::new(app_info);
-                    if let Some(deserialized_settings) = serializer.deserialize(app_info) {
-                        *settings = deserialized_settings;
+                    if let Some(deserialized_settings) = serializer.deserialize() {
+                        settings.assign(&deserialized_settings);
                     }
+                    self.sliders.clear();
+                    self.errors.clear();
                     self.saved = true;
                 }
 
@@ -70,8 +188,9 @@ impl IUiHelperModule for SettingsModule {
             }
 
             if ui.button("restore default") {
-                let app_info = &self.shared_state.borrow().app_info;
-                *settings = Settings::new(app_info);
+                settings.restore_defaults();
+                self.sliders.clear();
+                self.errors.clear();
                 self.saved = false;
             }
         }
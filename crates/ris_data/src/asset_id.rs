@@ -1,5 +1,46 @@
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use ris_io::FatPtr;
+use ris_io::RisDeserialize;
+use ris_io::RisSerialize;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AssetId {
     Index(usize),
     Path(String),
 }
+
+/// an `u8` tag, followed by the index as an `u32` or the path as a string
+impl RisSerialize for AssetId {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        let begin = ris_io::seek(stream, SeekFrom::Current(0))?;
+        match self {
+            Self::Index(id) => {
+                ris_io::write_u8(stream, 0)?;
+                ris_io::write_uint(stream, *id)?;
+            }
+            Self::Path(id) => {
+                ris_io::write_u8(stream, 1)?;
+                ris_io::write_string(stream, id)?;
+            }
+        }
+        let end = ris_io::seek(stream, SeekFrom::Current(0))?;
+        FatPtr::begin_end(begin, end)
+    }
+}
+
+impl RisDeserialize for AssetId {
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+        match ris_io::read_u8(stream)? {
+            0 => Ok(Self::Index(ris_io::read_uint(stream)?)),
+            1 => Ok(Self::Path(ris_io::read_string(stream)?)),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+}
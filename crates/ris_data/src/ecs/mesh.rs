@@ -1,6 +1,7 @@
 use ash::vk;
 
 use ris_error::RisResult;
+use ris_io::Pod;
 use ris_math::color::Rgb;
 use ris_math::vector::Vec2;
 use ris_math::vector::Vec3;
@@ -18,6 +19,11 @@ pub struct Vertex {
     pub uv: Vec2,
 }
 
+// all fields consist of `f32`s without padding
+unsafe impl Pod for Vertex {
+    const SCALAR_SIZE: usize = 4;
+}
+
 pub const VERTEX_BINDING_DESCRIPTIONS: [vk::VertexInputBindingDescription; 1] =
     [vk::VertexInputBindingDescription {
         binding: 0,
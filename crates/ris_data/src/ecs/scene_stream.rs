@@ -6,6 +6,7 @@ use std::io::Write;
 
 use ris_error::Extensions;
 use ris_error::RisResult;
+use ris_io::BoundedReader;
 use ris_io::FatPtr;
 
 use crate::asset_id::AssetId;
@@ -22,7 +23,7 @@ pub struct SceneWriter<'a> {
 }
 
 pub struct SceneReader<'a> {
-    stream: Cursor<Vec<u8>>,
+    stream: BoundedReader<Cursor<Vec<u8>>>,
     chunk: usize,
     pub scene: &'a Scene,
     pub lookup: Vec<usize>,
@@ -96,14 +97,40 @@ impl<'a> SceneWriter<'a> {
 }
 
 impl<'a> SceneReader<'a> {
-    pub fn new(chunk: usize, scene: &'a Scene, data: Vec<u8>, assets_ids: Vec<AssetId>) -> Self {
-        Self {
-            stream: Cursor::new(data),
+    /// `max_len` is the largest length a single length prefix may have. see `BoundedReader`.
+    pub fn new(
+        chunk: usize,
+        scene: &'a Scene,
+        data: Vec<u8>,
+        assets_ids: Vec<AssetId>,
+        max_len: usize,
+    ) -> RisResult<Self> {
+        let stream = BoundedReader::new(Cursor::new(data), max_len)?;
+
+        Ok(Self {
+            stream,
             chunk,
             scene,
             lookup: Vec::new(),
             assets_ids,
-        }
+        })
+    }
+
+    /// reads an `u32` length prefix, which is checked against the maximum and the remaining
+    /// bytes. see `BoundedReader::read_len()`.
+    pub fn read_len(&mut self, min_element_size: usize) -> RisResult<usize> {
+        let len = self.stream.read_len(min_element_size)?;
+        Ok(len)
+    }
+
+    pub fn read_string(&mut self) -> RisResult<String> {
+        let string = self.stream.read_string()?;
+        Ok(string)
+    }
+
+    pub fn read_fat_ptr(&mut self) -> RisResult<FatPtr> {
+        let ptr = self.stream.read_fat_ptr()?;
+        Ok(ptr)
     }
 
     pub fn read_game_object(&mut self) -> RisResult<GameObjectHandle> {
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use ris_error::RisResult;
 use ris_math::camera::Camera;
 
+use crate::asset_id::AssetId;
 use crate::ecs::scene::Scene;
 use crate::ecs::scene::SceneCreateInfo;
 use crate::input::Input;
@@ -13,6 +14,7 @@ pub struct GodState {
     // events
     pub event_rebuild_renderers: bool,
     pub event_window_resized: Option<(u32, u32)>,
+    pub event_assets_changed: Vec<AssetId>,
 
     // general
     pub input: Input,
@@ -31,6 +33,7 @@ impl GodState {
             // events
             event_rebuild_renderers: false,
             event_window_resized: None,
+            event_assets_changed: Vec::new(),
 
             // general
             input: Input::default(),
@@ -47,6 +50,7 @@ impl GodState {
     pub fn reset_events(&mut self) {
         self.event_rebuild_renderers = false;
         self.event_window_resized = None;
+        self.event_assets_changed.clear();
 
         self.settings.reset();
     }
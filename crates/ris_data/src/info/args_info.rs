@@ -1,10 +1,12 @@
 use std::env;
 
 use ris_error::RisResult;
+use ris_log::log_filter::LogFilter;
 
 pub const NO_RESTART_ARG: &str = "--no-restart";
 pub const WORKERS_ARG: &str = "--workers";
 pub const ASSETS_ARG: &str = "--assets";
+pub const LOG_FILTER_ARG: &str = "--log-filter";
 
 pub const DEFAULT_ASSETS_VALUE: &str = "assets";
 
@@ -15,6 +17,8 @@ pub struct ArgsInfo {
     pub no_restart: bool,
     pub workers: Option<usize>,
     pub assets: String,
+    /// overrides the log filter of the settings
+    pub log_filter: Option<LogFilter>,
 }
 
 #[cfg(debug_assertions)]
@@ -25,6 +29,7 @@ fn create_with_default_values(raw_args: Vec<String>, executable_path: String) ->
         no_restart: false,
         workers: None,
         assets: String::from(DEFAULT_ASSETS_VALUE),
+        log_filter: None,
     }
 }
 
@@ -36,6 +41,7 @@ fn create_with_default_values(raw_args: Vec<String>, executable_path: String) ->
         no_restart: false,
         workers: None,
         assets: String::from("ris_assets"),
+        log_filter: None,
     }
 }
 
@@ -99,6 +105,11 @@ impl ArgsInfo {
                     let second_arg = result.get_arg(i)?;
                     result.assets = String::from(second_arg);
                 }
+                LOG_FILTER_ARG => {
+                    i += 1;
+                    let second_arg = result.get_arg(i)?;
+                    result.log_filter = Some(second_arg.parse()?);
+                }
                 _ => return ris_error::new_result!("unexpected argument: [{}] -> {}", i, arg),
             };
 
@@ -125,6 +136,11 @@ impl ArgsInfo {
         result.push(String::from(ASSETS_ARG));
         result.push(String::from(&self.assets));
 
+        if let Some(log_filter) = &self.log_filter {
+            result.push(String::from(LOG_FILTER_ARG));
+            result.push(log_filter.to_string());
+        }
+
         result
     }
 
@@ -1,4 +1,9 @@
-#[derive(Clone)]
+use std::str::FromStr;
+
+use ris_error::RisError;
+
+/// maps the buttons of a device to general actions. `data[i]` holds the actions of button `i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RebindMatrix {
     pub data: [u32; 32],
 }
@@ -23,3 +28,74 @@ impl RebindMatrix {
         target.data[..32].copy_from_slice(&source.data[..32])
     }
 }
+
+/// parses comma separated rebinds, like `0=1+2,3=none`. `0=1+2` binds button 0 to the actions 1
+/// and 2, `3=none` unbinds button 3. buttons without a rebind keep their default action.
+impl FromStr for RebindMatrix {
+    type Err = RisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+
+        for rebind in s.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let Some((button, actions)) = rebind.split_once('=') else {
+                return ris_error::new_result!("rebind \"{}\" has no '='", rebind);
+            };
+
+            let button = parse_index(button)?;
+
+            let mut row = 0;
+            if actions.trim() != "none" {
+                for action in actions.split('+') {
+                    row |= 1 << parse_index(action)?;
+                }
+            }
+
+            result.data[button] = row;
+        }
+
+        Ok(result)
+    }
+}
+
+/// writes only the rows that differ from the default
+impl std::fmt::Display for RebindMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let default = Self::default();
+        let mut is_first = true;
+
+        for (i, row) in self.data.iter().enumerate() {
+            if *row == default.data[i] {
+                continue;
+            }
+
+            if !is_first {
+                write!(f, ",")?;
+            }
+            is_first = false;
+
+            write!(f, "{}=", i)?;
+
+            if *row == 0 {
+                write!(f, "none")?;
+                continue;
+            }
+
+            let actions = (0..32)
+                .filter(|x| row & (1 << x) != 0)
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>();
+            write!(f, "{}", actions.join("+"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_index(value: &str) -> Result<usize, RisError> {
+    let value = value.trim();
+    match value.parse::<usize>() {
+        Ok(index) if index < 32 => Ok(index),
+        _ => ris_error::new_result!("\"{}\" is not a button between 0 and 31", value),
+    }
+}
@@ -1 +0,0 @@
-pub const JOB_WORKERS: &str = "job.workers";
@@ -1,32 +1,44 @@
-pub mod job_settings;
-pub mod key;
 pub mod ris_yaml;
+pub mod schema;
 pub mod serializer;
 
-use job_settings::JobSettings;
+use ris_error::RisResult;
+use ris_log::log_filter::LogFilter;
 
-use crate::info::app_info::AppInfo;
+use crate::input::rebind_matrix::RebindMatrix;
 
-#[derive(Default, Clone)]
+use schema::Setting;
+use schema::SettingValue;
+
+/// the values of all settings in `schema::ALL`
+#[derive(Clone)]
 pub struct Settings {
     changed: bool,
     save_requested: bool,
 
-    job: JobSettings,
+    values: Vec<SettingValue>,
+    changed_values: Vec<bool>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Settings {
-    pub fn new(app_info: &AppInfo) -> Self {
+    pub fn new() -> Self {
         Self {
             changed: false,
             save_requested: false,
 
-            job: JobSettings::new(app_info),
+            values: schema::ALL.iter().map(|x| x.default_value()).collect(),
+            changed_values: vec![false; schema::ALL.len()],
         }
     }
 
     pub fn changed(&self) -> bool {
-        self.changed || self.job.changed()
+        self.changed || self.changed_values.iter().any(|x| *x)
     }
 
     pub fn reset(&mut self) {
@@ -35,7 +47,7 @@ impl Settings {
             self.save_requested = false;
         }
 
-        self.job.reset();
+        self.changed_values.fill(false);
     }
 
     pub fn save_requested(&self) -> bool {
@@ -47,11 +59,121 @@ impl Settings {
         self.save_requested = true;
     }
 
-    pub fn job(&self) -> &JobSettings {
-        &self.job
+    /// whether `setting` was set to a different value since the last `reset()`
+    pub fn setting_changed(&self, setting: &Setting) -> bool {
+        self.changed_values[index(setting)]
+    }
+
+    pub fn get(&self, setting: &Setting) -> &SettingValue {
+        &self.values[index(setting)]
+    }
+
+    /// validates `value` against the schema. returns an error and keeps the old value, if it is
+    /// invalid.
+    pub fn set(&mut self, setting: &Setting, value: SettingValue) -> RisResult<()> {
+        let value = setting.validate(value)?;
+        let index = index(setting);
+
+        if self.values[index] != value {
+            self.values[index] = value;
+            self.changed_values[index] = true;
+        }
+
+        Ok(())
+    }
+
+    pub fn restore_default(&mut self, setting: &Setting) {
+        let index = index(setting);
+        let value = setting.default_value();
+
+        if self.values[index] != value {
+            self.values[index] = value;
+            self.changed_values[index] = true;
+        }
+    }
+
+    pub fn restore_defaults(&mut self) {
+        for setting in schema::ALL.iter() {
+            self.restore_default(setting);
+        }
+    }
+
+    /// takes the values of `source`. unlike assigning `source` directly, this marks the values
+    /// that differ as changed.
+    pub fn assign(&mut self, source: &Settings) {
+        for (i, value) in source.values.iter().enumerate() {
+            if self.values[i] != *value {
+                self.values[i] = value.clone();
+                self.changed_values[i] = true;
+            }
+        }
+    }
+
+    pub fn get_bool(&self, setting: &Setting) -> bool {
+        match self.get(setting) {
+            SettingValue::Bool(value) => *value,
+            value => ris_error::throw!("{} is not a bool: {:?}", setting.key, value),
+        }
+    }
+
+    pub fn get_int(&self, setting: &Setting) -> i64 {
+        match self.get(setting) {
+            SettingValue::Int(value) => *value,
+            value => ris_error::throw!("{} is not an int: {:?}", setting.key, value),
+        }
+    }
+
+    pub fn get_float(&self, setting: &Setting) -> f32 {
+        match self.get(setting) {
+            SettingValue::Float(value) => *value,
+            value => ris_error::throw!("{} is not a float: {:?}", setting.key, value),
+        }
+    }
+
+    pub fn get_text(&self, setting: &Setting) -> &str {
+        match self.get(setting) {
+            SettingValue::Text(value) => value,
+            value => ris_error::throw!("{} is not text: {:?}", setting.key, value),
+        }
+    }
+
+    /// the log level, overridden by the log filter
+    pub fn log_filter(&self) -> LogFilter {
+        let level = self.get_text(&schema::LOG_LEVEL);
+        let filter = self.get_text(&schema::LOG_FILTER);
+
+        // both are validated, thus parsing them together cannot fail
+        format!("{},{}", level, filter).parse().unwrap_or_default()
+    }
+
+    pub fn log_filter_changed(&self) -> bool {
+        self.setting_changed(&schema::LOG_LEVEL) || self.setting_changed(&schema::LOG_FILTER)
     }
 
-    pub fn job_mut(&mut self) -> &mut JobSettings {
-        &mut self.job
+    /// stores the default of `value` as the log level and its targets as the log filter
+    pub fn set_log_filter(&mut self, value: &LogFilter) -> RisResult<()> {
+        // a filter without targets is formatted as its level
+        let level = LogFilter::new(value.default).to_string();
+        let targets = value
+            .targets
+            .iter()
+            .map(|x| format!("{}={}", x.target, LogFilter::new(x.level)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.set(&schema::LOG_LEVEL, SettingValue::Text(level))?;
+        self.set(&schema::LOG_FILTER, SettingValue::Text(targets))
+    }
+
+    pub fn rebind_matrix(&self, setting: &Setting) -> RebindMatrix {
+        // validated, thus parsing cannot fail
+        self.get_text(setting).parse().unwrap_or_default()
+    }
+}
+
+fn index(setting: &Setting) -> usize {
+    match schema::index_of(setting) {
+        Some(index) => index,
+        None => ris_error::throw!("{} is not part of the schema", setting.key),
     }
 }
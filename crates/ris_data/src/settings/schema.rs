@@ -0,0 +1,355 @@
+use ris_error::RisResult;
+use ris_log::log_filter::LogFilter;
+
+use crate::input::rebind_matrix::RebindMatrix;
+
+/// the type of a setting, its default and the values it accepts
+#[derive(Debug, Clone, Copy)]
+pub enum SettingKind {
+    Bool {
+        default: bool,
+    },
+    Int {
+        default: i64,
+        min: i64,
+        max: i64,
+    },
+    Float {
+        default: f32,
+        min: f32,
+        max: f32,
+    },
+    /// one of `options`, stored as text
+    Choice {
+        default: &'static str,
+        options: &'static [&'static str],
+    },
+    /// free text, which must pass `validate`. `validate` returns the normalized text.
+    Text {
+        default: &'static str,
+        validate: fn(&str) -> RisResult<String>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Setting {
+    /// `section.name`. the section groups settings in the settings file and the ui.
+    pub key: &'static str,
+    pub description: &'static str,
+    pub kind: SettingKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f32),
+    Text(String),
+}
+
+// job
+pub static JOB_WORKERS: Setting = Setting {
+    key: "job.workers",
+    description: "number of worker threads. 0 uses half of the cpus",
+    kind: SettingKind::Int {
+        default: 0,
+        min: 0,
+        max: 256,
+    },
+};
+
+// window
+pub static WINDOW_WIDTH: Setting = Setting {
+    key: "window.width",
+    description: "width of the window in pixels",
+    kind: SettingKind::Int {
+        default: 640,
+        min: 320,
+        max: 16384,
+    },
+};
+
+pub static WINDOW_HEIGHT: Setting = Setting {
+    key: "window.height",
+    description: "height of the window in pixels",
+    kind: SettingKind::Int {
+        default: 480,
+        min: 240,
+        max: 16384,
+    },
+};
+
+pub static WINDOW_MAXIMIZED: Setting = Setting {
+    key: "window.maximized",
+    description: "fills the desktop, but keeps the borders. width and height apply once restored",
+    kind: SettingKind::Bool { default: true },
+};
+
+pub static WINDOW_FULLSCREEN: Setting = Setting {
+    key: "window.fullscreen",
+    description: "covers the whole desktop",
+    kind: SettingKind::Bool { default: false },
+};
+
+pub static WINDOW_VSYNC: Setting = Setting {
+    key: "window.vsync",
+    description: "waits for the vertical blank before presenting a frame",
+    kind: SettingKind::Bool { default: false },
+};
+
+// log
+pub static LOG_LEVEL: Setting = Setting {
+    key: "log.level",
+    description: "messages below this level are not logged",
+    kind: SettingKind::Choice {
+        default: "trace",
+        options: &[
+            "trace", "debug", "info", "warning", "error", "fatal", "none",
+        ],
+    },
+};
+
+pub static LOG_FILTER: Setting = Setting {
+    key: "log.filter",
+    description: "levels per target, like ris_asset=debug. overrides the level",
+    kind: SettingKind::Text {
+        default: "",
+        validate: validate_log_filter,
+    },
+};
+
+// input
+pub static INPUT_MOUSE_REBINDS: Setting = Setting {
+    key: "input.mouse_rebinds",
+    description: "mouse buttons bound to other actions, like 0=1+2 or 3=none",
+    kind: SettingKind::Text {
+        default: "",
+        validate: validate_rebinds,
+    },
+};
+
+pub static INPUT_KEYBOARD_REBINDS: Setting = Setting {
+    key: "input.keyboard_rebinds",
+    description: "keyboard buttons bound to other actions, like 0=1+2 or 3=none",
+    kind: SettingKind::Text {
+        default: "",
+        validate: validate_rebinds,
+    },
+};
+
+pub static INPUT_GAMEPAD_REBINDS: Setting = Setting {
+    key: "input.gamepad_rebinds",
+    description: "gamepad buttons bound to other actions, like 0=1+2 or 3=none",
+    kind: SettingKind::Text {
+        default: "",
+        validate: validate_rebinds,
+    },
+};
+
+// audio
+pub static AUDIO_MASTER_VOLUME: Setting = Setting {
+    key: "audio.master_volume",
+    description: "scales all other volumes",
+    kind: SettingKind::Float {
+        default: 1.0,
+        min: 0.0,
+        max: 1.0,
+    },
+};
+
+pub static AUDIO_MUSIC_VOLUME: Setting = Setting {
+    key: "audio.music_volume",
+    description: "volume of the music",
+    kind: SettingKind::Float {
+        default: 1.0,
+        min: 0.0,
+        max: 1.0,
+    },
+};
+
+pub static AUDIO_EFFECTS_VOLUME: Setting = Setting {
+    key: "audio.effects_volume",
+    description: "volume of sound effects",
+    kind: SettingKind::Float {
+        default: 1.0,
+        min: 0.0,
+        max: 1.0,
+    },
+};
+
+/// all settings, in the order they appear in the settings file and the ui
+pub static ALL: &[&Setting] = &[
+    &JOB_WORKERS,
+    &WINDOW_WIDTH,
+    &WINDOW_HEIGHT,
+    &WINDOW_MAXIMIZED,
+    &WINDOW_FULLSCREEN,
+    &WINDOW_VSYNC,
+    &LOG_LEVEL,
+    &LOG_FILTER,
+    &INPUT_MOUSE_REBINDS,
+    &INPUT_KEYBOARD_REBINDS,
+    &INPUT_GAMEPAD_REBINDS,
+    &AUDIO_MASTER_VOLUME,
+    &AUDIO_MUSIC_VOLUME,
+    &AUDIO_EFFECTS_VOLUME,
+];
+
+/// the position of `setting` in `ALL`
+pub fn index_of(setting: &Setting) -> Option<usize> {
+    ALL.iter().position(|x| x.key == setting.key)
+}
+
+pub fn find(key: &str) -> Option<&'static Setting> {
+    ALL.iter().find(|x| x.key == key).copied()
+}
+
+impl Setting {
+    /// the part of the key before the first '.'
+    pub fn section(&self) -> &'static str {
+        match self.key.split_once('.') {
+            Some((section, _)) => section,
+            None => self.key,
+        }
+    }
+
+    /// the part of the key after the first '.'
+    pub fn name(&self) -> &'static str {
+        match self.key.split_once('.') {
+            Some((_, name)) => name,
+            None => self.key,
+        }
+    }
+
+    pub fn default_value(&self) -> SettingValue {
+        match self.kind {
+            SettingKind::Bool { default } => SettingValue::Bool(default),
+            SettingKind::Int { default, .. } => SettingValue::Int(default),
+            SettingKind::Float { default, .. } => SettingValue::Float(default),
+            SettingKind::Choice { default, .. } => SettingValue::Text(default.to_string()),
+            SettingKind::Text { default, .. } => SettingValue::Text(default.to_string()),
+        }
+    }
+
+    /// the description, followed by the values the setting accepts
+    pub fn comment(&self) -> String {
+        match self.kind {
+            SettingKind::Bool { .. } => format!("{}. true or false", self.description),
+            SettingKind::Int { min, max, .. } => {
+                format!("{}. {} to {}", self.description, min, max)
+            }
+            SettingKind::Float { min, max, .. } => {
+                format!("{}. {} to {}", self.description, min, max)
+            }
+            SettingKind::Choice { options, .. } => {
+                format!("{}. one of {}", self.description, options.join(", "))
+            }
+            SettingKind::Text { .. } => self.description.to_string(),
+        }
+    }
+
+    /// returns `value` if it has the type of the setting and is in range. text is normalized.
+    pub fn validate(&self, value: SettingValue) -> RisResult<SettingValue> {
+        match (self.kind, value) {
+            (SettingKind::Bool { .. }, SettingValue::Bool(value)) => Ok(SettingValue::Bool(value)),
+            (SettingKind::Int { min, max, .. }, SettingValue::Int(value)) => {
+                if value < min || value > max {
+                    ris_error::new_result!(
+                        "{} must be between {} and {}, but was {}",
+                        self.key,
+                        min,
+                        max,
+                        value,
+                    )
+                } else {
+                    Ok(SettingValue::Int(value))
+                }
+            }
+            (SettingKind::Float { min, max, .. }, SettingValue::Float(value)) => {
+                // also rejects nan
+                if !(min..=max).contains(&value) {
+                    ris_error::new_result!(
+                        "{} must be between {} and {}, but was {}",
+                        self.key,
+                        min,
+                        max,
+                        value,
+                    )
+                } else {
+                    Ok(SettingValue::Float(value))
+                }
+            }
+            (SettingKind::Choice { options, .. }, SettingValue::Text(value)) => {
+                let value = value.trim().to_lowercase();
+                if options.contains(&value.as_str()) {
+                    Ok(SettingValue::Text(value))
+                } else {
+                    ris_error::new_result!(
+                        "{} must be one of {}, but was \"{}\"",
+                        self.key,
+                        options.join(", "),
+                        value,
+                    )
+                }
+            }
+            (SettingKind::Text { validate, .. }, SettingValue::Text(value)) => {
+                // the settings file reserves these characters
+                if value.contains([':', '#', '\n']) {
+                    return ris_error::new_result!(
+                        "{} may not contain ':', '#' or newlines, but was \"{}\"",
+                        self.key,
+                        value,
+                    );
+                }
+
+                let value = validate(value.trim())?;
+                Ok(SettingValue::Text(value))
+            }
+            (_, value) => ris_error::new_result!("{} cannot be {:?}", self.key, value),
+        }
+    }
+
+    /// parses and validates `value`, as it is written in the settings file
+    pub fn parse(&self, value: &str) -> RisResult<SettingValue> {
+        let value = value.trim();
+        let parsed = match self.kind {
+            SettingKind::Bool { .. } => SettingValue::Bool(value.parse()?),
+            SettingKind::Int { .. } => SettingValue::Int(value.parse()?),
+            SettingKind::Float { .. } => SettingValue::Float(value.parse()?),
+            SettingKind::Choice { .. } | SettingKind::Text { .. } => {
+                SettingValue::Text(value.to_string())
+            }
+        };
+
+        self.validate(parsed)
+    }
+}
+
+impl std::fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn validate_log_filter(value: &str) -> RisResult<String> {
+    value.parse::<LogFilter>()?;
+
+    // not formatted by `LogFilter`, because it would add a default level, which overrides
+    // `LOG_LEVEL`
+    let directives = value
+        .split(',')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>();
+    Ok(directives.join(","))
+}
+
+fn validate_rebinds(value: &str) -> RisResult<String> {
+    let rebind_matrix = value.parse::<RebindMatrix>()?;
+    Ok(rebind_matrix.to_string())
+}
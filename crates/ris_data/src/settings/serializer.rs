@@ -4,8 +4,9 @@ use ris_error::RisResult;
 use ris_io::fallback_file::FallbackFileOverwrite;
 
 use crate::info::app_info::AppInfo;
-use crate::settings::key;
+use crate::settings::ris_yaml;
 use crate::settings::ris_yaml::RisYaml;
+use crate::settings::schema;
 use crate::settings::Settings;
 
 //pub const DEFAULT: &str = "default";
@@ -38,12 +39,12 @@ impl SettingsSerializer {
         Ok(())
     }
 
-    pub fn deserialize(&self, app_info: &AppInfo) -> Option<Settings> {
+    pub fn deserialize(&self) -> Option<Settings> {
         ris_log::debug!("deserializing settings...");
 
         for available_path in self.fallback_file.available_paths() {
             if let Some(bytes) = self.fallback_file.get_by_path(&available_path) {
-                match read_bytes(&bytes, app_info) {
+                match read_bytes(&bytes) {
                     Ok(settings) => {
                         ris_log::debug!("settings deserialized!");
                         return Some(settings);
@@ -65,12 +66,24 @@ impl SettingsSerializer {
     }
 }
 
-fn write_bytes(settings: &Settings) -> RisResult<Vec<u8>> {
+pub fn write_bytes(settings: &Settings) -> RisResult<Vec<u8>> {
     let mut yaml = RisYaml::default();
 
-    yaml.add_comment("jobs");
-    yaml.add_key_value(key::JOB_WORKERS, &settings.job.get_workers().to_string());
-    yaml.add_empty();
+    let mut previous_section = None;
+    for setting in schema::ALL.iter() {
+        let section = setting.section();
+        if previous_section != Some(section) {
+            if previous_section.is_some() {
+                yaml.add_empty();
+            }
+
+            yaml.add_comment(section);
+            previous_section = Some(section);
+        }
+
+        let value = settings.get(setting).to_string();
+        yaml.add_key_value_and_comment(setting.key, &value, &setting.comment());
+    }
 
     let string = yaml.to_string()?;
 
@@ -78,23 +91,34 @@ fn write_bytes(settings: &Settings) -> RisResult<Vec<u8>> {
     Ok(bytes)
 }
 
-fn read_bytes(bytes: &[u8], app_info: &AppInfo) -> RisResult<Settings> {
+/// settings missing in `bytes` keep their default. unknown keys are skipped with a warning, such
+/// that files of other versions can still be read.
+pub fn read_bytes(bytes: &[u8]) -> RisResult<Settings> {
     let string = String::from_utf8(bytes.to_vec())?;
 
-    let mut result = Settings::new(app_info);
+    let mut result = Settings::new();
     let yaml = RisYaml::try_from(string.as_str())?;
 
-    for (i, entry) in yaml.entries.iter().enumerate() {
+    for entry in yaml.entries.iter() {
         let (key, value) = match entry.key_value.as_ref() {
             Some(key_value) => key_value,
             None => continue,
         };
 
-        match key.as_str() {
-            key::JOB_WORKERS => result.job.set_workers(value.parse()?),
-            _ => return ris_error::new_result!("unkown key at line {}", i),
-        }
+        let Some(setting) = schema::find(key) else {
+            ris_log::warning!("unknown key \"{}\" on line {}", key, entry.line);
+            continue;
+        };
+
+        let value = match setting.parse(value) {
+            Ok(value) => value,
+            Err(e) => return ris_yaml::error_on_line(entry.line, &e.to_string()),
+        };
+        result.set(setting, value)?;
     }
 
+    // values read from a file do not count as changes
+    result.reset();
+
     Ok(result)
 }
@@ -0,0 +1,135 @@
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use crate::FatPtr;
+
+/// wraps a stream, whose content is not trusted. every length prefix read through it is checked
+/// against a maximum and against the bytes remaining in the stream, before anything is allocated.
+/// thus a corrupted length results in an error, instead of an enormous allocation.
+pub struct BoundedReader<R> {
+    inner: R,
+    end: u64,
+    max_len: usize,
+}
+
+impl<R: Read + Seek> BoundedReader<R> {
+    /// `max_len` is the largest length a single length prefix may have
+    pub fn new(mut inner: R, max_len: usize) -> Result<Self> {
+        let current = crate::seek(&mut inner, SeekFrom::Current(0))?;
+        let end = crate::seek(&mut inner, SeekFrom::End(0))?;
+        crate::seek(&mut inner, SeekFrom::Start(current))?;
+
+        Ok(Self {
+            inner,
+            end,
+            max_len,
+        })
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// the number of bytes between the current position and the end of the stream
+    pub fn remaining(&mut self) -> Result<u64> {
+        let current = crate::seek(&mut self.inner, SeekFrom::Current(0))?;
+        Ok(self.end.saturating_sub(current))
+    }
+
+    /// returns an error if `len` exceeds the maximum, or if `len` elements of at least
+    /// `min_element_size` bytes cannot fit into the remaining bytes.
+    pub fn check_len(&mut self, len: usize, min_element_size: usize) -> Result<()> {
+        if len > self.max_len {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("len {} exceeds the maximum of {}", len, self.max_len),
+            ));
+        }
+
+        let remaining = self.remaining()?;
+        let required = (len as u64).checked_mul(min_element_size as u64);
+        match required {
+            Some(required) if required <= remaining => Ok(()),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "len {} with elements of at least {} bytes exceeds the remaining {} bytes",
+                    len, min_element_size, remaining,
+                ),
+            )),
+        }
+    }
+
+    /// reads an `u32` length prefix and advances the stream. see `check_len()`.
+    pub fn read_len(&mut self, min_element_size: usize) -> Result<usize> {
+        let len = crate::read_uint(&mut self.inner)?;
+        self.check_len(len, min_element_size)?;
+        Ok(len)
+    }
+
+    /// reads a varint length prefix and advances the stream. see `check_len()`.
+    pub fn read_varint_len(&mut self, min_element_size: usize) -> Result<usize> {
+        let len = crate::read_varint(&mut self.inner)?;
+        let len = usize::try_from(len).map_err(|_| Error::from(ErrorKind::InvalidData))?;
+        self.check_len(len, min_element_size)?;
+        Ok(len)
+    }
+
+    /// reads bytes prefixed by their len as an `u32` and advances the stream
+    pub fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_len(1)?;
+        let mut bytes = vec![0; len];
+        crate::read(&mut self.inner, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// reads a string in the format of `ris_io::write_string()` and advances the stream
+    pub fn read_string(&mut self) -> Result<String> {
+        let bytes = self.read_bytes()?;
+        String::from_utf8(bytes).map_err(|_| Error::from(ErrorKind::InvalidData))
+    }
+
+    /// reads a `FatPtr` and advances the stream. returns an error if it points outside of the
+    /// stream.
+    pub fn read_fat_ptr(&mut self) -> Result<FatPtr> {
+        let ptr = crate::read_fat_ptr(&mut self.inner)?;
+        match ptr.addr.checked_add(ptr.len) {
+            Some(end) if end <= self.end => Ok(ptr),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "ptr {:?} points outside of the stream of {} bytes",
+                    ptr, self.end,
+                ),
+            )),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for BoundedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for BoundedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
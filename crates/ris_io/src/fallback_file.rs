@@ -1,33 +1,154 @@
 use std::fs::File;
 use std::io::BufRead;
+use std::io::Read;
+use std::io::Seek;
 use std::io::SeekFrom;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
 
 use chrono::DateTime;
 use chrono::Local;
 
 use ris_error::RisResult;
 
+const GZIP_EXTENSION: &str = ".gz";
+const GZIP_COMPRESSION_LEVEL: u8 = 6;
+
+/// when and how `FallbackFileAppend` rotates its current file while running. by default, the
+/// current file is only rotated on creation.
+#[derive(Debug, Default, Clone)]
+pub struct Rotation {
+    /// rotates once the current file exceeds this many bytes
+    pub max_file_size: Option<u64>,
+    /// rotates once the current file is older than this
+    pub max_file_age: Option<Duration>,
+    /// compresses old files with gzip. compression runs on a separate thread, such that writing
+    /// to the new current file isn't stalled. it is finished before the next rotation and when
+    /// the `FallbackFileAppend` is dropped.
+    pub compress: bool,
+    /// deletes the oldest old files, such that all files take at most this many bytes. the
+    /// current file is accounted for with `max_file_size`.
+    pub max_total_size: Option<u64>,
+}
+
 pub struct FallbackFileAppend {
     current_file: File,
+    current_path: PathBuf,
+    old_directory: PathBuf,
+    file_extension: String,
+    old_file_count: usize,
+    rotation: Rotation,
+    created: Instant,
+    /// compresses the previous current file, see `Rotation::compress`. `RisError` is not `Send`,
+    /// thus the thread returns the formatted error.
+    compression: Option<JoinHandle<Result<(), String>>>,
 }
 
 impl FallbackFileAppend {
     pub fn new(directory: &Path, file_extension: &str, old_file_count: usize) -> RisResult<Self> {
+        Self::with_rotation(
+            directory,
+            file_extension,
+            old_file_count,
+            Rotation::default(),
+        )
+    }
+
+    pub fn with_rotation(
+        directory: &Path,
+        file_extension: &str,
+        old_file_count: usize,
+        rotation: Rotation,
+    ) -> RisResult<Self> {
         let (current_path, old_directory) = generate_paths(directory, file_extension);
         std::fs::create_dir_all(&old_directory)?;
         delete_expired_files(&old_directory, old_file_count)?;
-        move_current_file(&current_path, &old_directory, file_extension)?;
+        let previous_path = archive_current_file(&current_path, &old_directory, file_extension)?;
         let current_file = create_current_file(&current_path)?;
+        let compression = finish_archive(previous_path, &old_directory, &rotation)?;
 
-        Ok(Self { current_file })
+        Ok(Self {
+            current_file,
+            current_path,
+            old_directory,
+            file_extension: file_extension.to_string(),
+            old_file_count,
+            rotation,
+            created: Instant::now(),
+            compression,
+        })
     }
 
     pub fn current(&mut self) -> &mut File {
         &mut self.current_file
     }
+
+    /// rotates, if the current file exceeds the size or age of the rotation. returns whether it
+    /// rotated.
+    pub fn rotate_if_required(&mut self) -> RisResult<bool> {
+        let size_exceeded = match self.rotation.max_file_size {
+            Some(max_file_size) => self.current_file.metadata()?.len() > max_file_size,
+            None => false,
+        };
+
+        let age_exceeded = match self.rotation.max_file_age {
+            Some(max_file_age) => self.created.elapsed() >= max_file_age,
+            None => false,
+        };
+
+        if !size_exceeded && !age_exceeded {
+            return Ok(false);
+        }
+
+        self.rotate()?;
+        Ok(true)
+    }
+
+    /// moves the current file to the old directory, compresses it if required and starts a new
+    /// current file. expired files and files exceeding the total size are deleted.
+    pub fn rotate(&mut self) -> RisResult<()> {
+        self.current_file.flush()?;
+        self.finish_compression()?;
+
+        std::fs::create_dir_all(&self.old_directory)?;
+        delete_expired_files(&self.old_directory, self.old_file_count)?;
+        let previous_path = archive_current_file(
+            &self.current_path,
+            &self.old_directory,
+            &self.file_extension,
+        )?;
+        self.current_file = create_current_file(&self.current_path)?;
+        self.created = Instant::now();
+        self.compression = finish_archive(previous_path, &self.old_directory, &self.rotation)?;
+
+        Ok(())
+    }
+
+    /// blocks until the previous current file is compressed
+    pub fn finish_compression(&mut self) -> RisResult<()> {
+        let Some(compression) = self.compression.take() else {
+            return Ok(());
+        };
+
+        match compression.join() {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => ris_error::new_result!("failed to compress old file: {}", e),
+            Err(_) => ris_error::new_result!("compression thread panicked"),
+        }
+    }
+}
+
+impl Drop for FallbackFileAppend {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish_compression() {
+            // the log may write to this file, thus logging is not an option
+            eprintln!("{}", e);
+        }
+    }
 }
 
 pub struct FallbackFileOverwrite {
@@ -51,26 +172,25 @@ impl FallbackFileOverwrite {
         }
     }
 
+    /// the current file is replaced atomically. the previous current file is copied to the old
+    /// directory beforehand, thus a crash at any point leaves a complete current file behind.
     pub fn overwrite_current(&self, buf: &[u8]) -> RisResult<()> {
         std::fs::create_dir_all(&self.old_directory)?;
         delete_expired_files(&self.old_directory, self.old_file_count)?;
-        move_current_file(
+
+        let mut current_file = AtomicFile::create(&self.current_path)?;
+        write_date(current_file.file())?;
+        current_file.file().write_all(buf)?;
+
+        if let Some(previous_path) = previous_path(
             &self.current_path,
             &self.old_directory,
             &self.file_extension,
-        )?;
-        let mut current_file = create_current_file(&self.current_path)?;
-
-        let written_bytes = current_file.write(buf)?;
-        if written_bytes != buf.len() {
-            ris_error::new_result!(
-                "failed to write to current file. expected to write {} bytes but actually wrote {}",
-                buf.len(),
-                written_bytes,
-            )
-        } else {
-            Ok(())
+        )? {
+            std::fs::copy(&self.current_path, previous_path)?;
         }
+
+        current_file.commit()
     }
 
     pub fn available_paths(&self) -> Vec<PathBuf> {
@@ -107,6 +227,130 @@ impl FallbackFileOverwrite {
     }
 }
 
+/// a file, which replaces the file at `path` only once it is committed. until then, everything is
+/// written to a temporary file next to it, which is flushed to disk and then renamed to `path`.
+/// thus `path` either holds its previous content or the complete new content, even if the
+/// process is interrupted while writing.
+///
+/// dropping it without committing deletes the temporary file and leaves `path` untouched.
+pub struct AtomicFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    file: Option<File>,
+}
+
+impl AtomicFile {
+    pub fn create(path: impl AsRef<Path>) -> RisResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let temp_path = temp_path(&path)?;
+
+        // a temporary file left behind by an interrupted write is truncated
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&temp_path)?;
+
+        Ok(Self {
+            path,
+            temp_path,
+            file: Some(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    pub fn file(&mut self) -> &mut File {
+        match self.file.as_mut() {
+            Some(file) => file,
+            None => ris_error::throw!("atomic file was already committed"),
+        }
+    }
+
+    /// flushes the temporary file to disk and renames it to the target path
+    pub fn commit(mut self) -> RisResult<()> {
+        let Some(file) = self.file.take() else {
+            return ris_error::new_result!("atomic file was already committed");
+        };
+
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(&self.temp_path, &self.path)?;
+        sync_parent_directory(&self.path);
+
+        Ok(())
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if let Some(file) = self.file.take() {
+            drop(file);
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+impl Read for AtomicFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.file().read(buf)
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file().flush()
+    }
+}
+
+impl Seek for AtomicFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.file().seek(pos)
+    }
+}
+
+/// writes `buf` to `path` atomically. see `AtomicFile`.
+pub fn write_atomic(path: impl AsRef<Path>, buf: &[u8]) -> RisResult<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.file().write_all(buf)?;
+    file.commit()
+}
+
+fn temp_path(path: &Path) -> RisResult<PathBuf> {
+    let Some(file_name) = path.file_name() else {
+        return ris_error::new_result!("path has no file name: {:?}", path);
+    };
+
+    let mut temp_file_name = file_name.to_os_string();
+    temp_file_name.push(".tmp");
+    Ok(path.with_file_name(temp_file_name))
+}
+
+// the rename is only durable, once the directory entry is flushed as well. directories cannot be
+// opened as files on every platform, thus this is done on a best effort basis.
+fn sync_parent_directory(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    if let Ok(directory) = File::open(parent) {
+        let _ = directory.sync_all();
+    }
+}
+
 fn generate_paths(directory: &Path, file_extension: &str) -> (PathBuf, PathBuf) {
     let mut current_path = PathBuf::new();
     current_path.push(directory);
@@ -152,13 +396,94 @@ fn get_sorted_entries(directory: &Path) -> RisResult<Vec<PathBuf>> {
     Ok(result)
 }
 
-fn move_current_file(
+/// moves the current file to the old directory. returns where it was moved to.
+fn archive_current_file(
     current_path: &Path,
     old_directory: &Path,
     file_extension: &str,
-) -> RisResult<()> {
-    if !current_path.exists() {
+) -> RisResult<Option<PathBuf>> {
+    let Some(previous_path) = previous_path(current_path, old_directory, file_extension)? else {
+        return Ok(None);
+    };
+
+    std::fs::rename(current_path, &previous_path)?;
+
+    Ok(Some(previous_path))
+}
+
+/// compresses the archived file and deletes the files exceeding the total size. when
+/// compressing, both happen on the returned thread, because the size of the old directory is
+/// only known once the file is compressed.
+fn finish_archive(
+    previous_path: Option<PathBuf>,
+    old_directory: &Path,
+    rotation: &Rotation,
+) -> RisResult<Option<JoinHandle<Result<(), String>>>> {
+    let previous_path = match previous_path {
+        Some(previous_path) if rotation.compress => previous_path,
+        _ => {
+            delete_exceeding_files(old_directory, rotation)?;
+            return Ok(None);
+        }
+    };
+
+    let old_directory = old_directory.to_path_buf();
+    let rotation = rotation.clone();
+    let handle = std::thread::Builder::new()
+        .name("compress_old_file".to_string())
+        .spawn(move || {
+            compress(&previous_path)
+                .and_then(|()| delete_exceeding_files(&old_directory, &rotation))
+                .map_err(|e| e.to_string())
+        })?;
+
+    Ok(Some(handle))
+}
+
+/// replaces the file at `path` with its gzip compressed counterpart
+fn compress(path: &Path) -> RisResult<()> {
+    let bytes = std::fs::read(path)?;
+    let compressed = gzip(&bytes);
+    write_atomic(compressed_path(path), &compressed)?;
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+/// deletes the oldest files, until the old directory fits into the total size of the rotation
+fn delete_exceeding_files(old_directory: &Path, rotation: &Rotation) -> RisResult<()> {
+    let Some(max_total_size) = rotation.max_total_size else {
         return Ok(());
+    };
+
+    let budget = max_total_size.saturating_sub(rotation.max_file_size.unwrap_or(0));
+    let mut total_size = 0u64;
+
+    for entry in get_sorted_entries(old_directory)? {
+        let metadata = entry.metadata()?;
+        total_size = total_size.saturating_add(metadata.len());
+
+        if total_size <= budget {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            let _ = std::fs::remove_dir_all(entry);
+        } else {
+            let _ = std::fs::remove_file(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// a unique path in the old directory for the current file. `None` if there is no current file.
+fn previous_path(
+    current_path: &Path,
+    old_directory: &Path,
+    file_extension: &str,
+) -> RisResult<Option<PathBuf>> {
+    if !current_path.exists() {
+        return Ok(None);
     }
 
     let file = File::open(current_path)?;
@@ -178,7 +503,7 @@ fn move_current_file(
 
     let attempts = 100;
     for _ in 0..attempts {
-        if !previous_path.exists() {
+        if !previous_path.exists() && !compressed_path(&previous_path).exists() {
             break;
         }
 
@@ -191,23 +516,75 @@ fn move_current_file(
         previous_path.push(sanitized_new_previous_filename);
     }
 
-    if previous_path.exists() {
+    if previous_path.exists() || compressed_path(&previous_path).exists() {
         ris_error::new_result!("failed to generate a unique old filename")
     } else {
-        std::fs::rename(current_path, &previous_path)?;
+        Ok(Some(previous_path))
+    }
+}
 
-        Ok(())
+fn compressed_path(path: &Path) -> PathBuf {
+    let mut compressed_path = path.as_os_str().to_os_string();
+    compressed_path.push(GZIP_EXTENSION);
+    PathBuf::from(compressed_path)
+}
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let deflated = miniz_oxide::deflate::compress_to_vec(bytes, GZIP_COMPRESSION_LEVEL);
+
+    // header: magic, deflate, no flags, no mtime, no extra flags, unknown os
+    let mut result = Vec::with_capacity(deflated.len() + 18);
+    result.extend_from_slice(&[0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff]);
+    result.extend_from_slice(&deflated);
+    result.extend_from_slice(&crc32(bytes).to_le_bytes());
+    result.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    result
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
     }
+
+    table
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc = CRC32_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
 }
 
 fn create_current_file(current_path: &Path) -> RisResult<File> {
     let mut current_file = File::create(current_path)?;
-
-    writeln!(current_file, "{}\n", Local::now().to_rfc3339())?;
-
+    write_date(&mut current_file)?;
     Ok(current_file)
 }
 
+fn write_date(file: &mut File) -> RisResult<()> {
+    writeln!(file, "{}\n", Local::now().to_rfc3339())?;
+    Ok(())
+}
+
 fn read_file_and_strip_date(file: &mut File) -> RisResult<Vec<u8>> {
     let file_size = crate::seek(file, SeekFrom::End(0))?;
 
@@ -8,6 +8,9 @@ use std::io::Write;
 
 pub const ADDR_SIZE: usize = std::mem::size_of::<u64>();
 
+/// the most bytes an `u64` varint can take
+pub const MAX_VARINT_LEN: usize = 10;
+
 /// represents a sized memory location. used in combination with stream io operations.
 ///
 /// Example:
@@ -155,6 +158,34 @@ pub fn write_string(stream: &mut (impl Write + Seek), string: impl AsRef<str>) -
     FatPtr::begin_end(begin, end)
 }
 
+/// writes an `u64` as an unsigned LEB128 varint and advances the stream. each byte holds 7 bits,
+/// least significant first, and the high bit is set on all but the last byte. thus small values
+/// take less than the 8 bytes of `write_u64()`. returns a `FatPtr` to the bytes written.
+pub fn write_varint(stream: &mut (impl Write + Seek), value: u64) -> Result<FatPtr> {
+    let mut bytes = Vec::with_capacity(MAX_VARINT_LEN);
+    let mut value = value;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            break;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+
+    write(stream, &bytes)
+}
+
+/// writes an `i64` as a zigzag encoded varint and advances the stream. values close to zero take
+/// the fewest bytes, no matter their sign. returns a `FatPtr` to the bytes written.
+pub fn write_varint_signed(stream: &mut (impl Write + Seek), value: i64) -> Result<FatPtr> {
+    let zigzag = ((value << 1) ^ (value >> 63)) as u64;
+    write_varint(stream, zigzag)
+}
+
 //
 // read
 //
@@ -262,12 +293,52 @@ pub fn read_fat_ptr(stream: &mut impl Read) -> Result<FatPtr> {
     Ok(FatPtr { addr, len })
 }
 
+/// reads an unsigned LEB128 varint and advances the stream. returns an error if the varint does
+/// not fit into an `u64`. see `write_varint()`.
+pub fn read_varint(stream: &mut impl Read) -> Result<u64> {
+    let mut result = 0;
+    for i in 0..MAX_VARINT_LEN {
+        let byte = read_u8(stream)?;
+        let bits = (byte & 0x7F) as u64;
+
+        // the last byte only has room for the most significant bit
+        if i == MAX_VARINT_LEN - 1 && bits > 1 {
+            return Err(Error::from(ErrorKind::InvalidData));
+        }
+
+        result |= bits << (i * 7);
+
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+
+    Err(Error::from(ErrorKind::InvalidData))
+}
+
+/// reads a zigzag encoded varint and advances the stream. see `write_varint_signed()`.
+pub fn read_varint_signed(stream: &mut impl Read) -> Result<i64> {
+    let zigzag = read_varint(stream)?;
+    let value = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+    Ok(value)
+}
+
 /// reads a string and advances the stream. it does so by reading a `u32`, and then reads that many
 /// UTF-8 encoded bytes.
+///
+/// the len is not trusted to reserve memory. use `BoundedReader::read_string()` to reject
+/// lengths that exceed the stream, before reading anything.
 pub fn read_string(stream: &mut (impl Read + Seek)) -> Result<String> {
     let length = read_uint(stream)?;
-    let mut bytes = vec![0; length];
-    read(stream, &mut bytes)?;
+    let mut bytes = Vec::new();
+    stream
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() != length {
+        return Err(Error::from(ErrorKind::InvalidData));
+    }
+
     let string = String::from_utf8(bytes).map_err(|_| Error::from(ErrorKind::InvalidData))?;
     Ok(string)
 }
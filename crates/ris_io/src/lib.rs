@@ -1,8 +1,17 @@
+pub mod bounded_reader;
 pub mod fallback_file;
 pub mod io;
 pub mod io_math;
 pub mod path;
+pub mod pod;
+pub mod serialize;
 pub mod util;
 
+pub use bounded_reader::*;
 pub use io::*;
 pub use io_math::*;
+pub use pod::*;
+pub use serialize::*;
+
+pub use ris_io_derive::RisDeserialize;
+pub use ris_io_derive::RisSerialize;
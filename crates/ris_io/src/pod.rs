@@ -0,0 +1,113 @@
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::Write;
+
+use ris_math::color::OkLab;
+use ris_math::color::OkLch;
+use ris_math::color::Rgb;
+use ris_math::matrix::Mat2;
+use ris_math::matrix::Mat2x3;
+use ris_math::matrix::Mat2x4;
+use ris_math::matrix::Mat3;
+use ris_math::matrix::Mat3x2;
+use ris_math::matrix::Mat3x4;
+use ris_math::matrix::Mat4;
+use ris_math::matrix::Mat4x2;
+use ris_math::matrix::Mat4x3;
+use ris_math::quaternion::Quat;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+use ris_math::vector::Vec4;
+
+use crate::FatPtr;
+
+/// plain old data, which can be copied from and to bytes as a whole.
+///
+/// # Safety
+///
+/// the type must have a defined layout (`#[repr(C)]` or a primitive), must not contain padding
+/// and every bit pattern must be a valid value. it must consist only of scalars which are
+/// `SCALAR_SIZE` bytes large, such that swapping each `SCALAR_SIZE` chunk converts it between
+/// host and little-endian byte order.
+pub unsafe trait Pod: Copy + 'static {
+    const SCALAR_SIZE: usize;
+}
+
+macro_rules! impl_pod {
+    ($scalar_size:expr, $($type:ty),*) => {
+        $(
+            unsafe impl Pod for $type {
+                const SCALAR_SIZE: usize = $scalar_size;
+            }
+        )*
+    };
+}
+
+impl_pod!(1, u8, i8);
+impl_pod!(2, u16, i16);
+impl_pod!(4, u32, i32, f32);
+impl_pod!(8, u64, i64, f64);
+impl_pod!(4, Vec2, Vec3, Vec4, Quat, Rgb, OkLab, OkLch);
+impl_pod!(4, Mat2, Mat2x3, Mat2x4, Mat3x2, Mat3, Mat3x4, Mat4x2, Mat4x3, Mat4);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+    const SCALAR_SIZE: usize = T::SCALAR_SIZE;
+}
+
+/// writes all values as little-endian and advances the stream. unlike writing element by element,
+/// this copies the slice as a whole on little-endian hosts. no len is written. returns a `FatPtr`
+/// to the bytes written.
+pub fn write_slice<T: Pod>(stream: &mut (impl Write + Seek), values: &[T]) -> Result<FatPtr> {
+    let bytes = as_bytes(values);
+
+    if cfg!(target_endian = "little") {
+        crate::write(stream, bytes)
+    } else {
+        let mut swapped = bytes.to_vec();
+        swap_scalars(&mut swapped, T::SCALAR_SIZE);
+        crate::write(stream, &swapped)
+    }
+}
+
+/// reads little-endian values until `values` is filled and advances the stream. see
+/// `write_slice()`.
+pub fn read_slice<T: Pod>(stream: &mut impl Read, values: &mut [T]) -> Result<()> {
+    let len = std::mem::size_of_val(values);
+
+    // sound, because `Pod` has no padding and any bit pattern is valid
+    let bytes = unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, len) };
+    stream.read_exact(bytes)?;
+
+    if cfg!(target_endian = "big") {
+        swap_scalars(bytes, T::SCALAR_SIZE);
+    }
+
+    Ok(())
+}
+
+/// reads `len` little-endian values and advances the stream. see `write_slice()`.
+///
+/// `len` is used to allocate memory. if it comes from an untrusted source, validate it first,
+/// for example with `BoundedReader::read_len()`.
+pub fn read_vec<T: Pod>(stream: &mut impl Read, len: usize) -> Result<Vec<T>> {
+    // sound, because any bit pattern of a `Pod` is valid
+    let zeroed = unsafe { std::mem::zeroed::<T>() };
+    let mut values = vec![zeroed; len];
+    read_slice(stream, &mut values)?;
+    Ok(values)
+}
+
+/// the bytes of `values` in host byte order
+pub fn as_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    let len = std::mem::size_of_val(values);
+
+    // sound, because `Pod` has no padding
+    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, len) }
+}
+
+fn swap_scalars(bytes: &mut [u8], scalar_size: usize) {
+    for scalar in bytes.chunks_exact_mut(scalar_size) {
+        scalar.reverse();
+    }
+}
@@ -0,0 +1,216 @@
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use ris_math::matrix::Mat2;
+use ris_math::matrix::Mat2x3;
+use ris_math::matrix::Mat2x4;
+use ris_math::matrix::Mat3;
+use ris_math::matrix::Mat3x2;
+use ris_math::matrix::Mat3x4;
+use ris_math::matrix::Mat4;
+use ris_math::matrix::Mat4x2;
+use ris_math::matrix::Mat4x3;
+use ris_math::quaternion::Quat;
+use ris_math::vector::Bvec2;
+use ris_math::vector::Bvec3;
+use ris_math::vector::Bvec4;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+use ris_math::vector::Vec4;
+
+use crate::FatPtr;
+
+/// a value that can be written to a stream. the format must match `RisDeserialize`.
+///
+/// can be derived for structs, which serializes all fields in declaration order.
+pub trait RisSerialize {
+    /// writes and advances the stream. returns a `FatPtr` to the bytes written.
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr>;
+}
+
+/// a value that can be read from a stream. the format must match `RisSerialize`.
+///
+/// can be derived for structs, which deserializes all fields in declaration order.
+pub trait RisDeserialize: Sized {
+    /// reads and advances the stream.
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self>;
+}
+
+/// serializes `value` and advances the stream. returns a `FatPtr` to the bytes written.
+pub fn serialize(stream: &mut (impl Write + Seek), value: &impl RisSerialize) -> Result<FatPtr> {
+    value.serialize(stream)
+}
+
+/// deserializes a `T` and advances the stream.
+pub fn deserialize<T: RisDeserialize>(stream: &mut (impl Read + Seek)) -> Result<T> {
+    T::deserialize(stream)
+}
+
+macro_rules! impl_le_bytes {
+    ($($type:ty),*) => {
+        $(
+            impl RisSerialize for $type {
+                fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+                    crate::write(stream, &self.to_le_bytes())
+                }
+            }
+
+            impl RisDeserialize for $type {
+                fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$type>()];
+                    crate::read(stream, &mut bytes)?;
+                    Ok(<$type>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_le_bytes!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+macro_rules! impl_with {
+    ($type:ty, $write:path, $read:path) => {
+        impl RisSerialize for $type {
+            fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+                $write(stream, *self)
+            }
+        }
+
+        impl RisDeserialize for $type {
+            fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+                $read(stream)
+            }
+        }
+    };
+}
+
+impl_with!(usize, crate::write_uint, crate::read_uint);
+impl_with!(isize, crate::write_int, crate::read_int);
+impl_with!(bool, crate::write_bool, crate::read_bool);
+impl_with!(FatPtr, crate::write_fat_ptr, crate::read_fat_ptr);
+impl_with!(Vec2, crate::write_vec2, crate::read_vec2);
+impl_with!(Vec3, crate::write_vec3, crate::read_vec3);
+impl_with!(Vec4, crate::write_vec4, crate::read_vec4);
+impl_with!(Bvec2, crate::write_bvec2, crate::read_bvec2);
+impl_with!(Bvec3, crate::write_bvec3, crate::read_bvec3);
+impl_with!(Bvec4, crate::write_bvec4, crate::read_bvec4);
+impl_with!(Quat, crate::write_quat, crate::read_quat);
+impl_with!(Mat2, crate::write_mat2, crate::read_mat2);
+impl_with!(Mat2x3, crate::write_mat2x3, crate::read_mat2x3);
+impl_with!(Mat2x4, crate::write_mat2x4, crate::read_mat2x4);
+impl_with!(Mat3x2, crate::write_mat3x2, crate::read_mat3x2);
+impl_with!(Mat3, crate::write_mat3, crate::read_mat3);
+impl_with!(Mat3x4, crate::write_mat3x4, crate::read_mat3x4);
+impl_with!(Mat4x2, crate::write_mat4x2, crate::read_mat4x2);
+impl_with!(Mat4x3, crate::write_mat4x3, crate::read_mat4x3);
+impl_with!(Mat4, crate::write_mat4, crate::read_mat4);
+
+impl RisSerialize for str {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        crate::write_string(stream, self)
+    }
+}
+
+impl RisSerialize for String {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        crate::write_string(stream, self)
+    }
+}
+
+impl RisDeserialize for String {
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+        crate::read_string(stream)
+    }
+}
+
+/// the len as an `u32`, followed by the elements
+impl<T: RisSerialize> RisSerialize for [T] {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        let begin = crate::seek(stream, SeekFrom::Current(0))?;
+        crate::write_uint(stream, self.len())?;
+        for value in self.iter() {
+            value.serialize(stream)?;
+        }
+        let end = crate::seek(stream, SeekFrom::Current(0))?;
+        FatPtr::begin_end(begin, end)
+    }
+}
+
+impl<T: RisSerialize> RisSerialize for Vec<T> {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        self.as_slice().serialize(stream)
+    }
+}
+
+impl<T: RisDeserialize> RisDeserialize for Vec<T> {
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+        let len = crate::read_uint(stream)?;
+
+        // the len is not trusted to reserve memory, because a corrupted len may be huge
+        let mut result = Vec::new();
+        for _ in 0..len {
+            result.push(T::deserialize(stream)?);
+        }
+
+        Ok(result)
+    }
+}
+
+/// the elements without a len, as it is known at compile time
+impl<T: RisSerialize, const N: usize> RisSerialize for [T; N] {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        let begin = crate::seek(stream, SeekFrom::Current(0))?;
+        for value in self.iter() {
+            value.serialize(stream)?;
+        }
+        let end = crate::seek(stream, SeekFrom::Current(0))?;
+        FatPtr::begin_end(begin, end)
+    }
+}
+
+impl<T: RisDeserialize, const N: usize> RisDeserialize for [T; N] {
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+        let mut result = Vec::with_capacity(N);
+        for _ in 0..N {
+            result.push(T::deserialize(stream)?);
+        }
+
+        result
+            .try_into()
+            .map_err(|_| Error::from(ErrorKind::InvalidData))
+    }
+}
+
+/// a bool, followed by the value if it is `true`
+impl<T: RisSerialize> RisSerialize for Option<T> {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        let begin = crate::seek(stream, SeekFrom::Current(0))?;
+        crate::write_bool(stream, self.is_some())?;
+        if let Some(value) = self {
+            value.serialize(stream)?;
+        }
+        let end = crate::seek(stream, SeekFrom::Current(0))?;
+        FatPtr::begin_end(begin, end)
+    }
+}
+
+impl<T: RisDeserialize> RisDeserialize for Option<T> {
+    fn deserialize(stream: &mut (impl Read + Seek)) -> Result<Self> {
+        if crate::read_bool(stream)? {
+            Ok(Some(T::deserialize(stream)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: RisSerialize + ?Sized> RisSerialize for &T {
+    fn serialize(&self, stream: &mut (impl Write + Seek)) -> Result<FatPtr> {
+        (**self).serialize(stream)
+    }
+}
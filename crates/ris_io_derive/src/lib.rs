@@ -0,0 +1,129 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::parse_quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::Generics;
+use syn::Index;
+
+/// implements `ris_io::RisSerialize` by serializing all fields in declaration order
+#[proc_macro_derive(RisSerialize)]
+pub fn derive_ris_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match struct_fields(&input, "RisSerialize") {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let writes = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                quote! { ::ris_io::RisSerialize::serialize(&self.#ident, stream)?; }
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| {
+                let index = Index::from(i);
+                quote! { ::ris_io::RisSerialize::serialize(&self.#index, stream)?; }
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let generics = add_bound(&input.generics, quote! { ::ris_io::RisSerialize });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::ris_io::RisSerialize for #name #ty_generics #where_clause {
+            fn serialize(
+                &self,
+                stream: &mut (impl ::std::io::Write + ::std::io::Seek),
+            ) -> ::std::io::Result<::ris_io::FatPtr> {
+                let begin = ::ris_io::seek(stream, ::std::io::SeekFrom::Current(0))?;
+                #(#writes)*
+                let end = ::ris_io::seek(stream, ::std::io::SeekFrom::Current(0))?;
+                ::ris_io::FatPtr::begin_end(begin, end)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// implements `ris_io::RisDeserialize` by deserializing all fields in declaration order
+#[proc_macro_derive(RisDeserialize)]
+pub fn derive_ris_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match struct_fields(&input, "RisDeserialize") {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let read = quote! { ::ris_io::RisDeserialize::deserialize(stream)? };
+    let construct = match fields {
+        Fields::Named(fields) => {
+            let reads = fields.named.iter().map(|field| {
+                let ident = &field.ident;
+                quote! { #ident: #read, }
+            });
+            quote! { Self { #(#reads)* } }
+        }
+        Fields::Unnamed(fields) => {
+            let reads = fields.unnamed.iter().map(|_| quote! { #read, });
+            quote! { Self ( #(#reads)* ) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    let generics = add_bound(&input.generics, quote! { ::ris_io::RisDeserialize });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::ris_io::RisDeserialize for #name #ty_generics #where_clause {
+            fn deserialize(
+                stream: &mut (impl ::std::io::Read + ::std::io::Seek),
+            ) -> ::std::io::Result<Self> {
+                Ok(#construct)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+fn struct_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for structs", derive),
+        )),
+    }
+}
+
+fn add_bound(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let params = generics
+        .type_params()
+        .map(|x| x.ident.clone())
+        .collect::<Vec<_>>();
+
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote! { #param: #bound });
+    }
+
+    generics
+}
@@ -9,6 +9,20 @@ impl Job {
         Self { to_invoke }
     }
 
+    /// # Safety
+    ///
+    /// `to_invoke` may borrow data, which does not live for `'static`.
+    /// * Clientcode **MUST** ensure, that the job is invoked or dropped before the borrowed data
+    ///   goes out of scope.
+    pub unsafe fn new_unchecked<'a, F: FnOnce() + 'a>(to_invoke: F) -> Self {
+        let to_invoke: Box<dyn FnOnce() + 'a> = Box::new(to_invoke);
+        let to_invoke: Box<dyn FnOnce()> = unsafe { std::mem::transmute(to_invoke) };
+
+        Self {
+            to_invoke: Some(to_invoke),
+        }
+    }
+
     pub fn invoke(&mut self) {
         if let Some(to_invoke) = self.to_invoke.take() {
             to_invoke();
@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::TryLockError;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+
+use crate::job::Job;
+use crate::job_future::JobFuture;
+use crate::job_future::SettableJobFuture;
+use crate::job_system;
+use crate::job_system::JobPriority;
+
+type BoxedFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// drives `future` on the workers of the job system. the future is polled as a job and polled
+/// again as a new job whenever it is woken, so awaiting never blocks a worker.
+///
+/// if the calling thread does not belong to a job system, the future is polled on the thread
+/// that wakes it.
+pub fn spawn<T: 'static, F: Future<Output = T> + 'static>(future: F) -> JobFuture<T> {
+    let (settable_future, result) = SettableJobFuture::new();
+
+    let task = Arc::new(Task {
+        future: Mutex::new(Some(Box::pin(async move {
+            let value = future.await;
+            settable_future.set(value);
+        }))),
+        is_notified: AtomicBool::new(false),
+        priority: job_system::current_priority(),
+    });
+
+    schedule(task);
+
+    result
+}
+
+/// polls `future` on the calling thread until it completes. runs pending jobs while the future
+/// is not ready, such that it may await futures of jobs submitted by the calling thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+
+    let signal = Arc::new(Signal {
+        is_woken: AtomicBool::new(false),
+    });
+    let waker = Waker::from(signal.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+
+        while !signal.is_woken.swap(false, Ordering::SeqCst) {
+            job_system::run_pending_job(file!(), line!());
+        }
+    }
+}
+
+struct Task {
+    /// `None` once the future has completed
+    future: Mutex<Option<BoxedFuture>>,
+    /// set by wakers. a waker that finds the task being polled leaves the poll to the thread
+    /// that currently polls it.
+    is_notified: AtomicBool,
+    /// the priority of the job that spawned the task. every poll is scheduled with it.
+    priority: JobPriority,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        schedule(self);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        schedule(self.clone());
+    }
+}
+
+fn schedule(task: Arc<Task>) {
+    let priority = task.priority;
+    job_system::schedule(Job::new(move || poll(task)), priority);
+}
+
+fn poll(task: Arc<Task>) {
+    task.is_notified.store(true, Ordering::SeqCst);
+
+    loop {
+        let mut future = match task.future.try_lock() {
+            Ok(future) => future,
+            // the thread that holds the lock polls again, because the task is notified
+            Err(TryLockError::WouldBlock) => return,
+            Err(TryLockError::Poisoned(e)) => ris_error::throw!("mutex is poisoned: {}", e),
+        };
+
+        if !task.is_notified.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        let Some(inner) = future.as_mut() else {
+            return;
+        };
+
+        let waker = Waker::from(task.clone());
+        let mut cx = Context::from_waker(&waker);
+        if inner.as_mut().poll(&mut cx).is_ready() {
+            *future = None;
+            return;
+        }
+
+        drop(future);
+
+        if !task.is_notified.load(Ordering::SeqCst) {
+            return;
+        }
+    }
+}
+
+struct Signal {
+    is_woken: AtomicBool,
+}
+
+impl Wake for Signal {
+    fn wake(self: Arc<Self>) {
+        self.is_woken.store(true, Ordering::SeqCst);
+    }
+}
+
+// the future is only ever accessed by the thread that holds the lock
+unsafe impl Send for Task {}
+unsafe impl Sync for Task {}
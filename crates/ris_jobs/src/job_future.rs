@@ -1,20 +1,49 @@
+use std::any::Any;
 use std::cell::UnsafeCell;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
 use std::time::Duration;
 use std::time::Instant;
 
+use crate::job::Job;
 use crate::job_system;
 
 pub struct SettableJobFuture<T> {
     is_ready: Arc<AtomicBool>,
-    data: Arc<UnsafeCell<Option<T>>>,
+    data: Arc<FutureData<T>>,
+    continuations: Arc<Mutex<Continuations>>,
 }
 
 pub struct JobFuture<T> {
     is_ready: Arc<AtomicBool>,
-    data: Arc<UnsafeCell<Option<T>>>,
+    data: Arc<FutureData<T>>,
+    continuations: Arc<Mutex<Continuations>>,
+}
+
+/// the result of a future. a panic, that was never taken, is passed to the panic handler of the
+/// job system when it is dropped. see `job_system::set_panic_handler()`
+struct FutureData<T>(UnsafeCell<Option<std::thread::Result<T>>>);
+
+/// something a job can depend on. see `job_system::submit_after()`
+pub trait JobDependency {
+    /// invokes `job` on the thread that completes the dependency. if the dependency is already
+    /// completed, `job` is invoked immediately on the calling thread.
+    fn on_ready(&self, job: Job);
+}
+
+#[derive(Default)]
+struct Continuations {
+    is_done: bool,
+    jobs: Vec<Job>,
+    waker: Option<Waker>,
 }
 
 #[derive(Debug)]
@@ -37,20 +66,53 @@ impl std::error::Error for TimeoutError {}
 impl<T> SettableJobFuture<T> {
     pub fn new() -> (SettableJobFuture<T>, JobFuture<T>) {
         let is_ready = Arc::new(AtomicBool::new(false));
-        let data = Arc::new(UnsafeCell::new(None));
+        let data = Arc::new(FutureData(UnsafeCell::new(None)));
+        let continuations = Arc::new(Mutex::new(Continuations::default()));
 
         let settable_job_future = SettableJobFuture {
             is_ready: is_ready.clone(),
             data: data.clone(),
+            continuations: continuations.clone(),
+        };
+        let job_future = JobFuture {
+            is_ready,
+            data,
+            continuations,
         };
-        let job_future = JobFuture { is_ready, data };
 
         (settable_job_future, job_future)
     }
 
     pub fn set(self, result: T) {
-        unsafe { *self.data.get() = Some(result) };
+        self.complete(Ok(result));
+    }
+
+    /// completes the future with the payload of a panic. the panic is resumed on the thread that
+    /// takes the result.
+    pub fn set_panic(self, payload: Box<dyn Any + Send>) {
+        self.complete(Err(payload));
+    }
+
+    fn complete(self, result: std::thread::Result<T>) {
+        unsafe { *self.data.0.get() = Some(result) };
         self.is_ready.store(true, Ordering::SeqCst);
+
+        let (jobs, waker) = {
+            let mut continuations = lock_continuations(&self.continuations);
+            continuations.is_done = true;
+            (
+                std::mem::take(&mut continuations.jobs),
+                continuations.waker.take(),
+            )
+        };
+
+        for mut job in jobs {
+            job.invoke();
+        }
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
     }
 }
 
@@ -72,10 +134,39 @@ impl<T> JobFuture<T> {
         }
     }
 
+    /// submits `f` with the result of this future, once it is ready. unlike `wait()`, this
+    /// does not block the calling thread.
+    pub fn then<U: 'static, F: FnOnce(T) -> U + 'static>(self, f: F) -> JobFuture<U>
+    where
+        T: 'static,
+    {
+        let (settable_future, future) = SettableJobFuture::new();
+
+        let continuations = self.continuations.clone();
+        let job = Job::new(move || {
+            let result = f(self.take());
+            settable_future.set(result);
+        });
+
+        // runs on the thread that completes this future, thus the continuation inherits the
+        // priority of the job that completed it
+        on_ready(
+            &continuations,
+            Job::new(move || job_system::schedule(job, job_system::current_priority())),
+        );
+
+        future
+    }
+
     fn take(self) -> T {
-        let result = unsafe { (*self.data.get()).take() };
+        self.take_data()
+    }
+
+    fn take_data(&self) -> T {
+        let result = unsafe { (*self.data.0.get()).take() };
         match result {
-            Some(value) => value,
+            Some(Ok(value)) => value,
+            Some(Err(payload)) => std::panic::resume_unwind(payload),
             None => ris_error::throw!("cannot take data, because it does not exist"),
         }
     }
@@ -84,12 +175,70 @@ impl<T> JobFuture<T> {
 impl<T: Default> JobFuture<T> {
     pub fn done() -> Self {
         let is_ready = Arc::new(AtomicBool::new(true));
-        let data = Arc::new(UnsafeCell::new(Some(T::default())));
+        let data = Arc::new(FutureData(UnsafeCell::new(Some(Ok(T::default())))));
+        let continuations = Arc::new(Mutex::new(Continuations {
+            is_done: true,
+            jobs: Vec::new(),
+            waker: None,
+        }));
 
-        Self { is_ready, data }
+        Self {
+            is_ready,
+            data,
+            continuations,
+        }
     }
 }
 
+/// allows to `.await` a `JobFuture` in an async task. see `job_executor::spawn()`
+impl<T> Future for JobFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut continuations = lock_continuations(&self.continuations);
+        if continuations.is_done {
+            drop(continuations);
+            Poll::Ready(self.take_data())
+        } else {
+            // only the most recent waker needs to be woken
+            match &continuations.waker {
+                Some(waker) if waker.will_wake(cx.waker()) => (),
+                _ => continuations.waker = Some(cx.waker().clone()),
+            }
+
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> JobDependency for JobFuture<T> {
+    fn on_ready(&self, job: Job) {
+        on_ready(&self.continuations, job);
+    }
+}
+
+impl<T> Drop for FutureData<T> {
+    fn drop(&mut self) {
+        if let Some(Err(payload)) = self.0.get_mut().take() {
+            job_system::handle_panic(payload.as_ref());
+        }
+    }
+}
+
+fn on_ready(continuations: &Mutex<Continuations>, mut job: Job) {
+    let mut continuations = lock_continuations(continuations);
+    if continuations.is_done {
+        drop(continuations);
+        job.invoke();
+    } else {
+        continuations.jobs.push(job);
+    }
+}
+
+fn lock_continuations(continuations: &Mutex<Continuations>) -> MutexGuard<'_, Continuations> {
+    ris_error::unwrap!(continuations.lock(), "mutex is poisoned")
+}
+
 fn spinlock(is_ready: Arc<AtomicBool>) {
     while !is_ready.load(Ordering::SeqCst) {
         job_system::run_pending_job(file!(), line!());
@@ -113,6 +262,7 @@ fn spinlock_with_timeout(is_ready: Arc<AtomicBool>, timout: Duration) -> Result<
     Ok(())
 }
 
+unsafe impl Send for Continuations {}
 unsafe impl<T> Send for SettableJobFuture<T> {}
 unsafe impl<T> Sync for SettableJobFuture<T> {}
 unsafe impl<T> Send for JobFuture<T> {}
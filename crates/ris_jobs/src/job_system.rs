@@ -1,5 +1,11 @@
+use std::any::Any;
+use std::cell::Cell;
 use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -10,25 +16,223 @@ use std::sync::RwLockWriteGuard;
 use std::sync::TryLockError;
 use std::thread;
 use std::thread::JoinHandle;
+use std::time::Instant;
 
-use crate as ris_jobs;
 use crate::errors::BlockedOrEmpty;
 use crate::errors::IsEmpty;
 use crate::job::Job;
 use crate::job_buffer::JobBuffer;
+use crate::job_future::JobDependency;
 use crate::job_future::JobFuture;
 use crate::job_future::SettableJobFuture;
+use crate::job_trace::JobTrace;
+use crate::job_trace::WaitKind;
+use crate::job_trace::WorkerTrace;
+use crate::job_trace::WorkerUtilization;
+
+/// see `set_panic_handler()`
+pub type PanicHandler = Arc<dyn Fn(&(dyn Any + Send)) + Send + Sync>;
+
+static PANIC_HANDLER: RwLock<Option<PanicHandler>> = RwLock::new(None);
 
 thread_local! {
     static WORKER_THREAD: RefCell<Option<WorkerThread>> = const { RefCell::new(None) };
+    /// the priority of the job, which currently runs on this thread
+    static CURRENT_PRIORITY: Cell<JobPriority> = const { Cell::new(JobPriority::Normal) };
 }
 
 struct WorkerThread {
-    local_buffer: Arc<JobBuffer>,
-    steal_buffers: Vec<Arc<JobBuffer>>,
+    /// indexed by priority
+    local_buffers: Vec<Arc<JobBuffer>>,
+    /// indexed by priority
+    steal_buffers: Vec<Vec<Arc<JobBuffer>>>,
+    /// the worker index of each steal buffer
+    steal_indices: Vec<usize>,
+    shared: Arc<SharedState>,
     index: usize,
 }
 
+/// state, which is shared by all worker threads of a job system
+struct SharedState {
+    full_buffer_policy: AtomicUsize,
+    /// jobs that didn't fit into a buffer when the policy is `FullBufferPolicy::Grow`. indexed by
+    /// priority.
+    overflow: Vec<Mutex<VecDeque<Job>>>,
+    submitted: [AtomicUsize; PRIORITY_COUNT],
+    invoked: [AtomicUsize; PRIORITY_COUNT],
+    stolen: AtomicUsize,
+    run_inline: AtomicUsize,
+    blocked: AtomicUsize,
+    grown: AtomicUsize,
+    tracing: AtomicBool,
+    /// indexed by worker
+    traces: Vec<Mutex<WorkerTrace>>,
+}
+
+unsafe impl Send for SharedState {}
+unsafe impl Sync for SharedState {}
+
+const PRIORITY_COUNT: usize = 3;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JobPriority {
+    /// frame critical work
+    High,
+    #[default]
+    Normal,
+    /// long running work, like decoding assets. only picked up, when no high or normal job is
+    /// pending.
+    Background,
+}
+
+impl JobPriority {
+    pub const ALL: [JobPriority; PRIORITY_COUNT] = [
+        JobPriority::High,
+        JobPriority::Normal,
+        JobPriority::Background,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            JobPriority::High => 0,
+            JobPriority::Normal => 1,
+            JobPriority::Background => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for JobPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobPriority::High => write!(f, "high"),
+            JobPriority::Normal => write!(f, "normal"),
+            JobPriority::Background => write!(f, "background"),
+        }
+    }
+}
+
+/// what `submit` does, when the buffer of the calling thread is full
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FullBufferPolicy {
+    /// invokes the submitted job immediately on the calling thread
+    RunInline,
+    /// runs pending jobs on the calling thread, until the buffer has room for the submitted job
+    #[default]
+    Block,
+    /// stores the submitted job in an unbounded queue, which is shared by all threads
+    Grow,
+}
+
+impl FullBufferPolicy {
+    pub const ALL: [FullBufferPolicy; 3] = [
+        FullBufferPolicy::RunInline,
+        FullBufferPolicy::Block,
+        FullBufferPolicy::Grow,
+    ];
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => FullBufferPolicy::RunInline,
+            2 => FullBufferPolicy::Grow,
+            _ => FullBufferPolicy::Block,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            FullBufferPolicy::RunInline => 0,
+            FullBufferPolicy::Block => 1,
+            FullBufferPolicy::Grow => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for FullBufferPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FullBufferPolicy::RunInline => write!(f, "run inline"),
+            FullBufferPolicy::Block => write!(f, "block"),
+            FullBufferPolicy::Grow => write!(f, "grow"),
+        }
+    }
+}
+
+/// counters since the job system was initialized. arrays are indexed in the order of
+/// `JobPriority::ALL`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JobSystemStats {
+    pub submitted: [usize; PRIORITY_COUNT],
+    pub invoked: [usize; PRIORITY_COUNT],
+    /// jobs that were taken from the buffer of another thread
+    pub stolen: usize,
+    /// jobs that were invoked immediately, because the buffer was full
+    pub run_inline: usize,
+    /// submits that had to wait, because the buffer was full
+    pub blocked: usize,
+    /// jobs that were moved into the overflow queue, because the buffer was full
+    pub grown: usize,
+    /// jobs that are currently in the overflow queue
+    pub overflow: [usize; PRIORITY_COUNT],
+}
+
+struct PendingJob {
+    count: AtomicUsize,
+    job: Mutex<Option<Job>>,
+}
+
+unsafe impl Send for PendingJob {}
+unsafe impl Sync for PendingJob {}
+
+/// see `scope()`
+pub struct Scope<'scope, 'env: 'scope> {
+    pending: AtomicUsize,
+    a_job_panicked: AtomicBool,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope> Scope<'scope, '_> {
+    /// submits a job, which may borrow data from outside the scope. if the job panics, waiting on
+    /// its future resumes the panic. if the scope ends without resuming it, the scope panics.
+    ///
+    /// the job may run on another worker, thus it and its result must be `Send`, like with
+    /// `std::thread::Scope::spawn()`:
+    ///
+    /// ```compile_fail
+    /// let rc = std::rc::Rc::new(42);
+    /// ris_jobs::job_system::scope(|s| {
+    ///     s.spawn(|| *rc + 1);
+    /// });
+    /// ```
+    pub fn spawn<ReturnType: Send + 'scope, F: FnOnce() -> ReturnType + Send + 'scope>(
+        &'scope self,
+        job: F,
+    ) -> JobFuture<ReturnType> {
+        let (settable_future, future) = SettableJobFuture::new();
+
+        self.pending.fetch_add(1, Ordering::SeqCst);
+
+        // sound, because `scope()` does not return before all jobs are done
+        let job = unsafe {
+            Job::new_unchecked(move || {
+                match std::panic::catch_unwind(AssertUnwindSafe(job)) {
+                    Ok(result) => settable_future.set(result),
+                    Err(payload) => {
+                        self.a_job_panicked.store(true, Ordering::SeqCst);
+                        settable_future.set_panic(payload);
+                    }
+                }
+
+                self.pending.fetch_sub(1, Ordering::SeqCst);
+            })
+        };
+
+        schedule(job, current_priority());
+
+        future
+    }
+}
+
 pub struct JobSystemGuard {
     handles: Option<Vec<JoinHandle<()>>>,
     done: Arc<AtomicBool>,
@@ -82,22 +286,52 @@ pub fn init(
     }
 
     // setup job buffers
-    let mut buffers = Vec::with_capacity(threads);
-    for _ in 0..threads {
-        buffers.push(JobBuffer::new(buffer_capacity))
+    let mut buffers = Vec::with_capacity(PRIORITY_COUNT);
+    for _ in JobPriority::ALL {
+        let mut priority_buffers = Vec::with_capacity(threads);
+        for _ in 0..threads {
+            priority_buffers.push(JobBuffer::new(buffer_capacity));
+        }
+
+        buffers.push(priority_buffers);
     }
 
+    let shared = Arc::new(SharedState {
+        full_buffer_policy: AtomicUsize::new(FullBufferPolicy::default().index()),
+        overflow: JobPriority::ALL
+            .iter()
+            .map(|_| Mutex::new(VecDeque::new()))
+            .collect(),
+        submitted: Default::default(),
+        invoked: Default::default(),
+        stolen: AtomicUsize::new(0),
+        run_inline: AtomicUsize::new(0),
+        blocked: AtomicUsize::new(0),
+        grown: AtomicUsize::new(0),
+        tracing: AtomicBool::new(false),
+        traces: (0..threads)
+            .map(|_| Mutex::new(WorkerTrace::new(Instant::now())))
+            .collect(),
+    });
+
     let done = Arc::new(AtomicBool::new(false));
 
     // setup worker threads
     let mut handles = Vec::with_capacity(threads - 1);
     for (i, core_ids) in affinities.iter().enumerate().take(threads).skip(1) {
         let core_ids = core_ids.clone();
-        let buffers = duplicate_buffers(&buffers);
+        let buffers = buffers.clone();
+        let shared = shared.clone();
         let done_copy = done.clone();
         handles.push(thread::spawn(move || {
-            setup_worker_thread(&core_ids, buffers, i, set_affinity);
-            run_worker_thread(i, done_copy);
+            setup_worker_thread(&core_ids, buffers, shared, i, set_affinity);
+
+            let result =
+                std::panic::catch_unwind(AssertUnwindSafe(|| run_worker_thread(i, done_copy)));
+            if let Err(payload) = result {
+                handle_panic(payload.as_ref());
+                std::panic::resume_unwind(payload);
+            }
         }))
     }
 
@@ -110,8 +344,8 @@ pub fn init(
 
     // setup main worker thread (this thread)
     let core_ids = affinities[0].clone();
-    let buffers = duplicate_buffers(&buffers);
-    setup_worker_thread(&core_ids, buffers, 0, set_affinity);
+    let buffers = buffers.clone();
+    setup_worker_thread(&core_ids, buffers, shared, 0, set_affinity);
 
     JobSystemGuard { handles, done }
 }
@@ -119,47 +353,252 @@ pub fn init(
 // public methods
 pub fn submit<ReturnType: 'static, F: FnOnce() -> ReturnType + 'static>(
     job: F,
+) -> JobFuture<ReturnType> {
+    submit_with_priority(JobPriority::Normal, job)
+}
+
+pub fn submit_with_priority<ReturnType: 'static, F: FnOnce() -> ReturnType + 'static>(
+    priority: JobPriority,
+    job: F,
 ) -> JobFuture<ReturnType> {
     let (settable_future, future) = SettableJobFuture::new();
 
-    let mut job = Job::new(move || {
+    let job = Job::new(move || {
         let result = job();
         settable_future.set(result);
     });
 
-    loop {
-        let not_pushed = WORKER_THREAD.with(|worker_thread| {
-            if let Some(worker_thread) = worker_thread.borrow_mut().as_mut() {
-                let push_result = unsafe { worker_thread.local_buffer.push(job) };
-                match push_result {
-                    Ok(()) => None,
-                    Err(blocked_or_full) => Some(blocked_or_full.not_pushed),
-                }
-            } else {
-                ris_log::error!("couldn't submit job, calling thread isn't a worker thread");
-                None
-            }
-        });
+    if push_job(job, priority).is_err() {
+        ris_log::error!("couldn't submit job, calling thread isn't a worker thread");
+    }
 
-        match not_pushed {
-            Some(not_pushed) => {
-                run_pending_job(file!(), line!());
-                job = not_pushed;
-            }
-            None => break,
+    future
+}
+
+/// creates a scope, in which jobs may borrow data from the caller, like `std::thread::scope()`.
+/// all jobs spawned via the scope are done, before this function returns. the calling thread
+/// runs pending jobs while waiting.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        pending: AtomicUsize::new(0),
+        a_job_panicked: AtomicBool::new(false),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+    while scope.pending.load(Ordering::SeqCst) > 0 {
+        run_pending_job(file!(), line!());
+    }
+
+    match result {
+        Err(e) => std::panic::resume_unwind(e),
+        Ok(_) if scope.a_job_panicked.load(Ordering::SeqCst) => {
+            panic!("a scoped job has panicked")
         }
+        Ok(result) => result,
     }
+}
+
+/// submits `job` once all `dependencies` are completed. no thread is blocked while waiting on
+/// the dependencies.
+pub fn submit_after<ReturnType: 'static, F: FnOnce() -> ReturnType + 'static>(
+    dependencies: &[&dyn JobDependency],
+    job: F,
+) -> JobFuture<ReturnType> {
+    let (settable_future, future) = SettableJobFuture::new();
+
+    let job = Job::new(move || {
+        let result = job();
+        settable_future.set(result);
+    });
+
+    // one more than dependencies, such that the job is not scheduled before all continuations
+    // are registered
+    let pending = Arc::new(PendingJob {
+        count: AtomicUsize::new(dependencies.len() + 1),
+        job: Mutex::new(Some(job)),
+    });
+    let on_ready = move || {
+        if pending.count.fetch_sub(1, Ordering::SeqCst) != 1 {
+            return;
+        }
+
+        let job = ris_error::unwrap!(pending.job.lock(), "mutex is poisoned").take();
+        // runs on the thread that completes the last dependency, thus the job inherits the
+        // priority of the dependency
+        if let Some(job) = job {
+            schedule(job, current_priority());
+        }
+    };
+
+    for dependency in dependencies {
+        dependency.on_ready(Job::new(on_ready.clone()));
+    }
+
+    on_ready();
 
     future
 }
 
+/// pushes `job` onto the buffer of the calling thread. if the calling thread isn't a worker
+/// thread, `job` is invoked immediately.
+pub(crate) fn schedule(job: Job, priority: JobPriority) {
+    if let Err(mut job) = push_job(job, priority) {
+        job.invoke();
+    }
+}
+
+/// the priority of the job, which runs on the calling thread. `JobPriority::Normal` if no job
+/// runs on it. jobs that continue the work of another job, like continuations and scoped jobs,
+/// are scheduled with this priority.
+pub fn current_priority() -> JobPriority {
+    CURRENT_PRIORITY.get()
+}
+
+/// runs a single job. higher priorities are preferred, thus background jobs only run when no
+/// other job is pending.
 pub fn run_pending_job(file: &str, line: u32) {
-    match ris_jobs::job_system::pop_job(file, line) {
-        Ok(mut job) => job.invoke(),
-        Err(ris_jobs::errors::IsEmpty) => match ris_jobs::job_system::steal_job(file, line) {
-            Ok(mut job) => job.invoke(),
-            Err(ris_jobs::errors::BlockedOrEmpty) => std::thread::yield_now(),
-        },
+    run_pending_job_or_wait(WaitKind::Spin, file, line);
+}
+
+/// enables or disables tracing of the job system, which the calling thread belongs to.
+/// enabling clears the previous trace.
+pub fn set_tracing(enabled: bool) {
+    let result = with_shared(|x| {
+        if enabled && !x.tracing.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            for trace in x.traces.iter() {
+                *lock_trace(trace) = WorkerTrace::new(now);
+            }
+        }
+
+        x.tracing.store(enabled, Ordering::SeqCst);
+    });
+
+    if result.is_none() {
+        ris_log::error!("couldn't set tracing, calling thread isn't a worker thread");
+    }
+}
+
+pub fn is_tracing() -> bool {
+    with_shared(|x| x.tracing.load(Ordering::SeqCst)).unwrap_or(false)
+}
+
+/// returns a copy of the events, that were recorded since tracing was enabled. a wait, which is
+/// still going on, is included up to now.
+pub fn trace() -> JobTrace {
+    let result = with_shared(|x| {
+        let now = Instant::now();
+        let mut result = JobTrace::default();
+        for trace in x.traces.iter() {
+            let trace = lock_trace(trace);
+            result.workers.push(trace.events(now));
+            result.dropped_events += trace.dropped_events();
+        }
+
+        result
+    });
+
+    match result {
+        Some(trace) => trace,
+        None => {
+            ris_log::error!("couldn't get trace, calling thread isn't a worker thread");
+            JobTrace::default()
+        }
+    }
+}
+
+/// returns the accumulated time of each worker since tracing was enabled, indexed by
+/// `thread_index()`
+pub fn utilization() -> Vec<WorkerUtilization> {
+    let result = with_shared(|x| {
+        let now = Instant::now();
+        x.traces
+            .iter()
+            .map(|x| lock_trace(x).utilization(now))
+            .collect()
+    });
+
+    match result {
+        Some(utilization) => utilization,
+        None => {
+            ris_log::error!("couldn't get utilization, calling thread isn't a worker thread");
+            Vec::new()
+        }
+    }
+}
+
+/// sets the function, which is called with panics that nobody resumes: panics that end a worker
+/// thread and panics of jobs, whose future is dropped without taking its result. `None` removes
+/// the handler. unlike the other settings, this applies to all job systems.
+pub fn set_panic_handler(handler: Option<PanicHandler>) {
+    *PANIC_HANDLER.write().unwrap_or_else(|e| e.into_inner()) = handler;
+}
+
+/// calls the handler of `set_panic_handler()`, if there is one
+pub(crate) fn handle_panic(payload: &(dyn Any + Send)) {
+    // cloned, such that the handler may panic or set another handler without deadlocking
+    let handler = PANIC_HANDLER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(handler) = handler {
+        handler(payload);
+    }
+}
+
+/// sets the policy of the job system, which the calling thread belongs to
+pub fn set_full_buffer_policy(policy: FullBufferPolicy) {
+    let result = with_shared(|x| x.full_buffer_policy.store(policy.index(), Ordering::SeqCst));
+
+    if result.is_none() {
+        ris_log::error!("couldn't set policy, calling thread isn't a worker thread");
+    }
+}
+
+pub fn full_buffer_policy() -> FullBufferPolicy {
+    let index = with_shared(|x| x.full_buffer_policy.load(Ordering::SeqCst));
+    index.map(FullBufferPolicy::from_index).unwrap_or_default()
+}
+
+/// returns the statistics of the job system, which the calling thread belongs to
+pub fn stats() -> JobSystemStats {
+    let result = with_shared(|x| {
+        let load = |counters: &[AtomicUsize; PRIORITY_COUNT]| {
+            let mut result = [0; PRIORITY_COUNT];
+            for (value, counter) in result.iter_mut().zip(counters) {
+                *value = counter.load(Ordering::Relaxed);
+            }
+            result
+        };
+
+        let mut overflow = [0; PRIORITY_COUNT];
+        for (value, queue) in overflow.iter_mut().zip(x.overflow.iter()) {
+            *value = lock_overflow(queue).len();
+        }
+
+        JobSystemStats {
+            submitted: load(&x.submitted),
+            invoked: load(&x.invoked),
+            stolen: x.stolen.load(Ordering::Relaxed),
+            run_inline: x.run_inline.load(Ordering::Relaxed),
+            blocked: x.blocked.load(Ordering::Relaxed),
+            grown: x.grown.load(Ordering::Relaxed),
+            overflow,
+        }
+    });
+
+    match result {
+        Some(stats) => stats,
+        None => {
+            ris_log::error!("couldn't get stats, calling thread isn't a worker thread");
+            JobSystemStats::default()
+        }
     }
 }
 
@@ -220,19 +659,182 @@ pub fn thread_index() -> i32 {
 }
 
 // privat methods
-fn duplicate_buffers(buffers: &Vec<Arc<JobBuffer>>) -> Vec<Arc<JobBuffer>> {
-    let mut result = Vec::new();
+fn run_pending_job_or_wait(wait_kind: WaitKind, file: &str, line: u32) {
+    let Some((shared, index)) = current_worker() else {
+        ris_log::error!(
+            "couldn't run pending job, calling thread isn't a worker thread. caller: {}:{}",
+            file,
+            line,
+        );
+        std::thread::yield_now();
+        return;
+    };
+
+    let tracing = shared.tracing.load(Ordering::Relaxed);
+
+    for priority in JobPriority::ALL {
+        let (job, stolen_from) = match pop_job(priority, file, line) {
+            Ok(job) => (Some(job), None),
+            Err(IsEmpty) => match steal_job(priority, file, line) {
+                Ok((job, from)) => (Some(job), Some(from)),
+                Err(BlockedOrEmpty) => (pop_overflow(&shared, priority), None),
+            },
+        };
+
+        let Some(mut job) = job else {
+            continue;
+        };
+
+        shared.invoked[priority.index()].fetch_add(1, Ordering::Relaxed);
+
+        if !tracing {
+            invoke(&mut job, priority);
+            return;
+        }
+
+        // the trace must not be locked while the job runs, because it may run jobs itself
+        let start = Instant::now();
+        {
+            let mut trace = lock_trace(&shared.traces[index]);
+            trace.close_wait(start);
+            if let Some(from) = stolen_from {
+                trace.steal(from, start);
+            }
+            trace.begin_job();
+        }
 
-    for buffer in buffers {
-        result.push(buffer.clone());
+        invoke(&mut job, priority);
+
+        let end = Instant::now();
+        lock_trace(&shared.traces[index]).job(priority, stolen_from.is_some(), start, end);
+        return;
     }
 
-    result
+    if tracing {
+        lock_trace(&shared.traces[index]).wait(wait_kind, Instant::now());
+    }
+
+    std::thread::yield_now();
+}
+
+/// invokes `job`, such that `current_priority()` returns `priority` while it runs
+fn invoke(job: &mut Job, priority: JobPriority) {
+    struct RestorePriority(JobPriority);
+
+    impl Drop for RestorePriority {
+        fn drop(&mut self) {
+            CURRENT_PRIORITY.set(self.0);
+        }
+    }
+
+    let _restore = RestorePriority(CURRENT_PRIORITY.replace(priority));
+    job.invoke();
+}
+
+fn current_worker() -> Option<(Arc<SharedState>, usize)> {
+    WORKER_THREAD.with(|worker_thread| {
+        worker_thread
+            .borrow()
+            .as_ref()
+            .map(|worker_thread| (worker_thread.shared.clone(), worker_thread.index))
+    })
+}
+
+fn lock_trace(trace: &Mutex<WorkerTrace>) -> MutexGuard<'_, WorkerTrace> {
+    ris_error::unwrap!(trace.lock(), "mutex is poisoned")
+}
+
+enum PushResult {
+    Pushed,
+    Full(Job, FullBufferPolicy),
+}
+
+fn push_job(job: Job, priority: JobPriority) -> Result<(), Job> {
+    let mut job = job;
+    let mut has_blocked = false;
+
+    loop {
+        let result = WORKER_THREAD.with(|worker_thread| {
+            if let Some(worker_thread) = worker_thread.borrow().as_ref() {
+                let buffer = &worker_thread.local_buffers[priority.index()];
+                let push_result = unsafe { buffer.push(job) };
+                match push_result {
+                    Ok(()) => {
+                        if !has_blocked {
+                            worker_thread.shared.submitted[priority.index()]
+                                .fetch_add(1, Ordering::Relaxed);
+                        }
+
+                        Ok(PushResult::Pushed)
+                    }
+                    Err(blocked_or_full) => {
+                        let policy = worker_thread
+                            .shared
+                            .full_buffer_policy
+                            .load(Ordering::SeqCst);
+                        let policy = FullBufferPolicy::from_index(policy);
+                        Ok(PushResult::Full(blocked_or_full.not_pushed, policy))
+                    }
+                }
+            } else {
+                Err(job)
+            }
+        });
+
+        // the job must be invoked after the worker thread is no longer borrowed, because it may
+        // submit jobs itself
+        match result? {
+            PushResult::Pushed => return Ok(()),
+            PushResult::Full(mut not_pushed, FullBufferPolicy::RunInline) => {
+                with_shared(|x| {
+                    x.submitted[priority.index()].fetch_add(1, Ordering::Relaxed);
+                    x.run_inline.fetch_add(1, Ordering::Relaxed);
+                    x.invoked[priority.index()].fetch_add(1, Ordering::Relaxed);
+                });
+                invoke(&mut not_pushed, priority);
+                return Ok(());
+            }
+            PushResult::Full(not_pushed, FullBufferPolicy::Grow) => {
+                with_shared(|x| {
+                    x.submitted[priority.index()].fetch_add(1, Ordering::Relaxed);
+                    x.grown.fetch_add(1, Ordering::Relaxed);
+                    lock_overflow(&x.overflow[priority.index()]).push_back(not_pushed);
+                });
+                return Ok(());
+            }
+            PushResult::Full(not_pushed, FullBufferPolicy::Block) => {
+                if !has_blocked {
+                    has_blocked = true;
+                    with_shared(|x| {
+                        x.submitted[priority.index()].fetch_add(1, Ordering::Relaxed);
+                        x.blocked.fetch_add(1, Ordering::Relaxed);
+                    });
+                }
+
+                run_pending_job(file!(), line!());
+                job = not_pushed;
+            }
+        }
+    }
+}
+
+fn with_shared<T>(f: impl FnOnce(&SharedState) -> T) -> Option<T> {
+    let (shared, _) = current_worker()?;
+    Some(f(&shared))
+}
+
+fn lock_overflow(queue: &Mutex<VecDeque<Job>>) -> MutexGuard<'_, VecDeque<Job>> {
+    ris_error::unwrap!(queue.lock(), "mutex is poisoned")
+}
+
+fn pop_overflow(shared: &SharedState, priority: JobPriority) -> Option<Job> {
+    lock_overflow(&shared.overflow[priority.index()]).pop_front()
 }
 
 fn setup_worker_thread(
     core_ids: &[usize],
-    buffers: Vec<Arc<JobBuffer>>,
+    buffers: Vec<Vec<Arc<JobBuffer>>>,
+    shared: Arc<SharedState>,
     index: usize,
     set_affinity: bool,
 ) {
@@ -243,21 +845,34 @@ fn setup_worker_thread(
         };
     }
 
-    let local_buffer = buffers[index].clone();
-    let mut steal_buffers = Vec::new();
+    let mut local_buffers = Vec::with_capacity(PRIORITY_COUNT);
+    let mut steal_buffers = Vec::with_capacity(PRIORITY_COUNT);
 
-    for buffer in buffers.iter().skip(index + 1) {
-        steal_buffers.push(buffer.clone());
-    }
+    let thread_count = buffers.first().map(|x| x.len()).unwrap_or_default();
+    let steal_indices = ((index + 1)..thread_count).chain(0..index).collect();
+
+    for priority_buffers in buffers.iter() {
+        local_buffers.push(priority_buffers[index].clone());
+
+        let mut priority_steal_buffers = Vec::new();
+
+        for buffer in priority_buffers.iter().skip(index + 1) {
+            priority_steal_buffers.push(buffer.clone());
+        }
+
+        for buffer in priority_buffers.iter().take(index) {
+            priority_steal_buffers.push(buffer.clone());
+        }
 
-    for buffer in buffers.iter().take(index) {
-        steal_buffers.push(buffer.clone());
+        steal_buffers.push(priority_steal_buffers);
     }
 
     WORKER_THREAD.with(move |worker_thread| {
         *worker_thread.borrow_mut() = Some(WorkerThread {
-            local_buffer,
+            local_buffers,
             steal_buffers,
+            steal_indices,
+            shared,
             index,
         });
     });
@@ -265,28 +880,47 @@ fn setup_worker_thread(
 
 fn run_worker_thread(index: usize, done: Arc<AtomicBool>) {
     while !done.load(Ordering::SeqCst) {
-        run_pending_job(file!(), line!());
+        run_pending_job_or_wait(WaitKind::Idle, file!(), line!());
     }
 
     empty_buffer(index);
 }
 
 fn empty_buffer(index: usize) {
+    let Some((shared, _)) = current_worker() else {
+        ris_log::error!("couldn't empty buffer, calling thread isn't a worker thread");
+        return;
+    };
+
     loop {
         ris_log::trace!("emptying {}", index);
-        match pop_job(file!(), line!()) {
-            Ok(mut job) => job.invoke(),
-            Err(IsEmpty) => break,
+
+        let mut job = None;
+        for priority in JobPriority::ALL {
+            job = pop_job(priority, file!(), line!())
+                .ok()
+                .or_else(|| pop_overflow(&shared, priority))
+                .map(|job| (job, priority));
+
+            if job.is_some() {
+                break;
+            }
+        }
+
+        match job {
+            Some((mut job, priority)) => invoke(&mut job, priority),
+            None => break,
         }
     }
 }
 
-fn pop_job(file: &str, line: u32) -> Result<Job, IsEmpty> {
+fn pop_job(priority: JobPriority, file: &str, line: u32) -> Result<Job, IsEmpty> {
     let mut result = Err(IsEmpty);
 
     WORKER_THREAD.with(|worker_thread| {
-        if let Some(worker_thread) = worker_thread.borrow_mut().as_mut() {
-            result = unsafe { worker_thread.local_buffer.wait_and_pop() };
+        if let Some(worker_thread) = worker_thread.borrow().as_ref() {
+            let buffer = &worker_thread.local_buffers[priority.index()];
+            result = unsafe { buffer.wait_and_pop() };
         } else {
             ris_log::error!(
                 "couldn't pop job, calling thread isn't a worker thread. caller: {}:{}",
@@ -299,14 +933,17 @@ fn pop_job(file: &str, line: u32) -> Result<Job, IsEmpty> {
     result
 }
 
-fn steal_job(file: &str, line: u32) -> Result<Job, BlockedOrEmpty> {
+/// returns the stolen job and the index of the worker it was stolen from
+fn steal_job(priority: JobPriority, file: &str, line: u32) -> Result<(Job, usize), BlockedOrEmpty> {
     let mut result = Err(BlockedOrEmpty);
 
     WORKER_THREAD.with(|worker_thread| {
-        if let Some(worker_thread) = worker_thread.borrow_mut().as_mut() {
-            for buffer in &worker_thread.steal_buffers {
-                result = buffer.steal();
-                if result.is_ok() {
+        if let Some(worker_thread) = worker_thread.borrow().as_ref() {
+            let buffers = &worker_thread.steal_buffers[priority.index()];
+            for (buffer, from) in buffers.iter().zip(worker_thread.steal_indices.iter()) {
+                if let Ok(job) = buffer.steal() {
+                    worker_thread.shared.stolen.fetch_add(1, Ordering::Relaxed);
+                    result = Ok((job, *from));
                     break;
                 }
             }
@@ -0,0 +1,259 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::job_system::JobPriority;
+
+/// events per worker. further events are dropped, such that a forgotten trace does not eat up
+/// all memory.
+pub const MAX_EVENTS_PER_WORKER: usize = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEventKind {
+    Job {
+        priority: JobPriority,
+        stolen: bool,
+    },
+    /// a job was taken from the buffer of the worker `from`. has no duration.
+    Steal {
+        from: usize,
+    },
+    /// the worker loop found no job to run
+    Idle,
+    /// a wait or a lock found no job to run
+    Spin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEvent {
+    pub kind: TraceEventKind,
+    /// since tracing was started
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerUtilization {
+    pub busy: Duration,
+    pub idle: Duration,
+    pub spin: Duration,
+}
+
+impl WorkerUtilization {
+    /// fraction of time spent running jobs. 0 when nothing was recorded.
+    pub fn busy_fraction(&self) -> f32 {
+        let total = self.busy + self.idle + self.spin;
+        if total.is_zero() {
+            0.0
+        } else {
+            self.busy.as_secs_f32() / total.as_secs_f32()
+        }
+    }
+}
+
+impl std::ops::Sub for WorkerUtilization {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            busy: self.busy.saturating_sub(rhs.busy),
+            idle: self.idle.saturating_sub(rhs.idle),
+            spin: self.spin.saturating_sub(rhs.spin),
+        }
+    }
+}
+
+/// the recorded events, indexed by `thread_index()` of the worker
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JobTrace {
+    pub workers: Vec<Vec<TraceEvent>>,
+    pub dropped_events: usize,
+}
+
+impl JobTrace {
+    /// serializes the trace into the chrome trace event format, which can be viewed with
+    /// `chrome://tracing` or perfetto
+    pub fn to_chrome_json(&self) -> String {
+        let mut events = Vec::new();
+
+        for (tid, worker) in self.workers.iter().enumerate() {
+            events.push(format!(
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{},\"args\":{{\"name\":\"worker {}\"}}}}",
+                tid, tid,
+            ));
+
+            for event in worker.iter() {
+                let ts = micros(event.start);
+                let dur = micros(event.duration);
+
+                let json = match event.kind {
+                    TraceEventKind::Job { priority, stolen } => format!(
+                        "{{\"name\":\"job\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{},\"args\":{{\"priority\":\"{}\",\"stolen\":{}}}}}",
+                        priority, ts, dur, tid, priority, stolen,
+                    ),
+                    TraceEventKind::Steal { from } => format!(
+                        "{{\"name\":\"steal\",\"ph\":\"i\",\"s\":\"t\",\"ts\":{},\"pid\":0,\"tid\":{},\"args\":{{\"from\":{}}}}}",
+                        ts, tid, from,
+                    ),
+                    TraceEventKind::Idle => format!(
+                        "{{\"name\":\"idle\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                        ts, dur, tid,
+                    ),
+                    TraceEventKind::Spin => format!(
+                        "{{\"name\":\"spin\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                        ts, dur, tid,
+                    ),
+                };
+
+                events.push(json);
+            }
+        }
+
+        format!(
+            "{{\"traceEvents\":[\n{}\n],\"displayTimeUnit\":\"ms\"}}",
+            events.join(",\n"),
+        )
+    }
+}
+
+fn micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1_000_000.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WaitKind {
+    Idle,
+    Spin,
+}
+
+/// the trace of a single worker. only locked by its own worker, except when it is read.
+pub(crate) struct WorkerTrace {
+    start: Instant,
+    events: Vec<TraceEvent>,
+    dropped_events: usize,
+    utilization: WorkerUtilization,
+    open_wait: Option<(WaitKind, Instant)>,
+    /// one entry per job that is currently running, innermost last. holds the time spent in
+    /// nested jobs and waits, which is already counted and thus not counted as busy again.
+    open_jobs: Vec<Duration>,
+}
+
+impl WorkerTrace {
+    pub fn new(start: Instant) -> Self {
+        Self {
+            start,
+            events: Vec::new(),
+            dropped_events: 0,
+            utilization: WorkerUtilization::default(),
+            open_wait: None,
+            open_jobs: Vec::new(),
+        }
+    }
+
+    /// includes the wait, which is still going on at `now`
+    pub fn events(&self, now: Instant) -> Vec<TraceEvent> {
+        let mut result = self.events.clone();
+        if let Some((kind, start)) = self.open_wait {
+            result.push(TraceEvent {
+                kind: wait_event_kind(kind),
+                start: start.saturating_duration_since(self.start),
+                duration: now.saturating_duration_since(start),
+            });
+        }
+
+        result
+    }
+
+    pub fn dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
+    /// includes the wait, which is still going on at `now`
+    pub fn utilization(&self, now: Instant) -> WorkerUtilization {
+        let mut result = self.utilization;
+        if let Some((kind, start)) = self.open_wait {
+            let duration = now.saturating_duration_since(start);
+            match kind {
+                WaitKind::Idle => result.idle += duration,
+                WaitKind::Spin => result.spin += duration,
+            }
+        }
+
+        result
+    }
+
+    /// consecutive waits of the same kind are merged into a single event
+    pub fn wait(&mut self, kind: WaitKind, now: Instant) {
+        match self.open_wait {
+            Some((open_kind, _)) if open_kind == kind => (),
+            _ => {
+                self.close_wait(now);
+                self.open_wait = Some((kind, now));
+            }
+        }
+    }
+
+    pub fn close_wait(&mut self, now: Instant) {
+        let Some((kind, start)) = self.open_wait.take() else {
+            return;
+        };
+
+        let duration = now.saturating_duration_since(start);
+        match kind {
+            WaitKind::Idle => self.utilization.idle += duration,
+            WaitKind::Spin => self.utilization.spin += duration,
+        }
+
+        self.add_to_open_job(duration);
+        self.push(wait_event_kind(kind), start, duration);
+    }
+
+    pub fn steal(&mut self, from: usize, now: Instant) {
+        self.push(TraceEventKind::Steal { from }, now, Duration::ZERO);
+    }
+
+    pub fn begin_job(&mut self) {
+        self.open_jobs.push(Duration::ZERO);
+    }
+
+    /// ends the job of the last `begin_job()`. only the time that isn't spent in nested jobs and
+    /// waits counts as busy, such that jobs, which run while a job waits, are not counted twice.
+    pub fn job(&mut self, priority: JobPriority, stolen: bool, start: Instant, end: Instant) {
+        // a wait that is still open began inside of this job, thus it ends with it
+        self.close_wait(end);
+
+        let duration = end.saturating_duration_since(start);
+
+        // none, if tracing was enabled while the job was running
+        let nested = self.open_jobs.pop().unwrap_or_default();
+        self.utilization.busy += duration.saturating_sub(nested);
+        self.add_to_open_job(duration);
+
+        self.push(TraceEventKind::Job { priority, stolen }, start, duration);
+    }
+
+    fn add_to_open_job(&mut self, duration: Duration) {
+        if let Some(nested) = self.open_jobs.last_mut() {
+            *nested += duration;
+        }
+    }
+
+    fn push(&mut self, kind: TraceEventKind, start: Instant, duration: Duration) {
+        if self.events.len() >= MAX_EVENTS_PER_WORKER {
+            self.dropped_events += 1;
+            return;
+        }
+
+        self.events.push(TraceEvent {
+            kind,
+            start: start.saturating_duration_since(self.start),
+            duration,
+        });
+    }
+}
+
+fn wait_event_kind(kind: WaitKind) -> TraceEventKind {
+    match kind {
+        WaitKind::Idle => TraceEventKind::Idle,
+        WaitKind::Spin => TraceEventKind::Spin,
+    }
+}
@@ -2,6 +2,9 @@ pub mod affinity;
 pub mod errors;
 pub mod job;
 pub mod job_buffer;
+pub mod job_executor;
 //pub mod job_cell;
 pub mod job_future;
 pub mod job_system;
+pub mod job_trace;
+pub mod parallel;
@@ -0,0 +1,151 @@
+use std::ops::Range;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+use crate::job::Job;
+use crate::job_system;
+
+/// invokes `f` for every index in `range`. the range is split into chunks of `grain` indices,
+/// which may run on different threads. blocks until all indices are processed, which is why `f`
+/// may borrow data from the caller.
+pub fn parallel_for<F: Fn(usize) + Sync>(range: Range<usize>, grain: usize, f: F) {
+    let grain = usize::max(grain, 1);
+    let chunk_count = range.len().div_ceil(grain);
+
+    run_chunks(chunk_count, &|chunk| {
+        for i in chunk_range(&range, grain, chunk) {
+            f(i);
+        }
+    });
+}
+
+/// splits `slice` into chunks of `chunk_size` elements and invokes `f` with the index of the
+/// chunk and the chunk itself. the last chunk may be shorter. blocks until all chunks are
+/// processed.
+pub fn par_chunks_mut<T: Send, F: Fn(usize, &mut [T]) + Sync>(
+    slice: &mut [T],
+    chunk_size: usize,
+    f: F,
+) {
+    let chunk_size = usize::max(chunk_size, 1);
+    let len = slice.len();
+    let chunk_count = len.div_ceil(chunk_size);
+    let ptr = SlicePtr(slice.as_mut_ptr());
+
+    run_chunks(chunk_count, &|chunk| {
+        let range = chunk_range(&(0..len), chunk_size, chunk);
+
+        // the chunks are disjoint and the slice is borrowed mutably until all chunks are done
+        let chunk_slice =
+            unsafe { std::slice::from_raw_parts_mut(ptr.get().add(range.start), range.len()) };
+        f(chunk, chunk_slice);
+    });
+}
+
+/// maps every index in `range` and reduces the results. `identity` must not change the result
+/// when reduced with another value, as it is the starting value of every chunk. chunks are
+/// reduced in order, so `reduce` does not need to be commutative.
+pub fn parallel_reduce<T, Map, Reduce>(
+    range: Range<usize>,
+    grain: usize,
+    identity: T,
+    map: Map,
+    reduce: Reduce,
+) -> T
+where
+    T: Clone + Send + Sync,
+    Map: Fn(usize) -> T + Sync,
+    Reduce: Fn(T, T) -> T + Sync,
+{
+    let grain = usize::max(grain, 1);
+    let chunk_count = range.len().div_ceil(grain);
+
+    let mut results = (0..chunk_count).map(|_| None).collect::<Vec<_>>();
+    par_chunks_mut(&mut results, 1, |chunk, result| {
+        let mut value = identity.clone();
+        for i in chunk_range(&range, grain, chunk) {
+            value = reduce(value, map(i));
+        }
+
+        result[0] = Some(value);
+    });
+
+    results.into_iter().flatten().fold(identity, reduce)
+}
+
+struct SlicePtr<T>(*mut T);
+
+impl<T> SlicePtr<T> {
+    // closures must capture the whole struct, not just the raw pointer, which is not `Sync`
+    fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+unsafe impl<T: Send> Sync for SlicePtr<T> {}
+
+fn chunk_range(range: &Range<usize>, grain: usize, chunk: usize) -> Range<usize> {
+    let start = range.start + chunk * grain;
+    let end = usize::min(start + grain, range.end);
+    start..end
+}
+
+/// invokes `f` for each chunk and blocks until all of them are done. the first chunk runs on
+/// the calling thread. if a chunk panics, the other chunks still complete and the panic of the
+/// first chunk that panicked is resumed on the calling thread.
+fn run_chunks(chunk_count: usize, f: &(dyn Fn(usize) + Sync)) {
+    if chunk_count == 0 {
+        return;
+    }
+
+    let remaining = AtomicUsize::new(chunk_count);
+    let panic = Mutex::new(None);
+    let run_chunk = |chunk: usize| {
+        if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(|| f(chunk))) {
+            let mut panic = panic.lock().unwrap_or_else(|e| e.into_inner());
+            panic.get_or_insert(payload);
+        }
+    };
+
+    let guard = WaitGuard(&remaining);
+
+    let priority = job_system::current_priority();
+    for chunk in 1..chunk_count {
+        let remaining = &remaining;
+        let run_chunk = &run_chunk;
+
+        // sound, because `WaitGuard` does not let this function return before all jobs are
+        // done, not even when unwinding
+        let job = unsafe {
+            Job::new_unchecked(move || {
+                run_chunk(chunk);
+                remaining.fetch_sub(1, Ordering::SeqCst);
+            })
+        };
+
+        job_system::schedule(job, priority);
+    }
+
+    run_chunk(0);
+    drop(guard);
+
+    let panic = panic.into_inner().unwrap_or_else(|e| e.into_inner());
+    if let Some(payload) = panic {
+        std::panic::resume_unwind(payload);
+    }
+}
+
+/// completes the chunk of the calling thread and waits for all others
+struct WaitGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+
+        while self.0.load(Ordering::SeqCst) > 0 {
+            job_system::run_pending_job(file!(), line!());
+        }
+    }
+}
@@ -3,6 +3,8 @@ use chrono::Local;
 
 use crate::color_string::Color;
 use crate::color_string::ColorString;
+use crate::log_field::LogField;
+use crate::log_field::LogValue;
 use crate::log_level::LogLevel;
 
 #[derive(Clone)]
@@ -13,6 +15,7 @@ pub struct ConstructedLogMessage {
     pub timestamp: DateTime<Local>,
     pub priority: LogLevel,
     pub message: String,
+    pub fields: Vec<LogField>,
 }
 
 impl ConstructedLogMessage {
@@ -25,7 +28,17 @@ impl ConstructedLogMessage {
 
         let colon = ColorString(":", Color::White).fmt(ansi_support);
 
-        let message = ColorString(&self.message, Color::BrightWhite).fmt(ansi_support);
+        let mut message = ColorString(&self.message, Color::BrightWhite).fmt(ansi_support);
+        if !self.fields.is_empty() {
+            let fields = self
+                .fields
+                .iter()
+                .map(|x| x.fmt())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let fields = ColorString(&fields, Color::White).fmt(ansi_support);
+            message = format!("{} {}", message, fields);
+        }
 
         let foot = ColorString(
             &format!("in {} at {}:{}", self.package, self.file, self.line),
@@ -38,4 +51,58 @@ impl ConstructedLogMessage {
             timestamp, priority, colon, message, foot,
         )
     }
+
+    /// formats the message as a single line json object
+    pub fn to_json(&self) -> String {
+        let mut fields = String::from("{");
+        for (i, field) in self.fields.iter().enumerate() {
+            if i != 0 {
+                fields.push(',');
+            }
+
+            let value = match &field.value {
+                LogValue::Bool(value) => value.to_string(),
+                LogValue::Int(value) => value.to_string(),
+                LogValue::UInt(value) => value.to_string(),
+                LogValue::Float(value) if value.is_finite() => value.to_string(),
+                LogValue::Float(_) => String::from("null"),
+                LogValue::String(value) => escape_json(value),
+            };
+
+            fields.push_str(&format!("{}:{}", escape_json(field.key), value));
+        }
+        fields.push('}');
+
+        format!(
+            "{{\"timestamp\":{},\"level\":{},\"package\":{},\"file\":{},\"line\":{},\"message\":{},\"fields\":{}}}",
+            escape_json(&self.timestamp.to_rfc3339()),
+            escape_json(&format!("{:?}", self.priority)),
+            escape_json(&self.package),
+            escape_json(&self.file),
+            self.line,
+            escape_json(&self.message),
+            fields,
+        )
+    }
+}
+
+/// quotes `value` as a json string
+pub fn escape_json(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
 }
@@ -1,5 +1,8 @@
 pub mod color_string;
 pub mod constructed_log_message;
 pub mod log;
+pub mod log_field;
+pub mod log_filter;
 pub mod log_level;
 pub mod log_message;
+pub mod log_queue;
@@ -1,17 +1,26 @@
-use std::sync::mpsc::channel;
-use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::sync::Mutex;
 use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
 
 use chrono::DateTime;
 use chrono::Local;
 
+use crate::constructed_log_message::ConstructedLogMessage;
+use crate::log_field::LogField;
+use crate::log_filter::LogFilter;
 use crate::log_level::LogLevel;
 use crate::log_message::LogMessage;
+use crate::log_queue::LogQueue;
+use crate::log_queue::QueueOptions;
 
 pub trait IAppender {
     fn print(&mut self, message: &LogMessage);
+
+    /// called after a batch of messages was printed. appenders that buffer should write their
+    /// buffer here.
+    fn flush(&mut self) {}
 }
 
 pub static LOG: Mutex<Option<Logger>> = Mutex::new(None);
@@ -30,14 +39,14 @@ impl Drop for LogGuard {
 }
 
 pub struct Logger {
-    log_level: LogLevel,
-    sender: Option<Sender<LogMessage>>,
+    filter: LogFilter,
+    queue: Arc<LogQueue>,
     thread_handle: Option<JoinHandle<()>>,
 }
 
 impl Drop for Logger {
     fn drop(&mut self) {
-        self.sender.take();
+        self.queue.close();
 
         if let Some(thread_handle) = self.thread_handle.take() {
             if thread_handle.join().is_err() {
@@ -47,20 +56,29 @@ impl Drop for Logger {
     }
 }
 
-pub fn init(log_level: LogLevel, appenders: Vec<Box<dyn IAppender + Send>>) -> LogGuard {
-    if matches!(log_level, LogLevel::None) || appenders.is_empty() {
+pub fn init(filter: impl Into<LogFilter>, appenders: Vec<Box<dyn IAppender + Send>>) -> LogGuard {
+    init_with_queue(filter, appenders, QueueOptions::default())
+}
+
+pub fn init_with_queue(
+    filter: impl Into<LogFilter>,
+    appenders: Vec<Box<dyn IAppender + Send>>,
+    queue_options: QueueOptions,
+) -> LogGuard {
+    let filter = filter.into();
+    if matches!(filter.min_level(), LogLevel::None) || appenders.is_empty() {
         return LogGuard;
     }
 
-    let (sender, receiver) = channel();
-    let sender = Some(sender);
+    let queue = Arc::new(LogQueue::new(queue_options));
+    let thread_queue = queue.clone();
     let thread_handle = Some(std::thread::spawn(|| {
-        log_thread(receiver, appenders);
+        log_thread(thread_queue, appenders);
     }));
 
     let logger = Logger {
-        log_level,
-        sender,
+        filter,
+        queue,
         thread_handle,
     };
 
@@ -76,57 +94,188 @@ pub fn init(log_level: LogLevel, appenders: Vec<Box<dyn IAppender + Send>>) -> L
     LogGuard
 }
 
-fn log_thread(receiver: Receiver<LogMessage>, mut appenders: Vec<Box<dyn IAppender + Send>>) {
-    for log_message in receiver.iter() {
+fn log_thread(queue: Arc<LogQueue>, mut appenders: Vec<Box<dyn IAppender + Send>>) {
+    queue.set_consumer();
+
+    let report_interval = queue.options().report_interval;
+    let mut last_report = Instant::now();
+    let mut reported_dropped = 0;
+
+    while let Some(batch) = queue.pop_all(report_interval) {
+        for log_message in batch.iter() {
+            for appender in appenders.iter_mut() {
+                appender.print(log_message);
+            }
+        }
+
+        if last_report.elapsed() >= report_interval {
+            last_report = Instant::now();
+            report_dropped(&queue, &mut reported_dropped, &mut appenders);
+        }
+
         for appender in appenders.iter_mut() {
-            appender.print(&log_message);
+            appender.flush();
         }
+
+        queue.finish(batch.len());
     }
 
+    report_dropped(&queue, &mut reported_dropped, &mut appenders);
+
     let final_log_message = LogMessage::Plain(String::from("log thread ended"));
 
     for appender in appenders.iter_mut() {
         appender.print(&final_log_message);
+        appender.flush();
     }
 }
 
-pub fn log_level() -> LogLevel {
+fn report_dropped(
+    queue: &LogQueue,
+    reported_dropped: &mut usize,
+    appenders: &mut [Box<dyn IAppender + Send>],
+) {
+    let dropped = queue.dropped();
+    if dropped == *reported_dropped {
+        return;
+    }
+
+    let constructed_log = ConstructedLogMessage {
+        package: String::from(env!("CARGO_PKG_NAME")),
+        file: String::from(file!()),
+        line: line!(),
+        timestamp: get_timestamp(),
+        priority: LogLevel::Warning,
+        message: String::from("log queue was full, messages were dropped"),
+        fields: vec![
+            LogField::new("dropped", dropped - *reported_dropped),
+            LogField::new("total", dropped),
+        ],
+    };
+    *reported_dropped = dropped;
+
+    let message = LogMessage::Constructed(constructed_log);
+    for appender in appenders.iter_mut() {
+        appender.print(&message);
+    }
+}
+
+pub fn filter() -> LogFilter {
     match LOG.lock() {
-        Err(e) => eprintln!("error while getting log_level: {}", e),
+        Err(e) => eprintln!("error while getting filter: {}", e),
         Ok(log) => {
             if let Some(logger) = &*log {
-                return logger.log_level;
+                return logger.filter.clone();
             }
         }
     }
 
-    LogLevel::None
+    LogFilter::new(LogLevel::None)
+}
+
+/// replaces the filter of the running logger. does nothing if the log is not initialized.
+pub fn set_filter(filter: LogFilter) {
+    match LOG.lock() {
+        Err(e) => eprintln!("error while setting filter: {}", e),
+        Ok(mut log) => {
+            if let Some(logger) = &mut *log {
+                logger.filter = filter;
+            }
+        }
+    }
 }
 
 pub fn get_timestamp() -> DateTime<Local> {
     Local::now()
 }
 
-pub fn can_log(priority: LogLevel) -> bool {
+pub fn can_log(priority: LogLevel, package: &str, file: &str) -> bool {
     if matches!(priority, LogLevel::None) {
         return false;
     }
 
-    let priority = priority as u8;
-    let log_level = log_level() as u8;
-    priority >= log_level
+    match LOG.lock() {
+        Err(e) => eprintln!("error while checking filter: {}", e),
+        Ok(log) => {
+            if let Some(logger) = &*log {
+                return logger.filter.is_enabled(priority, package, file);
+            }
+        }
+    }
+
+    false
 }
 
 pub fn forward_to_appenders(log_message: LogMessage) {
+    // the queue is pushed to outside of the lock, as pushing may block
+    let queue = match LOG.lock() {
+        Err(e) => {
+            eprintln!("error while forwarding to appenders: {}", e);
+            return;
+        }
+        Ok(log) => match &*log {
+            Some(logger) => logger.queue.clone(),
+            None => return,
+        },
+    };
+
+    queue.push(log_message);
+}
+
+/// the number of messages that were dropped, because the queue was full
+pub fn dropped_count() -> usize {
     match LOG.lock() {
-        Err(e) => eprintln!("error while forwarding to appenders: {}", e),
-        Ok(mut log) => {
-            if let Some(logger) = &mut *log {
-                if let Some(sender) = &mut logger.sender {
-                    let _ = sender.send(log_message);
+        Err(e) => eprintln!("error while getting dropped count: {}", e),
+        Ok(log) => {
+            if let Some(logger) = &*log {
+                return logger.queue.dropped();
+            }
+        }
+    }
+
+    0
+}
+
+/// blocks until the appenders have printed all messages forwarded so far, or until `timeout`
+/// has passed. returns whether all messages were printed.
+///
+/// when called from the log thread, for example by a panicking appender, this returns `false`
+/// immediately, as waiting on itself would never finish.
+pub fn flush(timeout: Duration) -> bool {
+    let queue = match LOG.lock() {
+        Err(e) => {
+            eprintln!("error while flushing log: {}", e);
+            return false;
+        }
+        Ok(log) => match &*log {
+            Some(logger) => {
+                let is_log_thread = logger
+                    .thread_handle
+                    .as_ref()
+                    .map(|x| x.thread().id() == std::thread::current().id())
+                    .unwrap_or(false);
+                if is_log_thread {
+                    return false;
                 }
+
+                logger.queue.clone()
             }
+            None => return true,
+        },
+    };
+
+    let target = queue.accepted();
+    let start = Instant::now();
+    loop {
+        if queue.finished() >= target {
+            return true;
         }
+
+        if start.elapsed() >= timeout {
+            return false;
+        }
+
+        std::thread::sleep(Duration::from_millis(1));
     }
 }
 
@@ -172,16 +321,44 @@ macro_rules! fatal {
     };
 }
 
+/// logs a message with `priority`. the message is formatted like `format!()`.
+///
+/// structured fields may precede the message, separated by `;`. a field is written as
+/// `key = value`, where the value must convert into a `LogValue`. `key = ?value` stores its
+/// `Debug` and `key = %value` its `Display` representation.
+///
+/// ```ignore
+/// ris_log::info!(asset_id = ?id, ms = elapsed; "loaded asset");
+/// ```
 #[macro_export]
 macro_rules! log {
-    ($priority:expr, $($arg:tt)*) => {
-        if (ris_log::log::can_log($priority)) {
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = ?$value:expr, $($rest:tt)+) => {
+        ris_log::log!(@fields $priority, [$($field,)* ris_log::log_field::LogField::debug(stringify!($key), &$value),] $($rest)+)
+    };
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = %$value:expr, $($rest:tt)+) => {
+        ris_log::log!(@fields $priority, [$($field,)* ris_log::log_field::LogField::display(stringify!($key), &$value),] $($rest)+)
+    };
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = $value:expr, $($rest:tt)+) => {
+        ris_log::log!(@fields $priority, [$($field,)* ris_log::log_field::LogField::new(stringify!($key), $value),] $($rest)+)
+    };
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = ?$value:expr; $($arg:tt)+) => {
+        ris_log::log!(@construct $priority, vec![$($field,)* ris_log::log_field::LogField::debug(stringify!($key), &$value)], $($arg)+)
+    };
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = %$value:expr; $($arg:tt)+) => {
+        ris_log::log!(@construct $priority, vec![$($field,)* ris_log::log_field::LogField::display(stringify!($key), &$value)], $($arg)+)
+    };
+    (@fields $priority:expr, [$($field:expr,)*] $key:ident = $value:expr; $($arg:tt)+) => {
+        ris_log::log!(@construct $priority, vec![$($field,)* ris_log::log_field::LogField::new(stringify!($key), $value)], $($arg)+)
+    };
+    (@construct $priority:expr, $fields:expr, $($arg:tt)*) => {
+        if (ris_log::log::can_log($priority, env!("CARGO_PKG_NAME"), file!())) {
             let package = String::from(env!("CARGO_PKG_NAME"));
             let file = String::from(file!());
             let line = line!();
             let timestamp = ris_log::log::get_timestamp();
             let priority = $priority;
             let message = format!($($arg)*);
+            let fields = $fields;
 
             let constructed_log = ris_log::constructed_log_message::ConstructedLogMessage {
                 package,
@@ -190,6 +367,7 @@ macro_rules! log {
                 timestamp,
                 priority,
                 message,
+                fields,
             };
 
             let message = ris_log::log_message::LogMessage::Constructed(constructed_log);
@@ -197,4 +375,10 @@ macro_rules! log {
             ris_log::log::forward_to_appenders(message);
         }
     };
+    ($priority:expr, $key:ident = $($rest:tt)+) => {
+        ris_log::log!(@fields $priority, [] $key = $($rest)+)
+    };
+    ($priority:expr, $($arg:tt)*) => {
+        ris_log::log!(@construct $priority, Vec::new(), $($arg)*)
+    };
 }
@@ -0,0 +1,113 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+
+/// a structured key-value pair attached to a log message
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogField {
+    pub key: &'static str,
+    pub value: LogValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+}
+
+impl LogField {
+    pub fn new(key: &'static str, value: impl Into<LogValue>) -> Self {
+        Self {
+            key,
+            value: value.into(),
+        }
+    }
+
+    /// stores the value formatted with `Debug`. used by the `key = ?value` syntax of the log
+    /// macros.
+    pub fn debug(key: &'static str, value: &impl Debug) -> Self {
+        Self::new(key, format!("{:?}", value))
+    }
+
+    /// stores the value formatted with `Display`. used by the `key = %value` syntax of the log
+    /// macros.
+    pub fn display(key: &'static str, value: &impl Display) -> Self {
+        Self::new(key, value.to_string())
+    }
+
+    /// formats the field as `key=value`. strings are quoted, if they are empty or contain
+    /// whitespace, `=` or `"`.
+    pub fn fmt(&self) -> String {
+        let value = match &self.value {
+            LogValue::String(value) => {
+                let needs_quotes = value.is_empty()
+                    || value
+                        .chars()
+                        .any(|x| x.is_whitespace() || x == '=' || x == '"');
+
+                if needs_quotes {
+                    format!("{:?}", value)
+                } else {
+                    value.clone()
+                }
+            }
+            value => value.to_string(),
+        };
+
+        format!("{}={}", self.key, value)
+    }
+}
+
+impl Display for LogValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::UInt(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $as:ty, $($type:ty),*) => {
+        $(
+            impl From<$type> for LogValue {
+                fn from(value: $type) -> Self {
+                    Self::$variant(value as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Int, i64, i8, i16, i32, i64, isize);
+impl_from!(UInt, u64, u8, u16, u32, u64, usize);
+impl_from!(Float, f64, f32, f64);
+
+impl From<bool> for LogValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<String> for LogValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for LogValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<&String> for LogValue {
+    fn from(value: &String) -> Self {
+        Self::String(value.clone())
+    }
+}
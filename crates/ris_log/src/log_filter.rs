@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use crate::log_level::LogLevel;
+
+/// decides which messages are logged, depending on where they come from.
+///
+/// a filter is written as comma separated directives, for example
+/// `info,ris_asset=debug,ris_jobs=warning`. a directive `target=level` applies to messages of
+/// the package `target` or of files whose path starts with `target`, like
+/// `crates/ris_asset/src/asset_loader.rs`. a directive without a target sets the level of all
+/// remaining messages, which is `trace` if omitted. if multiple targets match, the longest one
+/// wins.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogFilter {
+    pub default: LogLevel,
+    pub targets: Vec<LogTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogTarget {
+    pub target: String,
+    pub level: LogLevel,
+}
+
+#[derive(Debug)]
+pub struct ParseLogFilterError(String);
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self::new(LogLevel::Trace)
+    }
+}
+
+impl From<LogLevel> for LogFilter {
+    fn from(value: LogLevel) -> Self {
+        Self::new(value)
+    }
+}
+
+impl LogFilter {
+    pub fn new(default: LogLevel) -> Self {
+        Self {
+            default,
+            targets: Vec::new(),
+        }
+    }
+
+    /// the level that applies to messages from `package` and `file`
+    pub fn level(&self, package: &str, file: &str) -> LogLevel {
+        // called for every message while the log is locked, thus this must not allocate
+        if self.targets.is_empty() {
+            return self.default;
+        }
+
+        self.targets
+            .iter()
+            .filter(|x| x.target == package || path_starts_with(file, &x.target))
+            .max_by_key(|x| x.target.len())
+            .map(|x| x.level)
+            .unwrap_or(self.default)
+    }
+
+    pub fn is_enabled(&self, priority: LogLevel, package: &str, file: &str) -> bool {
+        if matches!(priority, LogLevel::None) {
+            return false;
+        }
+
+        priority >= self.level(package, file)
+    }
+
+    /// the lowest level of all directives. messages below it are never logged.
+    pub fn min_level(&self) -> LogLevel {
+        self.targets
+            .iter()
+            .map(|x| x.level)
+            .fold(self.default, std::cmp::min)
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = ParseLogFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+
+        for directive in s.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            match directive.rsplit_once('=') {
+                Some((target, level)) => {
+                    let target = target.trim().replace('\\', "/");
+                    if target.is_empty() {
+                        return Err(ParseLogFilterError(format!(
+                            "directive \"{}\" has no target",
+                            directive,
+                        )));
+                    }
+
+                    // filters are stored in the settings file, which reserves these characters
+                    if target.contains([':', '#']) {
+                        return Err(ParseLogFilterError(format!(
+                            "target \"{}\" may not contain ':' or '#'",
+                            target,
+                        )));
+                    }
+
+                    let level = parse_level(level)?;
+                    result.targets.retain(|x| x.target != target);
+                    result.targets.push(LogTarget { target, level });
+                }
+                None => result.default = parse_level(directive)?,
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", level_to_str(self.default))?;
+
+        for target in self.targets.iter() {
+            write!(f, ",{}={}", target.target, level_to_str(target.level))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ParseLogFilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to parse log filter: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseLogFilterError {}
+
+fn parse_level(value: &str) -> Result<LogLevel, ParseLogFilterError> {
+    match value.trim().to_lowercase().as_str() {
+        "trace" => Ok(LogLevel::Trace),
+        "debug" => Ok(LogLevel::Debug),
+        "info" => Ok(LogLevel::Info),
+        "warning" | "warn" => Ok(LogLevel::Warning),
+        "error" => Ok(LogLevel::Error),
+        "fatal" => Ok(LogLevel::Fatal),
+        "none" | "off" => Ok(LogLevel::None),
+        _ => Err(ParseLogFilterError(format!("unknown level \"{}\"", value))),
+    }
+}
+
+fn level_to_str(value: LogLevel) -> &'static str {
+    match value {
+        LogLevel::Trace => "trace",
+        LogLevel::Debug => "debug",
+        LogLevel::Info => "info",
+        LogLevel::Warning => "warning",
+        LogLevel::Error => "error",
+        LogLevel::Fatal => "fatal",
+        LogLevel::None => "none",
+    }
+}
+
+/// like `str::starts_with()`, but '\\' and '/' are treated as the same character
+fn path_starts_with(path: &str, prefix: &str) -> bool {
+    let normalize = |x: u8| if x == b'\\' { b'/' } else { x };
+
+    path.len() >= prefix.len()
+        && path
+            .bytes()
+            .zip(prefix.bytes())
+            .all(|(left, right)| normalize(left) == normalize(right))
+}
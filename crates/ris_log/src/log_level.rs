@@ -1,7 +1,7 @@
 use crate::color_string::Color;
 use crate::color_string::ColorString;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LogLevel {
     Trace = 0,
     Debug = 1,
@@ -1,3 +1,4 @@
+use crate::constructed_log_message;
 use crate::constructed_log_message::ConstructedLogMessage;
 
 #[derive(Clone)]
@@ -13,4 +14,16 @@ impl LogMessage {
             Self::Plain(message) => message.to_owned(),
         }
     }
+
+    /// formats the message as a single line json object. plain messages only contain the
+    /// message.
+    pub fn to_json(&self) -> String {
+        match self {
+            Self::Constructed(message) => message.to_json(),
+            Self::Plain(message) => format!(
+                "{{\"message\":{}}}",
+                constructed_log_message::escape_json(message),
+            ),
+        }
+    }
 }
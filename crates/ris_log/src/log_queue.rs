@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::thread::ThreadId;
+use std::time::Duration;
+
+use crate::log_level::LogLevel;
+use crate::log_message::LogMessage;
+
+pub const DEFAULT_CAPACITY: usize = 4096;
+pub const DEFAULT_REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// what happens to a message, when the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// the logging thread waits until the log thread made room
+    Block,
+    /// the oldest queued message is dropped
+    DropOldest,
+    /// messages below the level are dropped, the oldest queued ones first. messages at or above
+    /// the level block, if no message can be dropped. plain messages are never dropped.
+    DropBelow(LogLevel),
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueOptions {
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+    /// how often the number of dropped messages is logged
+    pub report_interval: Duration,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            policy: OverflowPolicy::Block,
+            report_interval: DEFAULT_REPORT_INTERVAL,
+        }
+    }
+}
+
+/// a bounded queue between the threads that log and the log thread
+pub struct LogQueue {
+    options: QueueOptions,
+    state: Mutex<QueueState>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+#[derive(Default)]
+struct QueueState {
+    messages: VecDeque<LogMessage>,
+    is_closed: bool,
+    consumer: Option<ThreadId>,
+    /// messages that entered the queue
+    accepted: usize,
+    /// accepted messages that were either popped and finished, or dropped
+    finished: usize,
+    dropped: usize,
+}
+
+impl LogQueue {
+    pub fn new(options: QueueOptions) -> Self {
+        Self {
+            options,
+            state: Mutex::new(QueueState::default()),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    pub fn options(&self) -> &QueueOptions {
+        &self.options
+    }
+
+    /// enqueues `message`, applying the overflow policy if the queue is full. messages pushed
+    /// after the queue was closed are dropped.
+    pub fn push(&self, message: LogMessage) {
+        let capacity = self.options.capacity.max(1);
+        let mut state = self.lock();
+
+        loop {
+            if state.is_closed {
+                state.dropped += 1;
+                return;
+            }
+
+            if state.messages.len() < capacity {
+                break;
+            }
+
+            let drop_index = match self.options.policy {
+                OverflowPolicy::Block => None,
+                OverflowPolicy::DropOldest => Some(0),
+                OverflowPolicy::DropBelow(level) => {
+                    if is_below(&message, level) {
+                        state.dropped += 1;
+                        return;
+                    }
+
+                    state.messages.iter().position(|x| is_below(x, level))
+                }
+            };
+
+            if let Some(drop_index) = drop_index {
+                state.messages.remove(drop_index);
+                state.dropped += 1;
+                state.finished += 1;
+                break;
+            }
+
+            // the log thread cannot wait for itself
+            if state.consumer == Some(std::thread::current().id()) {
+                state.dropped += 1;
+                return;
+            }
+
+            state = match self.not_full.wait(state) {
+                Ok(state) => state,
+                Err(e) => e.into_inner(),
+            };
+        }
+
+        state.messages.push_back(message);
+        state.accepted += 1;
+        self.not_empty.notify_one();
+    }
+
+    /// takes all queued messages. waits at most `timeout` for a message to arrive, and returns an
+    /// empty batch if none did. returns `None` once the queue is closed and empty.
+    ///
+    /// popped messages must be passed to `finish()` after they were printed.
+    pub fn pop_all(&self, timeout: Duration) -> Option<Vec<LogMessage>> {
+        let mut state = self.lock();
+
+        if state.messages.is_empty() && !state.is_closed {
+            state = match self.not_empty.wait_timeout(state, timeout) {
+                Ok((state, _)) => state,
+                Err(e) => e.into_inner().0,
+            };
+        }
+
+        if state.messages.is_empty() {
+            return if state.is_closed {
+                None
+            } else {
+                Some(Vec::new())
+            };
+        }
+
+        let batch = state.messages.drain(..).collect();
+        self.not_full.notify_all();
+        Some(batch)
+    }
+
+    /// marks `count` popped messages as printed
+    pub fn finish(&self, count: usize) {
+        self.lock().finished += count;
+    }
+
+    /// wakes the log thread and all waiting threads. the log thread pops the remaining messages,
+    /// further messages are dropped.
+    pub fn close(&self) {
+        self.lock().is_closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// registers the calling thread as the one that pops messages. it never blocks on a full
+    /// queue.
+    pub fn set_consumer(&self) {
+        self.lock().consumer = Some(std::thread::current().id());
+    }
+
+    pub fn accepted(&self) -> usize {
+        self.lock().accepted
+    }
+
+    pub fn finished(&self) -> usize {
+        self.lock().finished
+    }
+
+    /// the total number of dropped messages
+    pub fn dropped(&self) -> usize {
+        self.lock().dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        }
+    }
+}
+
+fn is_below(message: &LogMessage, level: LogLevel) -> bool {
+    match message {
+        LogMessage::Constructed(message) => message.priority < level,
+        LogMessage::Plain(_) => false,
+    }
+}
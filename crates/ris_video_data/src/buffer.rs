@@ -30,6 +30,9 @@ pub struct CopyToImageInfo<'a> {
     pub image: vk::Image,
     pub width: u32,
     pub height: u32,
+    /// offset of the pixels in this buffer
+    pub buffer_offset: vk::DeviceSize,
+    pub mip_level: u32,
     pub sync: TransientCommandSync,
 }
 
@@ -151,18 +154,20 @@ impl Buffer {
             image,
             width,
             height,
+            buffer_offset,
+            mip_level,
             sync,
         } = info;
 
         let transient_command = TransientCommand::begin(device, queue, transient_command_pool)?;
 
         let regions = [vk::BufferImageCopy {
-            buffer_offset: 0,
+            buffer_offset,
             buffer_row_length: 0,
             buffer_image_height: 0,
             image_subresource: vk::ImageSubresourceLayers {
                 aspect_mask: vk::ImageAspectFlags::COLOR,
-                mip_level: 0,
+                mip_level,
                 base_array_layer: 0,
                 layer_count: 1,
             },
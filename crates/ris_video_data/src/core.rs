@@ -25,6 +25,7 @@ pub struct VulkanCore {
     pub command_pool: vk::CommandPool,
     pub transient_command_pool: vk::CommandPool,
     pub swapchain: Swapchain,
+    pub vsync: bool,
 }
 
 impl VulkanCore {
@@ -57,7 +58,7 @@ impl VulkanCore {
     /// # Safety
     ///
     /// `free()` must be called, or you are leaking memory.
-    pub unsafe fn alloc(application_name: &str, window: &Window) -> RisResult<Self> {
+    pub unsafe fn alloc(application_name: &str, window: &Window, vsync: bool) -> RisResult<Self> {
         let entry = unsafe { ash::Entry::load() }?;
 
         // instance extensions
@@ -220,6 +221,7 @@ impl VulkanCore {
                 surface_loader: &surface_loader,
                 surface: &surface,
                 window_drawable_size: window.vulkan_drawable_size(),
+                vsync,
                 frames_in_flight: None,
             })
         }?;
@@ -238,9 +240,20 @@ impl VulkanCore {
             command_pool,
             transient_command_pool,
             swapchain,
+            vsync,
         })
     }
 
+    /// recreates the swapchain, if `vsync` differs
+    pub fn set_vsync(&mut self, vsync: bool, window_drawable_size: (u32, u32)) -> RisResult<()> {
+        if self.vsync == vsync {
+            return Ok(());
+        }
+
+        self.vsync = vsync;
+        self.recreate_swapchain(window_drawable_size)
+    }
+
     pub fn recreate_swapchain(&mut self, window_drawable_size: (u32, u32)) -> RisResult<()> {
         let Self {
             instance,
@@ -252,6 +265,7 @@ impl VulkanCore {
             command_pool,
             transient_command_pool,
             swapchain,
+            vsync,
             ..
         } = self;
 
@@ -272,6 +286,7 @@ impl VulkanCore {
                 surface_loader,
                 surface,
                 window_drawable_size,
+                vsync: *vsync,
                 frames_in_flight,
             })?;
         }
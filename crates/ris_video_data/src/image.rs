@@ -19,6 +19,7 @@ pub struct ImageCreateInfo<'a> {
     pub device: &'a ash::Device,
     pub width: u32,
     pub height: u32,
+    pub mip_levels: u32,
     pub format: vk::Format,
     pub tiling: vk::ImageTiling,
     pub usage: vk::ImageUsageFlags,
@@ -31,6 +32,7 @@ pub struct TransitionLayoutInfo<'a> {
     pub queue: vk::Queue,
     pub transient_command_pool: vk::CommandPool,
     pub format: vk::Format,
+    pub mip_levels: u32,
     pub old_layout: vk::ImageLayout,
     pub new_layout: vk::ImageLayout,
     pub sync: TransientCommandSync,
@@ -45,6 +47,7 @@ impl Image {
             device,
             width,
             height,
+            mip_levels,
             format,
             tiling,
             usage,
@@ -63,7 +66,7 @@ impl Image {
                 height,
                 depth: 1,
             },
-            mip_levels: 1,
+            mip_levels,
             array_layers: 1,
             samples: vk::SampleCountFlags::TYPE_1,
             tiling,
@@ -115,6 +118,7 @@ impl Image {
         image: vk::Image,
         format: vk::Format,
         aspect_mask: vk::ImageAspectFlags,
+        mip_levels: u32,
     ) -> RisResult<vk::ImageView> {
         let image_view_create_info = vk::ImageViewCreateInfo {
             s_type: vk::StructureType::IMAGE_VIEW_CREATE_INFO,
@@ -132,7 +136,7 @@ impl Image {
             subresource_range: vk::ImageSubresourceRange {
                 aspect_mask,
                 base_mip_level: 0,
-                level_count: 1,
+                level_count: mip_levels,
                 base_array_layer: 0,
                 layer_count: 1,
             },
@@ -149,6 +153,7 @@ impl Image {
             queue,
             transient_command_pool,
             format,
+            mip_levels,
             old_layout,
             new_layout,
             sync,
@@ -216,7 +221,7 @@ impl Image {
             subresource_range: vk::ImageSubresourceRange {
                 aspect_mask,
                 base_mip_level: 0,
-                level_count: 1,
+                level_count: mip_levels,
                 base_array_layer: 0,
                 layer_count: 1,
             },
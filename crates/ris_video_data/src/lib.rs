@@ -24,5 +24,7 @@ const VALIDATION_ENABLED: bool = true;
 const PREFERRED_FORMAT: vk::Format = vk::Format::B8G8R8A8_SRGB;
 const PREFERRED_COLOR_SPACE: vk::ColorSpaceKHR = vk::ColorSpaceKHR::SRGB_NONLINEAR;
 const PREFERRED_PRESENT_MODE: vk::PresentModeKHR = vk::PresentModeKHR::IMMEDIATE;
+// always supported
+const VSYNC_PRESENT_MODE: vk::PresentModeKHR = vk::PresentModeKHR::FIFO;
 
 const MAX_FRAMES_IN_FLIGHT: usize = 2;
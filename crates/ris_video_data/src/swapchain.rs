@@ -44,6 +44,7 @@ pub struct SwapchainCreateInfo<'a> {
     pub surface_loader: &'a SurfaceLoader,
     pub surface: &'a vk::SurfaceKHR,
     pub window_drawable_size: (u32, u32),
+    pub vsync: bool,
     pub frames_in_flight: Option<Vec<FrameInFlight>>,
 }
 
@@ -85,6 +86,7 @@ impl Swapchain {
             surface_loader,
             surface,
             window_drawable_size,
+            vsync,
             frames_in_flight,
         } = info;
 
@@ -102,9 +104,13 @@ impl Swapchain {
             None => formats[0],
         };
 
-        let preferred_surface_present_mode = present_modes
-            .iter()
-            .find(|&&x| x == super::PREFERRED_PRESENT_MODE);
+        let preferred_present_mode = if vsync {
+            super::VSYNC_PRESENT_MODE
+        } else {
+            super::PREFERRED_PRESENT_MODE
+        };
+        let preferred_surface_present_mode =
+            present_modes.iter().find(|&&x| x == preferred_present_mode);
         let surface_present_mode = match preferred_surface_present_mode {
             Some(present_mode) => present_mode,
             None => &present_modes[0],
@@ -204,6 +210,7 @@ impl Swapchain {
                 viewport_image,
                 format.format,
                 vk::ImageAspectFlags::COLOR,
+                1,
             )?;
 
             // depth
@@ -211,6 +218,7 @@ impl Swapchain {
                 device,
                 width: extent.width,
                 height: extent.height,
+                mip_levels: 1,
                 format: depth_format,
                 tiling: vk::ImageTiling::OPTIMAL,
                 usage: vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
@@ -223,6 +231,7 @@ impl Swapchain {
                 depth_image.image,
                 depth_format,
                 vk::ImageAspectFlags::DEPTH,
+                1,
             )?;
 
             depth_image.transition_layout(TransitionLayoutInfo {
@@ -230,6 +239,7 @@ impl Swapchain {
                 queue: graphics_queue,
                 transient_command_pool,
                 format: depth_format,
+                mip_levels: 1,
                 old_layout: vk::ImageLayout::UNDEFINED,
                 new_layout: vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
                 sync: TransientCommandSync::default(),
@@ -28,7 +28,12 @@ pub struct TextureCreateInfo<'a> {
     pub height: u32,
     pub format: vk::Format,
     pub filter: vk::Filter,
+    pub mipmap_mode: vk::SamplerMipmapMode,
+    pub address_mode: vk::SamplerAddressMode,
     pub pixels_rgba: &'a [u8],
+    /// all mip levels after `pixels_rgba`. each level is half the size of the previous one,
+    /// rounded down, but at least 1.
+    pub mips_rgba: &'a [&'a [u8]],
 }
 
 impl Texture {
@@ -46,31 +51,48 @@ impl Texture {
             height,
             format,
             filter,
+            mipmap_mode,
+            address_mode,
             pixels_rgba,
+            mips_rgba,
         } = info;
 
         ris_error::debug_assert!(width != 0)?;
         ris_error::debug_assert!(height != 0)?;
 
-        let actual_len = pixels_rgba.len();
-        let expected_len = (width * height * 4) as usize;
-        ris_error::debug_assert!(actual_len == expected_len)?;
+        // (width, height, offset in the staging buffer) of each mip level
+        let mut levels = Vec::with_capacity(mips_rgba.len() + 1);
+        let mut staging_data = Vec::new();
+        let (mut level_width, mut level_height) = (width, height);
+        for level_pixels in std::iter::once(pixels_rgba).chain(mips_rgba.iter().copied()) {
+            let actual_len = level_pixels.len();
+            let expected_len = (level_width * level_height * 4) as usize;
+            ris_error::debug_assert!(actual_len == expected_len)?;
+
+            levels.push((level_width, level_height, staging_data.len()));
+            staging_data.extend_from_slice(level_pixels);
+
+            level_width = (level_width / 2).max(1);
+            level_height = (level_height / 2).max(1);
+        }
+        let mip_levels = levels.len() as u32;
 
         // create image and copy asset to it
         let staging_buffer = Buffer::alloc(
             device,
-            pixels_rgba.len() as vk::DeviceSize,
+            staging_data.len() as vk::DeviceSize,
             vk::BufferUsageFlags::TRANSFER_SRC,
             vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
             physical_device_memory_properties,
         )?;
 
-        staging_buffer.write(device, pixels_rgba)?;
+        staging_buffer.write(device, &staging_data)?;
 
         let image = Image::alloc(ImageCreateInfo {
             device,
             width,
             height,
+            mip_levels,
             format,
             tiling: vk::ImageTiling::OPTIMAL,
             usage: vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED,
@@ -83,26 +105,32 @@ impl Texture {
             queue,
             transient_command_pool,
             format,
+            mip_levels,
             old_layout: vk::ImageLayout::UNDEFINED,
             new_layout: vk::ImageLayout::TRANSFER_DST_OPTIMAL,
             sync: TransientCommandSync::default(),
         })?;
 
-        staging_buffer.copy_to_image(CopyToImageInfo {
-            device,
-            queue,
-            transient_command_pool,
-            image: image.image,
-            width,
-            height,
-            sync: TransientCommandSync::default(),
-        })?;
+        for (mip_level, (level_width, level_height, offset)) in levels.into_iter().enumerate() {
+            staging_buffer.copy_to_image(CopyToImageInfo {
+                device,
+                queue,
+                transient_command_pool,
+                image: image.image,
+                width: level_width,
+                height: level_height,
+                buffer_offset: offset as vk::DeviceSize,
+                mip_level: mip_level as u32,
+                sync: TransientCommandSync::default(),
+            })?;
+        }
 
         image.transition_layout(TransitionLayoutInfo {
             device,
             queue,
             transient_command_pool,
             format,
+            mip_levels,
             old_layout: vk::ImageLayout::TRANSFER_DST_OPTIMAL,
             new_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
             sync: TransientCommandSync::default(),
@@ -111,7 +139,13 @@ impl Texture {
         staging_buffer.free(device);
 
         // create image view
-        let view = Image::alloc_view(device, image.image, format, vk::ImageAspectFlags::COLOR)?;
+        let view = Image::alloc_view(
+            device,
+            image.image,
+            format,
+            vk::ImageAspectFlags::COLOR,
+            mip_levels,
+        )?;
 
         // create sampler
         let sampler_create_info = vk::SamplerCreateInfo {
@@ -120,17 +154,17 @@ impl Texture {
             flags: vk::SamplerCreateFlags::empty(),
             mag_filter: filter,
             min_filter: filter,
-            mipmap_mode: vk::SamplerMipmapMode::NEAREST,
-            address_mode_u: vk::SamplerAddressMode::REPEAT,
-            address_mode_v: vk::SamplerAddressMode::REPEAT,
-            address_mode_w: vk::SamplerAddressMode::REPEAT,
+            mipmap_mode,
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            address_mode_w: address_mode,
             mip_lod_bias: 0.0,
             anisotropy_enable: vk::TRUE,
             max_anisotropy: physical_device_properties.limits.max_sampler_anisotropy,
             compare_enable: vk::FALSE,
             compare_op: vk::CompareOp::ALWAYS,
             min_lod: 0.0,
-            max_lod: 0.0,
+            max_lod: mip_levels as f32,
             border_color: vk::BorderColor::INT_OPAQUE_BLACK,
             unnormalized_coordinates: vk::FALSE,
         };
@@ -74,7 +74,10 @@ impl GizmoTextMesh {
             height: 1,
             format: vk::Format::R8G8B8A8_UINT,
             filter: vk::Filter::NEAREST,
+            mipmap_mode: vk::SamplerMipmapMode::NEAREST,
+            address_mode: vk::SamplerAddressMode::REPEAT,
             pixels_rgba: text,
+            mips_rgba: &[],
         })?;
 
         Ok(Self {
@@ -143,7 +146,10 @@ impl GizmoTextMesh {
                     height: 1,
                     format: vk::Format::R8G8B8A8_UINT,
                     filter: vk::Filter::NEAREST,
+                    mipmap_mode: vk::SamplerMipmapMode::NEAREST,
+                    address_mode: vk::SamplerAddressMode::REPEAT,
                     pixels_rgba: text,
+                    mips_rgba: &[],
                 })
             }?;
 
@@ -172,6 +178,7 @@ impl GizmoTextMesh {
                     queue: *graphics_queue,
                     transient_command_pool: *transient_command_pool,
                     format: vk::Format::R8G8B8A8_UINT,
+                    mip_levels: 1,
                     old_layout: vk::ImageLayout::UNDEFINED,
                     new_layout: vk::ImageLayout::TRANSFER_DST_OPTIMAL,
                     sync: TransientCommandSync::default(),
@@ -184,6 +191,8 @@ impl GizmoTextMesh {
                     image: image.image,
                     width: (text.len() / 4) as u32,
                     height: 1,
+                    buffer_offset: 0,
+                    mip_level: 0,
                     sync: TransientCommandSync::default(),
                 })?;
 
@@ -192,6 +201,7 @@ impl GizmoTextMesh {
                     queue: *graphics_queue,
                     transient_command_pool: *transient_command_pool,
                     format: vk::Format::R8G8B8A8_UINT,
+                    mip_levels: 1,
                     old_layout: vk::ImageLayout::TRANSFER_DST_OPTIMAL,
                     new_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
                     sync: TransientCommandSync::default(),
@@ -2,7 +2,6 @@ use std::ptr;
 
 use ash::vk;
 
-use ris_asset::codecs::qoi;
 use ris_asset::RisGodAsset;
 use ris_debug::gizmo::GizmoTextVertex;
 use ris_error::Extensions;
@@ -13,7 +12,6 @@ use ris_video_data::buffer::Buffer;
 use ris_video_data::core::VulkanCore;
 use ris_video_data::swapchain::SwapchainEntry;
 use ris_video_data::texture::Texture;
-use ris_video_data::texture::TextureCreateInfo;
 
 use super::gizmo_text_mesh::GizmoTextMesh;
 
@@ -85,8 +83,6 @@ impl GizmoTextRenderer {
             instance,
             suitable_device,
             device,
-            graphics_queue,
-            transient_command_pool,
             swapchain,
             ..
         } = core;
@@ -498,26 +494,13 @@ impl GizmoTextRenderer {
         // texture
         let font_future = ris_asset::load_async(god_asset.debug_font_texture.clone());
         let font_data = font_future.wait(None)??;
-        let (pixels, desc) = qoi::decode(&font_data, None)?;
-
-        let pixels_rgba = match desc.channels {
-            qoi::Channels::RGB => ris_asset::util::add_alpha_channel(&pixels)?,
-            qoi::Channels::RGBA => pixels,
-        };
-
         let font_texture = unsafe {
-            Texture::alloc(TextureCreateInfo {
-                device,
-                queue: *graphics_queue,
-                transient_command_pool: *transient_command_pool,
+            crate::texture_asset::alloc(
+                core,
                 physical_device_memory_properties,
                 physical_device_properties,
-                width: desc.width,
-                height: desc.height,
-                format: vk::Format::R8G8B8A8_SRGB,
-                filter: vk::Filter::NEAREST,
-                pixels_rgba: &pixels_rgba,
-            })
+                &font_data,
+            )
         }?;
 
         // frames
@@ -434,7 +434,10 @@ impl ImguiRenderer {
                 height: font_atlas_texture.height,
                 format: vk::Format::R8G8B8A8_SRGB,
                 filter: vk::Filter::LINEAR,
+                mipmap_mode: vk::SamplerMipmapMode::NEAREST,
+                address_mode: vk::SamplerAddressMode::REPEAT,
                 pixels_rgba: font_atlas_texture.data,
+                mips_rgba: &[],
             })
         }?;
 
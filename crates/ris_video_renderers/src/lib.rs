@@ -2,6 +2,8 @@ pub mod gizmo;
 pub mod imgui;
 pub mod scene;
 
+pub mod texture_asset;
+
 pub use gizmo::gizmo_segment_renderer::GizmoSegmentRenderer;
 pub use gizmo::gizmo_text_renderer::GizmoTextRenderer;
 pub use imgui::imgui_backend::ImguiBackend;
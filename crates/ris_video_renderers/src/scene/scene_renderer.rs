@@ -2,7 +2,6 @@ use std::ptr;
 
 use ash::vk;
 
-use ris_asset::codecs::qoi;
 use ris_asset::RisGodAsset;
 use ris_data::ecs::mesh::VERTEX_ATTRIBUTE_DESCRIPTIONS;
 use ris_data::ecs::mesh::VERTEX_BINDING_DESCRIPTIONS;
@@ -15,7 +14,6 @@ use ris_video_data::buffer::Buffer;
 use ris_video_data::core::VulkanCore;
 use ris_video_data::swapchain::SwapchainEntry;
 use ris_video_data::texture::Texture;
-use ris_video_data::texture::TextureCreateInfo;
 
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
@@ -96,8 +94,6 @@ impl SceneRenderer {
             instance,
             suitable_device,
             device,
-            graphics_queue,
-            transient_command_pool,
             swapchain,
             ..
         } = core;
@@ -111,26 +107,13 @@ impl SceneRenderer {
         // texture
         let texture_asset_id = god_asset.texture.clone();
         let content = ris_asset::load_async(texture_asset_id.clone()).wait(None)??;
-        let (pixels, desc) = qoi::decode(&content, None)?;
-
-        let pixels_rgba = match desc.channels {
-            qoi::Channels::RGB => ris_asset::util::add_alpha_channel(&pixels)?,
-            qoi::Channels::RGBA => pixels,
-        };
-
         let texture = unsafe {
-            Texture::alloc(TextureCreateInfo {
-                device,
-                queue: *graphics_queue,
-                transient_command_pool: *transient_command_pool,
+            crate::texture_asset::alloc(
+                core,
                 physical_device_memory_properties,
                 physical_device_properties,
-                width: desc.width,
-                height: desc.height,
-                format: vk::Format::R8G8B8A8_SRGB,
-                filter: vk::Filter::LINEAR,
-                pixels_rgba: &pixels_rgba,
-            })
+                &content,
+            )
         }?;
 
         // push constants
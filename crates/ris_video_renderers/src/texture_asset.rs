@@ -0,0 +1,61 @@
+use ash::vk;
+
+use ris_asset::assets::ris_texture::RisTexture;
+use ris_asset::assets::ris_texture::TextureColorSpace;
+use ris_asset::assets::ris_texture::TextureFilter;
+use ris_asset::assets::ris_texture::TextureWrap;
+use ris_error::RisResult;
+use ris_video_data::core::VulkanCore;
+use ris_video_data::texture::Texture;
+use ris_video_data::texture::TextureCreateInfo;
+
+/// uploads a ris_texture asset, including all its mip levels and sampler settings
+///
+/// # Safety
+///
+/// `free()` must be called on the returned texture, or you are leaking memory.
+pub unsafe fn alloc(
+    core: &VulkanCore,
+    physical_device_memory_properties: vk::PhysicalDeviceMemoryProperties,
+    physical_device_properties: vk::PhysicalDeviceProperties,
+    bytes: &[u8],
+) -> RisResult<Texture> {
+    let texture = RisTexture::load(bytes)?;
+
+    let format = match texture.color_space {
+        TextureColorSpace::Srgb => vk::Format::R8G8B8A8_SRGB,
+        TextureColorSpace::Linear => vk::Format::R8G8B8A8_UNORM,
+    };
+    let (filter, mipmap_mode) = match texture.filter {
+        TextureFilter::Nearest => (vk::Filter::NEAREST, vk::SamplerMipmapMode::NEAREST),
+        TextureFilter::Linear => (vk::Filter::LINEAR, vk::SamplerMipmapMode::LINEAR),
+    };
+    let address_mode = match texture.wrap {
+        TextureWrap::Repeat => vk::SamplerAddressMode::REPEAT,
+        TextureWrap::MirroredRepeat => vk::SamplerAddressMode::MIRRORED_REPEAT,
+        TextureWrap::ClampToEdge => vk::SamplerAddressMode::CLAMP_TO_EDGE,
+    };
+
+    let mips_rgba = texture.mips[1..]
+        .iter()
+        .map(|x| x.pixels_rgba.as_slice())
+        .collect::<Vec<_>>();
+
+    unsafe {
+        Texture::alloc(TextureCreateInfo {
+            device: &core.device,
+            queue: core.graphics_queue,
+            transient_command_pool: core.transient_command_pool,
+            physical_device_memory_properties,
+            physical_device_properties,
+            width: texture.width(),
+            height: texture.height(),
+            format,
+            filter,
+            mipmap_mode,
+            address_mode,
+            pixels_rgba: &texture.mips[0].pixels_rgba,
+            mips_rgba: &mips_rgba,
+        })
+    }
+}
@@ -2,10 +2,13 @@
 
 use std::path::PathBuf;
 
+use ris_core::crash_report;
 use ris_core::god_job;
 use ris_core::god_object::GodObject;
 use ris_core::log_appenders::console_appender::ConsoleAppender;
 use ris_core::log_appenders::file_appender::FileAppender;
+use ris_core::log_appenders::json_appender::JsonAppender;
+use ris_core::log_appenders::ring_buffer_appender::RingBufferAppender;
 use ris_core::log_appenders::ui_helper_appender::UiHelperAppender;
 use ris_data::info::app_info::AppInfo;
 use ris_data::info::args_info::ArgsInfo;
@@ -18,12 +21,18 @@ use ris_error::RisResult;
 use ris_log::log;
 use ris_log::log::IAppender;
 use ris_log::log::LogGuard;
+use ris_log::log_filter::LogFilter;
 use ris_log::log_level::LogLevel;
 use ris_log::log_message::LogMessage;
+use ris_log::log_queue::OverflowPolicy;
+use ris_log::log_queue::QueueOptions;
 
 pub mod scripts;
 
 pub const LOG_LEVEL: LogLevel = LogLevel::Trace;
+pub const JSON_LOG_FILENAME: &str = "current.jsonl";
+pub const CRASH_REPORT_LOG_COUNT: usize = 256;
+pub const LOG_QUEUE_CAPACITY: usize = 8192;
 pub const RESTART_CODE: i32 = 42;
 
 fn main() -> Result<(), String> {
@@ -77,19 +86,50 @@ fn setup_logging(app_info: &AppInfo) -> RisResult<LogGuard> {
 
     let console_appender = Box::new(ConsoleAppender);
     let file_appender = Box::new(FileAppender::new(&logs_dir)?);
+    let json_appender = Box::new(JsonAppender::new(&logs_dir.join(JSON_LOG_FILENAME))?);
+    let ring_buffer_appender = Box::new(RingBufferAppender::new(CRASH_REPORT_LOG_COUNT)?);
     let ui_helper_appender = Box::new(UiHelperAppender::new()?);
-    let appenders: Vec<Box<dyn IAppender + Send>> =
-        vec![console_appender, file_appender, ui_helper_appender];
+    let appenders: Vec<Box<dyn IAppender + Send>> = vec![
+        console_appender,
+        file_appender,
+        json_appender,
+        ring_buffer_appender,
+        ui_helper_appender,
+    ];
+
+    // until the settings are loaded, the filter of the args or the default level is used
+    let log_filter = match &app_info.args.log_filter {
+        Some(log_filter) => log_filter.clone(),
+        None => LogFilter::new(LOG_LEVEL),
+    };
 
-    let log_guard = log::init(LOG_LEVEL, appenders);
+    // under pressure, only messages below warning are dropped
+    let queue_options = QueueOptions {
+        capacity: LOG_QUEUE_CAPACITY,
+        policy: OverflowPolicy::DropBelow(LogLevel::Warning),
+        ..Default::default()
+    };
+    let log_guard = log::init_with_queue(log_filter, appenders, queue_options);
 
     Ok(log_guard)
 }
 
 fn run_engine(app_info: AppInfo) -> RisResult<()> {
     let _log_guard = setup_logging(&app_info)?;
+    crash_report::set_panic_hook();
     ris_log::log::forward_to_appenders(LogMessage::Plain(app_info.to_string()));
 
+    let mut crash_report_dir = PathBuf::from(&app_info.file.pref_path);
+    crash_report_dir.push(crash_report::DIRECTORY_NAME);
+    let crash_report_app_info = app_info.clone();
+    crash_report::report_unresumed_panics(crash_report_dir.clone(), app_info.clone());
+
+    crash_report::catch_unwind(&crash_report_dir, &crash_report_app_info, || {
+        run_god_job(app_info)
+    })
+}
+
+fn run_god_job(app_info: AppInfo) -> RisResult<()> {
     let script_registry = scripts::registry()?;
 
     let god_object = match GodObject::new(app_info, script_registry) {
@@ -1,8 +1,10 @@
 use ris_data::ecs::script_prelude::*;
+use ris_io::RisDeserialize;
+use ris_io::RisSerialize;
 use ris_math::quaternion::Quat;
 use ris_math::vector::Vec3;
 
-#[derive(Debug)]
+#[derive(Debug, RisSerialize, RisDeserialize)]
 pub struct TestRotationScript {
     pub rotation_axis: Vec3,
 }
@@ -54,12 +56,12 @@ impl Script for TestRotationScript {
     }
 
     fn serialize(&mut self, f: &mut SceneWriter) -> RisResult<()> {
-        ris_io::write_vec3(f, self.rotation_axis)?;
+        ris_io::serialize(f, self)?;
         Ok(())
     }
 
     fn deserialize(&mut self, f: &mut SceneReader) -> RisResult<()> {
-        self.rotation_axis = ris_io::read_vec3(f)?;
+        *self = ris_io::deserialize(f)?;
         Ok(())
     }
 }
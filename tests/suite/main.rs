@@ -1,9 +1,11 @@
 pub mod ris_asset;
+pub mod ris_core;
 pub mod ris_data;
 pub mod ris_debug;
 pub mod ris_input;
 pub mod ris_io;
 pub mod ris_jobs;
+pub mod ris_log;
 pub mod ris_math;
 pub mod ris_rng;
 pub mod ris_util;
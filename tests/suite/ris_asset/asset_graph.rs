@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use ris_asset::asset_compiler;
+use ris_asset::asset_compiler::CompileOptions;
+use ris_asset::asset_graph::AssetGraph;
+use ris_asset::assets::ris_god_asset;
+use ris_asset::RisHeader;
+use ris_data::asset_id::AssetId;
+
+const MAGIC: [u8; 16] = *b"ris_test_asset\0\0";
+
+fn write_asset(directory: &Path, id: &str, references: &[&str]) {
+    let references = references
+        .iter()
+        .map(|x| AssetId::Path(x.to_string()))
+        .collect();
+    let header = RisHeader::new(MAGIC, references);
+    let bytes = header.serialize().unwrap();
+
+    let path = directory.join(id);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn write_raw(directory: &Path, id: &str, bytes: &[u8]) {
+    let path = directory.join(id);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn build(directory: &Path) -> AssetGraph {
+    AssetGraph::build(directory.to_str().unwrap()).unwrap()
+}
+
+#[test]
+fn should_find_root_and_reachable_assets() {
+    let dir = ris_util::prep_test_dir!();
+    write_asset(&dir, ris_god_asset::PATH, &["a.asset", "sub/b.txt"]);
+    write_asset(&dir, "a.asset", &["c.txt"]);
+    write_raw(&dir, "sub/b.txt", b"hello world");
+    write_raw(&dir, "c.txt", b"c");
+    write_raw(&dir, "unused.txt", b"");
+    write_asset(&dir, "unused.asset", &["a.asset"]);
+
+    let graph = build(&dir);
+
+    assert_eq!(graph.root(), Some(ris_god_asset::PATH));
+    assert!(graph.is_reachable(ris_god_asset::PATH));
+    assert!(graph.is_reachable("a.asset"));
+    assert!(graph.is_reachable("sub/b.txt"));
+    assert!(graph.is_reachable("c.txt"));
+    assert_eq!(graph.unreachable(), vec!["unused.asset", "unused.txt"]);
+    assert!(graph.missing_references().is_empty());
+    assert!(graph.cycles().is_empty());
+}
+
+#[test]
+fn should_find_missing_references() {
+    let dir = ris_util::prep_test_dir!();
+    write_asset(&dir, ris_god_asset::PATH, &["a.asset", "missing_1.txt"]);
+    write_asset(&dir, "a.asset", &["missing_2.txt"]);
+
+    let graph = build(&dir);
+
+    assert_eq!(
+        graph.missing_references(),
+        vec![
+            ("a.asset", "missing_2.txt"),
+            (ris_god_asset::PATH, "missing_1.txt"),
+        ],
+    );
+}
+
+#[test]
+fn should_find_cycles() {
+    let dir = ris_util::prep_test_dir!();
+    write_asset(&dir, ris_god_asset::PATH, &["a.asset"]);
+    write_asset(&dir, "a.asset", &["b.asset"]);
+    write_asset(&dir, "b.asset", &["c.asset", "d.asset"]);
+    write_asset(&dir, "c.asset", &["a.asset"]);
+    write_asset(&dir, "d.asset", &[]);
+    write_asset(&dir, "e.asset", &["e.asset"]);
+
+    let graph = build(&dir);
+
+    assert_eq!(
+        graph.cycles(),
+        vec![vec!["a.asset", "b.asset", "c.asset"], vec!["e.asset"]],
+    );
+}
+
+#[test]
+fn should_have_no_root_without_god_asset() {
+    let dir = ris_util::prep_test_dir!();
+    write_asset(&dir, "a.asset", &[]);
+
+    let graph = build(&dir);
+
+    assert_eq!(graph.root(), None);
+    assert_eq!(graph.unreachable(), vec!["a.asset"]);
+}
+
+#[test]
+fn should_export_dot() {
+    let dir = ris_util::prep_test_dir!();
+    write_asset(&dir, ris_god_asset::PATH, &["a.asset", "missing.txt"]);
+    write_asset(&dir, "a.asset", &[]);
+    write_raw(&dir, "unused.txt", b"");
+
+    let dot = build(&dir).to_dot();
+
+    let expected = format!(
+        "digraph assets {{
+    \"a.asset\";
+    \"{0}\" [shape=box];
+    \"unused.txt\" [color=gray, fontcolor=gray];
+    \"missing.txt\" [color=red, fontcolor=red, style=dashed];
+    \"{0}\" -> \"a.asset\";
+    \"{0}\" -> \"missing.txt\";
+}}
+",
+        ris_god_asset::PATH
+    );
+    assert_eq!(dot, expected);
+}
+
+#[test]
+fn should_strip_unreachable_assets_when_compiling() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("source");
+    let compiled = dir.join("compiled");
+    let decompiled = dir.join("decompiled");
+    write_asset(&source, ris_god_asset::PATH, &["a.txt"]);
+    write_raw(&source, "a.txt", b"a");
+    write_raw(&source, "unused.txt", b"unused");
+
+    let options = CompileOptions {
+        include_original_paths: true,
+        strip_unreachable: true,
+    };
+    asset_compiler::compile(
+        source.to_str().unwrap(),
+        compiled.to_str().unwrap(),
+        options,
+    )
+    .unwrap();
+    asset_compiler::decompile(compiled.to_str().unwrap(), decompiled.to_str().unwrap()).unwrap();
+
+    assert!(decompiled.join(ris_god_asset::PATH).exists());
+    assert!(decompiled.join("a.txt").exists());
+    assert!(!decompiled.join("unused.txt").exists());
+}
@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
+
+fn write_png(path: &Path, color: u8) {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, 2, 2);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[color; 12]).unwrap();
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut file = File::create(path).unwrap();
+    file.write_all(&bytes).unwrap();
+}
+
+fn import(dir: &Path, force: bool) {
+    let source = dir.join("source");
+    let target = dir.join("target");
+    asset_importer::import_all(
+        source.to_str().unwrap(),
+        target.to_str().unwrap(),
+        None,
+        ImportOptions { force },
+    )
+    .unwrap();
+}
+
+// marks an imported file, such that it can be detected whether it was imported again
+fn mark(path: &Path) {
+    let file = File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+}
+
+fn is_marked(path: &Path) -> bool {
+    let modified = path.metadata().unwrap().modified().unwrap();
+    modified == SystemTime::UNIX_EPOCH
+}
+
+#[test]
+fn should_import_all() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    write_png(&dir.join("source/sub/b.png"), 1);
+
+    import(&dir, false);
+
+    assert!(dir.join("target/a.ris_texture").exists());
+    assert!(dir.join("target/sub/b.ris_texture").exists());
+}
+
+#[test]
+fn should_skip_assets_that_are_up_to_date() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    import(&dir, false);
+    mark(&dir.join("target/a.ris_texture"));
+
+    import(&dir, false);
+
+    assert!(is_marked(&dir.join("target/a.ris_texture")));
+}
+
+#[test]
+fn should_import_changed_assets() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    write_png(&dir.join("source/b.png"), 0);
+    import(&dir, false);
+    mark(&dir.join("target/a.ris_texture"));
+    mark(&dir.join("target/b.ris_texture"));
+
+    write_png(&dir.join("source/b.png"), 42);
+    import(&dir, false);
+
+    assert!(is_marked(&dir.join("target/a.ris_texture")));
+    assert!(!is_marked(&dir.join("target/b.ris_texture")));
+}
+
+#[test]
+fn should_import_assets_whose_imported_files_were_deleted() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    import(&dir, false);
+
+    std::fs::remove_file(dir.join("target/a.ris_texture")).unwrap();
+    import(&dir, false);
+
+    assert!(dir.join("target/a.ris_texture").exists());
+}
+
+#[test]
+fn should_import_everything_when_forced() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    import(&dir, false);
+    mark(&dir.join("target/a.ris_texture"));
+    File::create(dir.join("target/unrelated.txt")).unwrap();
+
+    import(&dir, true);
+
+    assert!(!is_marked(&dir.join("target/a.ris_texture")));
+    assert!(!dir.join("target/unrelated.txt").exists());
+}
+
+#[test]
+fn should_delete_imported_files_of_deleted_assets() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    write_png(&dir.join("source/b.png"), 0);
+    import(&dir, false);
+
+    std::fs::remove_file(dir.join("source/b.png")).unwrap();
+    import(&dir, false);
+
+    assert!(dir.join("target/a.ris_texture").exists());
+    assert!(!dir.join("target/b.ris_texture").exists());
+}
+
+#[test]
+fn should_import_everything_when_cache_is_corrupt() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    import(&dir, false);
+    mark(&dir.join("target/a.ris_texture"));
+
+    let cache_path = ris_asset::import_cache::ImportCache::path(&dir.join("target"));
+    std::fs::write(cache_path, [1, 2, 3]).unwrap();
+    import(&dir, false);
+
+    assert!(!is_marked(&dir.join("target/a.ris_texture")));
+}
+
+#[test]
+fn should_not_use_time_for_up_to_date_check() {
+    let dir = ris_util::prep_test_dir!();
+    write_png(&dir.join("source/a.png"), 0);
+    import(&dir, false);
+    mark(&dir.join("target/a.ris_texture"));
+
+    // touching the file without changing its content must not cause an import
+    let file = File::options()
+        .write(true)
+        .open(dir.join("source/a.png"))
+        .unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    import(&dir, false);
+
+    assert!(is_marked(&dir.join("target/a.ris_texture")));
+}
@@ -0,0 +1,167 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use ris_asset::asset_watcher::AssetWatcher;
+use ris_asset::asset_watcher::AssetWatcherInfo;
+use ris_data::asset_id::AssetId;
+
+fn write_file(path: &Path, bytes: &[u8], modified: SystemTime) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut file = File::create(path).unwrap();
+    file.write_all(bytes).unwrap();
+    file.set_modified(modified).unwrap();
+}
+
+fn write_png(path: &Path, modified: SystemTime) {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, 2, 2);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0; 12]).unwrap();
+    }
+
+    write_file(path, &bytes, modified);
+}
+
+fn create_watcher(asset_directory: &Path) -> AssetWatcher {
+    let source_directory = asset_directory.join("__raw");
+    let target_directory = asset_directory.join("__imported_raw");
+    std::fs::create_dir_all(&source_directory).unwrap();
+
+    let info = AssetWatcherInfo {
+        asset_directory: PathBuf::from(asset_directory),
+        source_directory: source_directory.to_str().unwrap().to_string(),
+        target_directory: target_directory.to_str().unwrap().to_string(),
+        temp_directory: None,
+        poll_interval: Duration::ZERO,
+    };
+
+    AssetWatcher::new(info).unwrap()
+}
+
+#[test]
+fn should_not_report_unchanged_assets() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    write_file(&dir.join("scenes/a.ris_scene"), &[1, 2, 3], t0);
+    write_png(&dir.join("__raw/texture.png"), t0);
+
+    let mut watcher = create_watcher(&dir);
+
+    assert!(watcher.poll().unwrap().is_empty());
+    assert!(watcher.poll().unwrap().is_empty());
+}
+
+#[test]
+fn should_report_changed_and_new_assets() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(2000);
+    write_file(&dir.join("scenes/a.ris_scene"), &[1, 2, 3], t0);
+    write_file(&dir.join("scenes/b.ris_scene"), &[4, 5, 6], t0);
+
+    let mut watcher = create_watcher(&dir);
+
+    write_file(&dir.join("scenes/b.ris_scene"), &[7, 8, 9], t1);
+    write_file(&dir.join("c.ris_scene"), &[10], t0);
+
+    let mut changed = watcher.poll().unwrap();
+    changed.sort_by_key(|x| format!("{:?}", x));
+
+    assert_eq!(
+        changed,
+        vec![
+            AssetId::Path(String::from("c.ris_scene")),
+            AssetId::Path(String::from("scenes/b.ris_scene")),
+        ],
+    );
+    assert!(watcher.poll().unwrap().is_empty());
+}
+
+#[test]
+fn should_report_deleted_and_recreated_assets() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    write_file(&dir.join("scenes/a.ris_scene"), &[1, 2, 3], t0);
+
+    let mut watcher = create_watcher(&dir);
+
+    std::fs::remove_file(dir.join("scenes/a.ris_scene")).unwrap();
+    assert!(watcher.poll().unwrap().is_empty());
+
+    // same modification time as before it was deleted
+    write_file(&dir.join("scenes/a.ris_scene"), &[4, 5, 6], t0);
+    assert_eq!(
+        watcher.poll().unwrap(),
+        vec![AssetId::Path(String::from("scenes/a.ris_scene"))],
+    );
+}
+
+#[test]
+fn should_reimport_changed_raw_assets() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(2000);
+    write_png(&dir.join("__raw/textures/texture.png"), t0);
+
+    let mut watcher = create_watcher(&dir);
+    let imported_path = dir.join("__imported_raw/textures/texture.ris_texture");
+    assert!(!imported_path.exists());
+
+    write_png(&dir.join("__raw/textures/texture.png"), t1);
+
+    let changed = watcher.poll().unwrap();
+
+    assert!(imported_path.exists());
+    assert_eq!(
+        changed,
+        vec![AssetId::Path(String::from(
+            "__imported_raw/textures/texture.ris_texture"
+        ))],
+    );
+}
+
+#[test]
+fn should_not_kill_watcher_when_import_fails() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(2000);
+    write_png(&dir.join("__raw/texture.png"), t0);
+
+    let mut watcher = create_watcher(&dir);
+
+    write_file(&dir.join("__raw/texture.png"), &[0, 1, 2, 3], t1);
+
+    let changed = watcher.poll().unwrap();
+
+    assert!(changed.is_empty());
+}
+
+#[test]
+fn should_skip_polls_within_interval() {
+    let dir = ris_util::prep_test_dir!();
+    let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(2000);
+    write_file(&dir.join("a.ris_scene"), &[1], t0);
+
+    let source_directory = dir.join("__raw");
+    std::fs::create_dir_all(&source_directory).unwrap();
+    let info = AssetWatcherInfo {
+        asset_directory: dir.clone(),
+        source_directory: source_directory.to_str().unwrap().to_string(),
+        target_directory: dir.join("__imported_raw").to_str().unwrap().to_string(),
+        temp_directory: None,
+        poll_interval: Duration::from_secs(3600),
+    };
+    let mut watcher = AssetWatcher::new(info).unwrap();
+
+    write_file(&dir.join("a.ris_scene"), &[2], t1);
+
+    assert!(watcher.poll().unwrap().is_empty());
+}
@@ -0,0 +1,290 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
+use ris_asset::asset_loader_directory::AssetLoaderDirectory;
+use ris_asset::assets::ris_shader_variants::RisShaderVariants;
+use ris_asset::importer::glsl_to_spirv_importer;
+use ris_asset::importer::glsl_to_spirv_importer::PreprocessorError;
+use ris_asset::importer::glsl_to_spirv_importer::PreprocessorErrorKind;
+use ris_asset::importer::glsl_to_spirv_importer::SourceLocation;
+use ris_asset::importer::glsl_to_spirv_importer::SourceMap;
+use ris_data::asset_id::AssetId;
+use ris_error::RisResult;
+
+#[test]
+fn should_parse_variants() {
+    let source = "#ris_glsl 450 vertex fragment\n#variant TEXTURED\n#variant VERTEX_COLOR A_2\n";
+    let keywords = glsl_to_spirv_importer::parse_variants(source, "test.glsl").unwrap();
+
+    assert_eq!(keywords, vec!["TEXTURED", "VERTEX_COLOR", "A_2"]);
+}
+
+#[test]
+fn should_not_parse_invalid_variants() {
+    let sources = [
+        "#variant",
+        "#variant textured",
+        "#variant 2D",
+        "#variant A-B",
+        "#variant A A",
+        "#variant A\n#variant A",
+        "#variant A B C D E F G H I",
+    ];
+
+    for source in sources {
+        let result = glsl_to_spirv_importer::parse_variants(source, "test.glsl");
+        assert!(result.is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn should_name_variants() {
+    let keywords = vec![String::from("TEXTURED"), String::from("VERTEX_COLOR")];
+
+    let names = (0..4)
+        .map(|x| glsl_to_spirv_importer::variant_stem("default", &keywords, x))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        vec![
+            "default",
+            "default.textured",
+            "default.vertex_color",
+            "default.textured.vertex_color",
+        ],
+    );
+}
+
+#[test]
+fn should_deduce_target_extensions_of_variants() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("default.glsl");
+    std::fs::write(
+        &source,
+        "#ris_glsl 450 vertex fragment\n#variant TEXTURED\n",
+    )
+    .unwrap();
+
+    let extensions = glsl_to_spirv_importer::target_extensions(&source).unwrap();
+
+    assert_eq!(
+        extensions,
+        vec![
+            "vert.spv",
+            "geom.spv",
+            "frag.spv",
+            "reflection.ris_yaml",
+            "textured.vert.spv",
+            "textured.geom.spv",
+            "textured.frag.spv",
+            "textured.reflection.ris_yaml",
+            "ris_variants",
+        ],
+    );
+}
+
+fn preprocessor_error(result: RisResult<Vec<PathBuf>>) -> PreprocessorError {
+    let error = result.unwrap_err();
+    let source = error.source.expect("error has no source");
+    source
+        .downcast_ref::<PreprocessorError>()
+        .expect("source is not a preprocessor error")
+        .clone()
+}
+
+fn location(file: &Path, line: usize) -> SourceLocation {
+    SourceLocation {
+        file: ris_io::path::to_str(file).replace('\\', "/"),
+        line,
+    }
+}
+
+#[test]
+fn should_rewrite_diagnostics_to_original_locations() {
+    let mut source_map = SourceMap::default();
+    for line in 1..=3 {
+        source_map.push(SourceLocation {
+            file: String::from("default.glsl"),
+            line,
+        });
+    }
+    source_map.push(SourceLocation {
+        file: String::from("util/util.glsl"),
+        line: 7,
+    });
+
+    let message = "default.vert.glsl:4: error: 'foo' : undeclared identifier\n\
+                   default.vert.glsl:2: warning: unused\n\
+                   default.vert.glsl:42: error: out of range\n\
+                   other.glsl:4: error: other file\n";
+    let rewritten = source_map.rewrite("default.vert.glsl", message);
+
+    assert_eq!(
+        rewritten,
+        "util/util.glsl:7: error: 'foo' : undeclared identifier\n\
+         default.glsl:2: warning: unused\n\
+         default.vert.glsl:42: error: out of range\n\
+         other.glsl:4: error: other file\n",
+    );
+}
+
+#[test]
+fn should_report_include_cycles() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("main.glsl");
+    let a = dir.join("a.glsl");
+    let b = dir.join("b.glsl");
+    std::fs::write(
+        &source,
+        "#ris_glsl 450 vertex fragment\n#include a.glsl\nvoid main() {}\n",
+    )
+    .unwrap();
+    std::fs::write(&a, "#ris_glsl header\n#include b.glsl\n").unwrap();
+    std::fs::write(&b, "#ris_glsl header\n// b\n#include a.glsl\n").unwrap();
+
+    let result = glsl_to_spirv_importer::import(source, Vec::new(), &dir, None);
+    let error = preprocessor_error(result);
+
+    let a_location = location(&a, 1);
+    let b_location = location(&b, 1);
+    assert_eq!(error.location, location(&b, 3));
+    assert_eq!(
+        error.kind,
+        PreprocessorErrorKind::IncludeCycle(vec![
+            a_location.file.clone(),
+            b_location.file.clone(),
+            a_location.file.clone(),
+        ]),
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "{}:3: error: include cycle: {} -> {} -> {}",
+            b_location.file, a_location.file, b_location.file, a_location.file,
+        ),
+    );
+}
+
+#[test]
+fn should_report_errors_in_headers_at_their_location() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("main.glsl");
+    let header = dir.join("header.glsl");
+    std::fs::write(
+        &source,
+        "#ris_glsl 450 vertex fragment\n\n#include header.glsl\n",
+    )
+    .unwrap();
+    std::fs::write(&header, "#ris_glsl header\n\n\n#variant A\n").unwrap();
+
+    let result = glsl_to_spirv_importer::import(source, Vec::new(), &dir, None);
+    let error = preprocessor_error(result);
+
+    assert_eq!(error.location, location(&header, 4));
+}
+
+#[test]
+fn should_report_undefined_io_stage_pairs() {
+    let sources = [
+        (
+            "#ris_glsl 450 vertex fragment\n#io vertex geometry\n",
+            "vertex",
+            "geometry",
+        ),
+        (
+            "#ris_glsl 450 vertex geometry fragment\n\n#io fragment vertex\n",
+            "fragment",
+            "vertex",
+        ),
+        (
+            "#ris_glsl 450 geometry fragment\n#vertex\n#io vertex fragment\n",
+            "vertex",
+            "fragment",
+        ),
+    ];
+
+    for (i, (source_text, expected_in, expected_out)) in sources.iter().enumerate() {
+        let dir = ris_util::prep_test_dir!();
+        let source = dir.join("main.glsl");
+        std::fs::write(&source, source_text).unwrap();
+
+        let result = glsl_to_spirv_importer::import(source.clone(), Vec::new(), &dir, None);
+        let error = preprocessor_error(result);
+
+        let line = source_text.lines().count();
+        assert_eq!(error.location, location(&source, line), "{}", i);
+        assert_eq!(
+            error.kind,
+            PreprocessorErrorKind::UndefinedIo(expected_in.to_string(), expected_out.to_string()),
+            "{}",
+            i,
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "{}:{}: error: undefined io stage pair: {} -> {}",
+                error.location.file, line, expected_in, expected_out,
+            ),
+        );
+    }
+}
+
+#[test]
+fn should_write_variant_ids_relative_to_asset_directory() {
+    let dir = ris_util::prep_test_dir!();
+    let assets = dir.join("assets");
+    let source = assets.join("__raw");
+    let target = assets.join("__imported_raw");
+    std::fs::create_dir_all(source.join("shaders")).unwrap();
+    std::fs::write(
+        source.join("shaders/tinted.glsl"),
+        "#ris_glsl 450 vertex fragment
+#variant TINTED
+
+#vertex
+void main() {
+    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+}
+
+#fragment
+layout(location = 0) out vec4 out_color;
+void main() {
+#ifdef TINTED
+    out_color = vec4(1.0, 0.0, 0.0, 1.0);
+#else
+    out_color = vec4(1.0);
+#endif
+}
+",
+    )
+    .unwrap();
+
+    asset_importer::import_all(
+        source.to_str().unwrap(),
+        target.to_str().unwrap(),
+        None,
+        ImportOptions::default(),
+    )
+    .unwrap();
+
+    let bytes = std::fs::read(target.join("shaders/tinted.ris_variants")).unwrap();
+    let table = RisShaderVariants::load(&bytes).unwrap();
+    assert_eq!(table.variants.len(), 2);
+
+    let loader = AssetLoaderDirectory::new(&assets);
+    for variant in table.variants.iter() {
+        assert!(variant.geom.is_none());
+
+        for id in [&variant.vert, &variant.frag] {
+            let Some(AssetId::Path(id)) = id else {
+                panic!("expected a path id, but got {:?}", id);
+            };
+
+            assert!(id.starts_with("__imported_raw/shaders/"), "{}", id);
+            assert!(!loader.load(id.clone()).unwrap().is_empty(), "{}", id);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::path::Path;
+
+use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
+use ris_asset::assets::ris_mesh::RisMesh;
+use ris_asset::importer::gltf_to_ris_mesh_importer;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+// a single triangle. layout of the buffer:
+//
+// - 0..36: positions, 3 x VEC3 f32
+// - 36..60: uvs, 3 x VEC2 f32
+// - 60..72: colors, 3 x VEC4 u8 normalized
+// - 72..78: indices, 3 x u16
+fn triangle_buffer() -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    let positions = [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let uvs = [0.0f32, 0.0, 1.0, 0.0, 0.0, 1.0];
+    for value in positions.iter().chain(uvs.iter()) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    bytes.extend_from_slice(&[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255]);
+
+    for index in [0u16, 1, 2] {
+        bytes.extend_from_slice(&index.to_le_bytes());
+    }
+
+    bytes
+}
+
+fn triangle_document(buffer_uri: Option<&str>, byte_length: usize) -> String {
+    let uri = match buffer_uri {
+        Some(uri) => format!("\"uri\": \"{}\", ", uri),
+        None => String::new(),
+    };
+
+    format!(
+        r#"{{
+    "asset": {{ "version": "2.0" }},
+    "buffers": [ {{ {}"byteLength": {} }} ],
+    "bufferViews": [
+        {{ "buffer": 0, "byteOffset": 0, "byteLength": 72 }},
+        {{ "buffer": 0, "byteOffset": 72, "byteLength": 6 }}
+    ],
+    "accessors": [
+        {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" }},
+        {{ "bufferView": 0, "byteOffset": 36, "componentType": 5126, "count": 3, "type": "VEC2" }},
+        {{ "bufferView": 0, "byteOffset": 60, "componentType": 5121, "normalized": true, "count": 3, "type": "VEC4" }},
+        {{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }}
+    ],
+    "meshes": [
+        {{
+            "name": "triangle",
+            "primitives": [
+                {{
+                    "attributes": {{ "POSITION": 0, "TEXCOORD_0": 1, "COLOR_0": 2 }},
+                    "indices": 3,
+                    "mode": 4
+                }}
+            ]
+        }}
+    ]
+}}"#,
+        uri, byte_length,
+    )
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::new();
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+fn create_glb(document: &str, buffer: &[u8]) -> Vec<u8> {
+    let mut json = document.as_bytes().to_vec();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+
+    let mut bin = buffer.to_vec();
+    while bin.len() % 4 != 0 {
+        bin.push(0);
+    }
+
+    let length = 12 + 8 + json.len() + 8 + bin.len();
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"glTF");
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    bytes.extend_from_slice(&(length as u32).to_le_bytes());
+    bytes.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"JSON");
+    bytes.extend_from_slice(&json);
+    bytes.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"BIN\0");
+    bytes.extend_from_slice(&bin);
+    bytes
+}
+
+fn assert_triangle(mesh: &RisMesh) {
+    assert_eq!(mesh.positions.len(), 3);
+    assert_eq!(mesh.positions[1], Vec3(1.0, 0.0, 0.0));
+    assert_eq!(mesh.positions[2], Vec3(0.0, 1.0, 0.0));
+    assert_eq!(mesh.uvs[1], Vec2(1.0, 0.0));
+    assert_eq!(mesh.colors.len(), 3);
+    assert_eq!(mesh.colors[0].0, 1.0);
+    assert_eq!(mesh.colors[1].1, 1.0);
+    assert_eq!(mesh.colors[2].0, 0.0);
+    assert!(mesh.normals.is_empty());
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+}
+
+#[test]
+fn should_parse_gltf_with_embedded_buffer() {
+    let buffer = triangle_buffer();
+    let uri = format!(
+        "data:application/octet-stream;base64,{}",
+        encode_base64(&buffer)
+    );
+    let document = triangle_document(Some(&uri), buffer.len());
+
+    let (mesh, dependencies) =
+        gltf_to_ris_mesh_importer::parse(document.as_bytes(), Path::new(".")).unwrap();
+
+    assert_triangle(&mesh);
+    assert!(dependencies.is_empty());
+}
+
+#[test]
+fn should_parse_gltf_with_external_buffer() {
+    let dir = ris_util::prep_test_dir!();
+    let buffer = triangle_buffer();
+    std::fs::write(dir.join("triangle.bin"), &buffer).unwrap();
+    let document = triangle_document(Some("triangle.bin"), buffer.len());
+
+    let (mesh, dependencies) = gltf_to_ris_mesh_importer::parse(document.as_bytes(), &dir).unwrap();
+
+    assert_triangle(&mesh);
+    assert_eq!(dependencies, vec![dir.join("triangle.bin")]);
+}
+
+#[test]
+fn should_parse_glb() {
+    let buffer = triangle_buffer();
+    let document = triangle_document(None, buffer.len());
+    let glb = create_glb(&document, &buffer);
+
+    let (mesh, dependencies) = gltf_to_ris_mesh_importer::parse(&glb, Path::new(".")).unwrap();
+
+    assert_triangle(&mesh);
+    assert!(dependencies.is_empty());
+}
+
+#[test]
+fn should_merge_primitives() {
+    let buffer = triangle_buffer();
+    let document = triangle_document(None, buffer.len()).replace(
+        r#""mode": 4
+                }"#,
+        r#""mode": 4
+                },
+                {
+                    "attributes": { "POSITION": 0 }
+                }"#,
+    );
+    let glb = create_glb(&document, &buffer);
+
+    let (mesh, _) = gltf_to_ris_mesh_importer::parse(&glb, Path::new(".")).unwrap();
+
+    assert_eq!(mesh.positions.len(), 6);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 3, 4, 5]);
+    // the second primitive has no uvs and colors, thus the merged mesh has neither
+    assert!(mesh.uvs.is_empty());
+    assert!(mesh.colors.is_empty());
+}
+
+#[test]
+fn should_fail_on_out_of_bounds_accessor() {
+    let buffer = triangle_buffer();
+    let document = triangle_document(None, buffer.len()).replace(
+        "\"count\": 3, \"type\": \"SCALAR\"",
+        "\"count\": 4, \"type\": \"SCALAR\"",
+    );
+    let glb = create_glb(&document, &buffer);
+
+    let result = gltf_to_ris_mesh_importer::parse(&glb, Path::new("."));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_fail_on_overflowing_offsets() {
+    let buffer = triangle_buffer();
+    let documents = [
+        triangle_document(None, buffer.len()).replace(
+            "\"byteOffset\": 72, \"byteLength\": 6",
+            "\"byteOffset\": 18446744073709551615, \"byteLength\": 6",
+        ),
+        triangle_document(None, buffer.len()).replace(
+            "\"byteOffset\": 0, \"byteLength\": 72",
+            "\"byteOffset\": 0, \"byteLength\": 72, \"byteStride\": 9223372036854775808",
+        ),
+    ];
+
+    for document in documents {
+        let glb = create_glb(&document, &buffer);
+        let result = gltf_to_ris_mesh_importer::parse(&glb, Path::new("."));
+        assert!(result.is_err(), "{}", document);
+    }
+}
+
+#[test]
+fn should_fail_on_deeply_nested_json() {
+    let depth = 100_000;
+    let document = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+    let result = gltf_to_ris_mesh_importer::parse(document.as_bytes(), Path::new("."));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_import_fixtures_with_side_files() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("source");
+    let target = dir.join("target");
+    copy_fixtures(&source);
+
+    let import = || {
+        asset_importer::import_all(
+            source.to_str().unwrap(),
+            target.to_str().unwrap(),
+            None,
+            ImportOptions::default(),
+        )
+        .unwrap();
+    };
+
+    import();
+    let bytes = std::fs::read(target.join("triangle.ris_mesh")).unwrap();
+    assert_triangle(&RisMesh::load(&bytes).unwrap());
+    let bytes = std::fs::read(target.join("quad.ris_mesh")).unwrap();
+    let quad = RisMesh::load(&bytes).unwrap();
+    assert_eq!(quad.positions.len(), 4);
+    assert_eq!(quad.indices, vec![0, 1, 2, 0, 2, 3]);
+
+    // side files are not imported on their own
+    assert!(!target.join("triangle.bin").exists());
+    assert!(!target.join("quad.mtl").exists());
+
+    // changing the external buffer must cause the gltf to be imported again
+    let buffer_path = source.join("triangle.bin");
+    let mut changed_buffer = std::fs::read(&buffer_path).unwrap();
+    changed_buffer[12..16].copy_from_slice(&2.0f32.to_le_bytes());
+    std::fs::write(&buffer_path, &changed_buffer).unwrap();
+
+    import();
+    let bytes = std::fs::read(target.join("triangle.ris_mesh")).unwrap();
+    let mesh = RisMesh::load(&bytes).unwrap();
+    assert_eq!(mesh.positions[1], Vec3(2.0, 0.0, 0.0));
+}
+
+fn copy_fixtures(target: &Path) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/meshes");
+    std::fs::create_dir_all(target).unwrap();
+
+    for entry in std::fs::read_dir(fixtures).unwrap() {
+        let entry = entry.unwrap();
+        std::fs::copy(entry.path(), target.join(entry.file_name())).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+use ris_asset::import_cache;
+use ris_asset::import_cache::ImportCache;
+use ris_asset::import_cache::ImportCacheEntry;
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let mut cache = ImportCache::default();
+    cache.insert(
+        String::from("shaders/default.glsl"),
+        ImportCacheEntry {
+            source_hash: 42,
+            importer_version: 3,
+            targets: vec![
+                String::from("shaders/default.vert.spv"),
+                String::from("shaders/default.frag.spv"),
+            ],
+            dependencies: vec![(String::from("shaders/util/util.glsl"), 13)],
+        },
+    );
+    cache.insert(
+        String::from("images/texture.png"),
+        ImportCacheEntry {
+            source_hash: 0,
+            importer_version: 1,
+            targets: vec![String::from("images/texture.qoi")],
+            dependencies: Vec::new(),
+        },
+    );
+
+    let bytes = cache.serialize().unwrap();
+    let deserialized = ImportCache::deserialize(&bytes).unwrap();
+
+    let mut sources = deserialized.sources();
+    sources.sort();
+    assert_eq!(sources, vec!["images/texture.png", "shaders/default.glsl"]);
+    for source in sources {
+        assert_eq!(cache.get(&source), deserialized.get(&source));
+    }
+}
+
+#[test]
+fn should_not_deserialize_invalid_magic() {
+    let cache = ImportCache::default();
+    let mut bytes = cache.serialize().unwrap();
+    bytes[4] = 0;
+
+    assert!(ImportCache::deserialize(&bytes).is_err());
+}
+
+#[test]
+fn should_be_stale_when_dependency_changed() {
+    let dir = ris_util::prep_test_dir!();
+    let dependency_path = dir.join("util.glsl");
+    std::fs::write(&dependency_path, "version 1").unwrap();
+    let dependency_hash = import_cache::hash_file(&dependency_path).unwrap();
+
+    let mut cache = ImportCache::default();
+    cache.insert(
+        String::from("default.glsl"),
+        ImportCacheEntry {
+            source_hash: 42,
+            importer_version: 1,
+            targets: Vec::new(),
+            dependencies: vec![(
+                dependency_path.to_str().unwrap().to_string(),
+                dependency_hash,
+            )],
+        },
+    );
+
+    assert!(!cache.is_stale("default.glsl", 42, 1, &dir));
+    assert!(cache.is_stale("default.glsl", 43, 1, &dir));
+    assert!(cache.is_stale("default.glsl", 42, 2, &dir));
+    assert!(cache.is_stale("unknown.glsl", 42, 1, &dir));
+
+    std::fs::write(&dependency_path, "version 2").unwrap();
+    assert!(cache.is_stale("default.glsl", 42, 1, &dir));
+
+    std::fs::remove_file(&dependency_path).unwrap();
+    assert!(cache.is_stale("default.glsl", 42, 1, &dir));
+}
+
+#[test]
+fn should_hash_stable() {
+    // these values are persisted, thus they must never change
+    assert_eq!(import_cache::hash(&[]), 0xcbf29ce484222325);
+    assert_eq!(import_cache::hash(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(import_cache::hash(b"foobar"), 0x85944171f73967e8);
+}
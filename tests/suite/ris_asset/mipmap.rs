@@ -0,0 +1,98 @@
+use ris_asset::mipmap;
+use ris_asset::mipmap::MipFilter;
+use ris_asset::mipmap::MipInfo;
+
+fn info(filter: Option<MipFilter>, srgb: bool, premultiply_alpha: bool) -> MipInfo {
+    MipInfo {
+        filter,
+        srgb,
+        premultiply_alpha,
+    }
+}
+
+#[test]
+fn should_count_levels() {
+    assert_eq!(mipmap::level_count(1, 1), 1);
+    assert_eq!(mipmap::level_count(2, 2), 2);
+    assert_eq!(mipmap::level_count(256, 256), 9);
+    assert_eq!(mipmap::level_count(5, 3), 3);
+    assert_eq!(mipmap::level_count(1, 1000), 10);
+}
+
+#[test]
+fn should_generate_full_chain() {
+    for filter in [MipFilter::Box, MipFilter::Kaiser] {
+        let pixels = vec![100; 5 * 3 * 4];
+        let levels = mipmap::generate(5, 3, &pixels, info(Some(filter), true, false)).unwrap();
+
+        let sizes = levels
+            .iter()
+            .map(|x| (x.width, x.height))
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![(5, 3), (2, 1), (1, 1)]);
+        assert_eq!(levels.len() as u32, mipmap::level_count(5, 3));
+
+        for level in levels {
+            assert_eq!(
+                level.pixels_rgba.len(),
+                (level.width * level.height * 4) as usize
+            );
+        }
+    }
+}
+
+#[test]
+fn should_keep_constant_image_constant() {
+    for filter in [MipFilter::Box, MipFilter::Kaiser] {
+        let pixels = [10, 20, 30, 40].repeat(16 * 8);
+        let levels = mipmap::generate(16, 8, &pixels, info(Some(filter), true, false)).unwrap();
+
+        for level in levels {
+            for pixel in level.pixels_rgba.chunks_exact(4) {
+                assert_eq!(pixel, &[10, 20, 30, 40], "{:?}", filter);
+            }
+        }
+    }
+}
+
+#[test]
+fn should_filter_in_linear_space() {
+    let pixels = [
+        0, 0, 0, 255, 255, 255, 255, 255, //
+        255, 255, 255, 255, 0, 0, 0, 255,
+    ];
+
+    let srgb = mipmap::generate(2, 2, &pixels, info(Some(MipFilter::Box), true, false)).unwrap();
+    let linear = mipmap::generate(2, 2, &pixels, info(Some(MipFilter::Box), false, false)).unwrap();
+
+    // 50% gray in linear space is brighter when encoded as srgb
+    assert_eq!(srgb[1].pixels_rgba, vec![188, 188, 188, 255]);
+    assert_eq!(linear[1].pixels_rgba, vec![128, 128, 128, 255]);
+}
+
+#[test]
+fn should_only_prepare_first_level_without_filter() {
+    let pixels = vec![7; 4 * 4 * 4];
+
+    let levels = mipmap::generate(4, 4, &pixels, info(None, true, false)).unwrap();
+
+    assert_eq!(levels.len(), 1);
+    assert_eq!(levels[0].pixels_rgba, pixels);
+}
+
+#[test]
+fn should_premultiply_alpha() {
+    let pixels = [255, 128, 0, 0, 255, 128, 0, 255];
+
+    let levels = mipmap::generate(2, 1, &pixels, info(None, false, true)).unwrap();
+
+    assert_eq!(levels[0].pixels_rgba, vec![0, 0, 0, 0, 255, 128, 0, 255]);
+}
+
+#[test]
+fn should_not_generate_from_invalid_image() {
+    let info = info(Some(MipFilter::Box), true, false);
+
+    assert!(mipmap::generate(0, 4, &[], info).is_err());
+    assert!(mipmap::generate(2, 2, &[0; 15], info).is_err());
+}
@@ -1,2 +1,16 @@
+pub mod asset_graph;
+pub mod asset_importer;
+pub mod asset_watcher;
+pub mod glsl_to_spirv_importer;
+pub mod gltf_to_ris_mesh_importer;
+pub mod import_cache;
+pub mod mipmap;
+pub mod obj_to_ris_mesh_importer;
+pub mod png_to_ris_texture_importer;
 pub mod qoi;
+pub mod ris_header;
+pub mod ris_mesh;
 pub mod ris_scene;
+pub mod ris_shader_variants;
+pub mod ris_texture;
+pub mod spirv_reflection;
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
+use ris_asset::assets::ris_mesh::RisMesh;
+use ris_asset::importer::obj_to_ris_mesh_importer;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+const CUBE: &str = "
+# cube with normals, one quad per face
+mtllib cube.mtl
+o cube
+v -1 -1 -1
+v 1 -1 -1
+v 1 1 -1
+v -1 1 -1
+v -1 -1 1
+v 1 -1 1
+v 1 1 1
+v -1 1 1
+vn 0 0 -1
+vn 0 0 1
+vn 0 -1 0
+vn 0 1 0
+vn -1 0 0
+vn 1 0 0
+usemtl default
+s off
+f 1//1 4//1 3//1 2//1
+f 5//2 6//2 7//2 8//2
+f 1//3 2//3 6//3 5//3
+f 4//4 8//4 7//4 3//4
+f 1//5 5//5 8//5 4//5
+f 2//6 3//6 7//6 6//6
+";
+
+fn parse(text: &str) -> RisMesh {
+    obj_to_ris_mesh_importer::parse(text, "test.obj").unwrap()
+}
+
+#[test]
+fn should_parse_triangle() {
+    let mesh = parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n");
+
+    assert_eq!(mesh.positions.len(), 3);
+    assert_eq!(mesh.positions[1], Vec3(1.0, 0.0, 0.0));
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    assert!(mesh.normals.is_empty());
+    assert!(mesh.uvs.is_empty());
+    assert!(mesh.colors.is_empty());
+}
+
+#[test]
+fn should_triangulate_and_split_vertices_with_different_normals() {
+    let mesh = parse(CUBE);
+
+    // each corner is shared by 3 faces with different normals
+    assert_eq!(mesh.positions.len(), 24);
+    assert_eq!(mesh.normals.len(), 24);
+    assert_eq!(mesh.indices.len(), 36);
+    assert_eq!(&mesh.indices[0..6], &[0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.normals[0], Vec3(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn should_share_identical_vertices() {
+    let mesh = parse("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n");
+
+    assert_eq!(mesh.positions.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+}
+
+#[test]
+fn should_parse_uvs_and_vertex_colors() {
+    let mesh = parse(
+        "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nvt 0 0\nvt 1 0\nvt 0 1\nf 1/1 2/2 3/3\n",
+    );
+
+    // obj uvs start at the bottom
+    assert_eq!(
+        mesh.uvs,
+        vec![Vec2(0.0, 1.0), Vec2(1.0, 1.0), Vec2(0.0, 0.0)]
+    );
+    assert_eq!(mesh.colors.len(), 3);
+    assert_eq!(mesh.colors[1].1, 1.0);
+}
+
+#[test]
+fn should_resolve_negative_indices() {
+    let mesh = parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n");
+
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    assert_eq!(mesh.positions[2], Vec3(0.0, 1.0, 0.0));
+}
+
+#[test]
+fn should_fail_on_invalid_input() {
+    let inputs = [
+        "v 0 0\n",
+        "v 0 0 a\n",
+        "v 0 0 0\nv 1 0 0\nf 1 2\n",
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n",
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 0 1 2\n",
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2 3\n",
+        "v 0 0 0 1 1 1\nv 1 0 0\nv 0 1 0\nf 1 2 3\n",
+    ];
+
+    for input in inputs {
+        let result = obj_to_ris_mesh_importer::parse(input, "test.obj");
+        assert!(result.is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn should_import_obj() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("source");
+    let target = dir.join("target");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::write(source.join("cube.obj"), CUBE).unwrap();
+
+    asset_importer::import_all(
+        to_str(&source),
+        to_str(&target),
+        None,
+        ImportOptions::default(),
+    )
+    .unwrap();
+
+    let bytes = std::fs::read(target.join("cube.ris_mesh")).unwrap();
+    let mesh = RisMesh::load(&bytes).unwrap();
+    assert_eq!(mesh.positions.len(), 24);
+    assert_eq!(mesh.indices.len(), 36);
+}
+
+fn to_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
@@ -0,0 +1,254 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use ris_asset::asset_importer;
+use ris_asset::asset_importer::ImportOptions;
+use ris_asset::assets::ris_texture::RisTexture;
+use ris_asset::assets::ris_texture::TextureColorSpace;
+use ris_asset::assets::ris_texture::TextureFilter;
+use ris_asset::assets::ris_texture::TextureWrap;
+use ris_asset::importer::png_to_ris_texture_importer;
+use ris_asset::importer::png_to_ris_texture_importer::TextureMeta;
+use ris_asset::mipmap::MipFilter;
+
+fn write_png(
+    path: &Path,
+    width: u32,
+    height: u32,
+    color_type: png::ColorType,
+    bit_depth: png::BitDepth,
+    palette: Option<&[u8]>,
+    data: &[u8],
+) {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(color_type);
+        encoder.set_depth(bit_depth);
+        if let Some(palette) = palette {
+            encoder.set_palette(palette.to_vec());
+        }
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(data).unwrap();
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn import(source: &Path) -> RisTexture {
+    let target = source.with_extension("ris_texture");
+    png_to_ris_texture_importer::import(source.to_path_buf(), vec![target.clone()]).unwrap();
+
+    let bytes = std::fs::read(target).unwrap();
+    RisTexture::load(&bytes).unwrap()
+}
+
+fn meta_path(source: &Path) -> PathBuf {
+    TextureMeta::path(source).unwrap()
+}
+
+#[test]
+fn should_import_rgb_with_default_settings() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("rgb.png");
+    write_png(
+        &source,
+        4,
+        2,
+        png::ColorType::Rgb,
+        png::BitDepth::Eight,
+        None,
+        &[50; 4 * 2 * 3],
+    );
+
+    let texture = import(&source);
+
+    assert_eq!(texture.color_space, TextureColorSpace::Srgb);
+    assert_eq!(texture.wrap, TextureWrap::Repeat);
+    assert_eq!(texture.filter, TextureFilter::Linear);
+    assert!(!texture.premultiplied_alpha);
+    assert_eq!(texture.mips.len(), 3);
+    assert_eq!(texture.mips[0].pixels_rgba, [50, 50, 50, 255].repeat(8));
+    assert_eq!(texture.mips[2].pixels_rgba, vec![50, 50, 50, 255]);
+}
+
+#[test]
+fn should_import_grayscale() {
+    let dir = ris_util::prep_test_dir!();
+    let gray = dir.join("gray.png");
+    let gray_alpha = dir.join("gray_alpha.png");
+    let gray_16 = dir.join("gray_16.png");
+    write_png(
+        &gray,
+        2,
+        1,
+        png::ColorType::Grayscale,
+        png::BitDepth::Eight,
+        None,
+        &[10, 20],
+    );
+    write_png(
+        &gray_alpha,
+        2,
+        1,
+        png::ColorType::GrayscaleAlpha,
+        png::BitDepth::Eight,
+        None,
+        &[10, 100, 20, 200],
+    );
+    write_png(
+        &gray_16,
+        2,
+        1,
+        png::ColorType::Grayscale,
+        png::BitDepth::Sixteen,
+        None,
+        &[10, 0xff, 20, 0x00],
+    );
+
+    let gray = import(&gray);
+    let gray_alpha = import(&gray_alpha);
+    let gray_16 = import(&gray_16);
+
+    assert_eq!(
+        gray.mips[0].pixels_rgba,
+        vec![10, 10, 10, 255, 20, 20, 20, 255]
+    );
+    assert_eq!(
+        gray_alpha.mips[0].pixels_rgba,
+        vec![10, 10, 10, 100, 20, 20, 20, 200]
+    );
+    assert_eq!(
+        gray_16.mips[0].pixels_rgba,
+        vec![10, 10, 10, 255, 20, 20, 20, 255]
+    );
+}
+
+#[test]
+fn should_import_palette() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("palette.png");
+    let palette = [255, 0, 0, 0, 255, 0, 0, 0, 255];
+    // 2 bits per pixel: 0, 1, 2, 1
+    write_png(
+        &source,
+        4,
+        1,
+        png::ColorType::Indexed,
+        png::BitDepth::Two,
+        Some(&palette),
+        &[0b00_01_10_01],
+    );
+
+    let texture = import(&source);
+
+    assert_eq!(
+        texture.mips[0].pixels_rgba,
+        vec![255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 255, 0, 255],
+    );
+}
+
+#[test]
+fn should_apply_sidecar() {
+    let dir = ris_util::prep_test_dir!();
+    let source = dir.join("image.png");
+    write_png(
+        &source,
+        2,
+        2,
+        png::ColorType::Rgba,
+        png::BitDepth::Eight,
+        None,
+        &[255, 255, 255, 0].repeat(4),
+    );
+    std::fs::write(
+        meta_path(&source),
+        "color_space: linear\nwrap: clamp_to_edge # comment\nfilter: nearest\nmips: none\npremultiply_alpha: true\n",
+    )
+    .unwrap();
+
+    let texture = import(&source);
+
+    assert_eq!(texture.color_space, TextureColorSpace::Linear);
+    assert_eq!(texture.wrap, TextureWrap::ClampToEdge);
+    assert_eq!(texture.filter, TextureFilter::Nearest);
+    assert!(texture.premultiplied_alpha);
+    assert_eq!(texture.mips.len(), 1);
+    assert_eq!(texture.mips[0].pixels_rgba, vec![0; 16]);
+}
+
+#[test]
+fn should_serialize_and_deserialize_sidecar() {
+    let meta = TextureMeta {
+        color_space: TextureColorSpace::Linear,
+        wrap: TextureWrap::MirroredRepeat,
+        filter: TextureFilter::Nearest,
+        mips: Some(MipFilter::Kaiser),
+        premultiply_alpha: true,
+    };
+
+    let text = meta.serialize().unwrap();
+    let deserialized = TextureMeta::deserialize(&text).unwrap();
+
+    assert_eq!(meta, deserialized);
+    assert_eq!(
+        TextureMeta::deserialize("").unwrap(),
+        TextureMeta::default()
+    );
+}
+
+#[test]
+fn should_not_deserialize_invalid_sidecar() {
+    let inputs = [
+        "color_space: rgb",
+        "mips: lanczos",
+        "premultiply_alpha: yes",
+        "unknown: true",
+    ];
+
+    for input in inputs {
+        assert!(TextureMeta::deserialize(input).is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn should_import_again_when_sidecar_is_created() {
+    let dir = ris_util::prep_test_dir!();
+    let source_directory = dir.join("source");
+    let target_directory = dir.join("target");
+    let source = source_directory.join("image.png");
+    write_png(
+        &source,
+        2,
+        2,
+        png::ColorType::Rgb,
+        png::BitDepth::Eight,
+        None,
+        &[0; 12],
+    );
+
+    let import_all = || {
+        asset_importer::import_all(
+            source_directory.to_str().unwrap(),
+            target_directory.to_str().unwrap(),
+            None,
+            ImportOptions::default(),
+        )
+        .unwrap();
+
+        let bytes = std::fs::read(target_directory.join("image.ris_texture")).unwrap();
+        RisTexture::load(&bytes).unwrap()
+    };
+
+    let texture = import_all();
+    assert_eq!(texture.filter, TextureFilter::Linear);
+
+    std::fs::write(meta_path(&source), "filter: nearest\n").unwrap();
+    let texture = import_all();
+    assert_eq!(texture.filter, TextureFilter::Nearest);
+
+    std::fs::write(meta_path(&source), "filter: linear\n").unwrap();
+    let texture = import_all();
+    assert_eq!(texture.filter, TextureFilter::Linear);
+}
@@ -1,9 +1,12 @@
 use ris_asset::codecs::qoi;
 use ris_asset::codecs::qoi::Channels;
 use ris_asset::codecs::qoi::ColorSpace;
+use ris_asset::codecs::qoi::DecodeError;
 use ris_asset::codecs::qoi::DecodeErrorKind;
 use ris_asset::codecs::qoi::EncodeErrorKind;
+use ris_asset::codecs::qoi::QoiDecoder;
 use ris_asset::codecs::qoi::QoiDesc;
+use ris_asset::codecs::qoi::QoiEncoder;
 use ris_rng::rng::Rng;
 use ris_rng::rng::Seed;
 use ris_util::testing;
@@ -37,9 +40,8 @@ fn should_encode_and_decode_fuzzed() {
     });
 }
 
-#[test]
 #[cfg(not(miri))]
-fn should_encode_and_decode_raw_assets() {
+fn raw_assets() -> Vec<(Vec<u8>, QoiDesc)> {
     let executable_string = std::env::args().next().expect("no cli args");
     let executable_path = std::path::PathBuf::from(executable_string);
     let executable_directory = executable_path.parent().expect("executable has no parent");
@@ -79,6 +81,7 @@ fn should_encode_and_decode_raw_assets() {
         }
     }
 
+    let mut result = Vec::new();
     for png in pngs {
         // decode png
         let decoder = png::Decoder::new(std::fs::File::open(png).unwrap());
@@ -86,7 +89,6 @@ fn should_encode_and_decode_raw_assets() {
         let mut original = vec![0; reader.output_buffer_size()];
         let info = reader.next_frame(&mut original).unwrap();
 
-        // encode/decode qoi
         let width = info.width;
         let height = info.height;
         let channels = match info.color_type {
@@ -103,6 +105,16 @@ fn should_encode_and_decode_raw_assets() {
             color_space,
         };
 
+        result.push((original, desc));
+    }
+
+    result
+}
+
+#[test]
+#[cfg(not(miri))]
+fn should_encode_and_decode_raw_assets() {
+    for (original, desc) in raw_assets() {
         let encoded = qoi::encode(&original, desc).unwrap();
         let (copy, copy_desc) = qoi::decode(&encoded, None).unwrap();
 
@@ -111,6 +123,232 @@ fn should_encode_and_decode_raw_assets() {
     }
 }
 
+fn encode_streamed(data: &[u8], desc: QoiDesc, rng: &mut Rng) -> Vec<u8> {
+    let mut encoder = QoiEncoder::new(Vec::new(), desc).unwrap();
+
+    let mut data = data;
+    while !data.is_empty() {
+        let chunk_len = usize::min(rng.next_i32_between(0, 64) as usize, data.len());
+        encoder.write_pixels(&data[..chunk_len]).unwrap();
+        data = &data[chunk_len..];
+    }
+
+    encoder.finish().unwrap()
+}
+
+fn decode_streamed(encoded: &[u8], channels: Option<Channels>, rng: &mut Rng) -> Vec<u8> {
+    let mut decoder = QoiDecoder::new(encoded, channels).unwrap();
+    let mut decoded = vec![0; decoder.bytes_left()];
+
+    let mut written = 0;
+    while written < decoded.len() {
+        let chunk_len = usize::min(
+            rng.next_i32_between(1, 64) as usize,
+            decoded.len() - written,
+        );
+        let read = decoder
+            .read_pixels(&mut decoded[written..written + chunk_len])
+            .unwrap();
+        assert_eq!(read, chunk_len);
+        written += read;
+    }
+
+    assert_eq!(decoder.read_pixels(&mut [0; 4]).unwrap(), 0);
+    let rest = decoder.finish().unwrap();
+    assert!(rest.is_empty());
+
+    decoded
+}
+
+fn decode_rows(encoded: &[u8], channels: Option<Channels>) -> Vec<u8> {
+    let mut decoder = QoiDecoder::new(encoded, channels).unwrap();
+    let row_len = decoder.row_len();
+
+    let mut decoded = Vec::new();
+    let mut expected_y = 0;
+    decoder
+        .decode_rows(|y, row| {
+            assert_eq!(y, expected_y);
+            assert_eq!(row.len(), row_len);
+            expected_y += 1;
+            decoded.extend_from_slice(row);
+            Ok::<_, DecodeError>(())
+        })
+        .unwrap();
+
+    assert_eq!(expected_y, decoder.desc().height);
+    decoded
+}
+
+fn assert_streaming_matches(data: &[u8], desc: QoiDesc, rng: &mut Rng) {
+    let encoded = qoi::encode(data, desc).unwrap();
+    let streamed = encode_streamed(data, desc, rng);
+    ris_util::assert_bytes_eq!(&encoded, &streamed);
+
+    for channels in [None, Some(Channels::RGB), Some(Channels::RGBA)] {
+        let (decoded, decoded_desc) = qoi::decode(&encoded, channels).unwrap();
+
+        let streamed = decode_streamed(&encoded, channels, rng);
+        ris_util::assert_bytes_eq!(&decoded, &streamed);
+
+        let rows = decode_rows(&encoded, channels);
+        ris_util::assert_bytes_eq!(&decoded, &rows);
+
+        let mut buf = vec![0; decoded.len()];
+        let buf_desc = qoi::decode_into(&encoded, channels, &mut buf).unwrap();
+        assert_eq!(decoded_desc, buf_desc);
+        ris_util::assert_bytes_eq!(&decoded, &buf);
+    }
+}
+
+#[test]
+fn should_stream_like_encode_and_decode_fuzzed() {
+    let rng = std::rc::Rc::new(std::cell::RefCell::new(Rng::new(Seed::new().unwrap())));
+    testing::repeat(10, move |_| {
+        let mut rng = rng.borrow_mut();
+
+        let width = rng.next_i32_between(1, miri_choose(200, 10)) as u32;
+        let height = rng.next_i32_between(1, miri_choose(200, 10)) as u32;
+        let channels = Channels::try_from(rng.next_i32_between(3, 4) as u8).unwrap();
+        let color_space = ColorSpace::try_from(rng.next_i32_between(0, 1) as u8).unwrap();
+
+        // repeat and slightly vary pixels, such that all ops are used
+        let channel_count = channels as usize;
+        let mut data = rng.next_bytes(width as usize * height as usize * channel_count);
+        for i in (channel_count..data.len()).step_by(channel_count) {
+            match rng.next_i32_between(0, 3) {
+                0 => data.copy_within(i - channel_count..i, i),
+                1 => {
+                    for j in i..i + channel_count {
+                        let delta = rng.next_i32_between(-8, 8) as u8;
+                        data[j] = data[j - channel_count].wrapping_add(delta);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let desc = QoiDesc {
+            width,
+            height,
+            channels,
+            color_space,
+        };
+
+        assert_streaming_matches(&data, desc, &mut rng);
+    });
+}
+
+#[test]
+#[cfg(not(miri))]
+fn should_stream_like_encode_and_decode_raw_assets() {
+    let mut rng = Rng::new(Seed::new().unwrap());
+    for (original, desc) in raw_assets() {
+        assert_streaming_matches(&original, desc, &mut rng);
+    }
+}
+
+#[test]
+fn should_implement_read_and_write() {
+    let desc = QoiDesc {
+        width: 3,
+        height: 2,
+        channels: Channels::RGBA,
+        color_space: ColorSpace::Linear,
+    };
+    let data = [
+        1, 2, 3, 4, 1, 2, 3, 4, 5, 6, 7, 8, 255, 0, 0, 255, 5, 6, 7, 8, 9, 9, 9, 9,
+    ];
+
+    let mut encoder = QoiEncoder::new(Vec::new(), desc).unwrap();
+    std::io::Write::write_all(&mut encoder, &data).unwrap();
+    let encoded = encoder.finish().unwrap();
+    ris_util::assert_bytes_eq!(&encoded, &qoi::encode(&data, desc).unwrap());
+
+    let mut decoder = QoiDecoder::new(encoded.as_slice(), None).unwrap();
+    let mut decoded = Vec::new();
+    std::io::Read::read_to_end(&mut decoder, &mut decoded).unwrap();
+    ris_util::assert_bytes_eq!(&data[..], &decoded);
+}
+
+#[test]
+fn should_not_finish_encoder_when_pixels_are_missing() {
+    let desc = QoiDesc {
+        width: 2,
+        height: 2,
+        channels: Channels::RGB,
+        color_space: ColorSpace::SRGB,
+    };
+
+    let mut encoder = QoiEncoder::new(Vec::new(), desc).unwrap();
+    encoder.write_pixels(&[1, 2, 3, 4, 5]).unwrap();
+    let error = encoder.finish().unwrap_err();
+
+    assert!(matches!(
+        error.kind,
+        EncodeErrorKind::DataDoesNotMatchDimensions
+    ));
+}
+
+#[test]
+fn should_not_write_more_pixels_than_dimensions() {
+    let desc = QoiDesc {
+        width: 1,
+        height: 1,
+        channels: Channels::RGB,
+        color_space: ColorSpace::SRGB,
+    };
+
+    let mut encoder = QoiEncoder::new(Vec::new(), desc).unwrap();
+    encoder.write_pixels(&[1, 2]).unwrap();
+    let error = encoder.write_pixels(&[3, 4]).unwrap_err();
+
+    assert!(matches!(
+        error.kind,
+        EncodeErrorKind::DataDoesNotMatchDimensions
+    ));
+}
+
+#[test]
+fn should_not_decode_truncated_stream() {
+    let desc = QoiDesc {
+        width: 4,
+        height: 4,
+        channels: Channels::RGB,
+        color_space: ColorSpace::SRGB,
+    };
+    let data = Rng::new(Seed::new().unwrap()).next_bytes(4 * 4 * 3);
+    let encoded = qoi::encode(&data, desc).unwrap();
+    let truncated = &encoded[..encoded.len() - 20];
+
+    let mut decoder = QoiDecoder::new(truncated, None).unwrap();
+    let mut buf = vec![0; 4 * 4 * 3];
+    let error = decoder.read_pixels(&mut buf).unwrap_err();
+
+    assert!(matches!(error.kind, DecodeErrorKind::IoError(_)));
+}
+
+#[test]
+fn should_not_decode_into_buffer_of_wrong_size() {
+    let desc = QoiDesc {
+        width: 2,
+        height: 2,
+        channels: Channels::RGBA,
+        color_space: ColorSpace::SRGB,
+    };
+    let encoded = qoi::encode(&[0; 16], desc).unwrap();
+
+    let mut buf = [0; 12];
+    let error = qoi::decode_into(&encoded, None, &mut buf).unwrap_err();
+    assert!(matches!(
+        error.kind,
+        DecodeErrorKind::BufferDoesNotMatchDimensions
+    ));
+
+    let mut buf = [0; 12];
+    qoi::decode_into(&encoded, Some(Channels::RGB), &mut buf).unwrap();
+}
+
 #[test]
 fn should_not_encode_when_width_is_zero() {
     let desc = QoiDesc {
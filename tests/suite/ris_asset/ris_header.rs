@@ -0,0 +1,69 @@
+use std::io::Cursor;
+
+use ris_asset::assets::ris_scene;
+use ris_asset::RisHeader;
+use ris_data::asset_id::AssetId;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+use ris_util::testing::miri_choose;
+
+#[test]
+fn should_serialize_and_load() {
+    let references = vec![
+        AssetId::Path(String::from("a.ris_mesh")),
+        AssetId::Path(String::from("b/c.ris_texture")),
+    ];
+    let header = RisHeader::new(ris_scene::MAGIC, references.clone());
+    let mut bytes = header.serialize().unwrap();
+    bytes.extend([1, 2, 3]);
+
+    let loaded = RisHeader::load(&bytes).unwrap().unwrap();
+    loaded.assert_magic(ris_scene::MAGIC).unwrap();
+    assert_eq!(loaded.references, references);
+    assert_eq!(loaded.content(&bytes).unwrap(), &[1, 2, 3]);
+}
+
+#[test]
+fn should_not_load_reference_count_exceeding_the_bytes() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write(&mut stream, &ris_scene::MAGIC).unwrap();
+    ris_io::write_bool(&mut stream, true).unwrap();
+    ris_io::write_uint(&mut stream, u32::MAX as usize).unwrap();
+    ris_io::write_uint(&mut stream, 42).unwrap();
+
+    let bytes = stream.into_inner();
+    assert!(RisHeader::load(&bytes).is_err());
+}
+
+#[test]
+fn should_not_load_string_exceeding_the_bytes() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write(&mut stream, &ris_scene::MAGIC).unwrap();
+    ris_io::write_bool(&mut stream, false).unwrap();
+    ris_io::write_uint(&mut stream, 1).unwrap();
+    ris_io::write_uint(&mut stream, 1_000_000).unwrap();
+    ris_io::write(&mut stream, b"abc").unwrap();
+
+    let bytes = stream.into_inner();
+    assert!(RisHeader::load(&bytes).is_err());
+}
+
+#[test]
+fn should_not_panic_when_loading_random_bytes() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..miri_choose(10_000, 100) {
+        let len = rng.next_i32_between(0, 100) as usize;
+        let mut bytes = rng.next_bytes(len);
+        let _ = RisHeader::load(&bytes);
+
+        // a valid magic and a small is_compiled gets past the first checks
+        bytes.splice(0..0, ris_scene::MAGIC);
+        bytes.insert(16, rng.next_u8() & 1);
+        if let Ok(Some(header)) = RisHeader::load(&bytes) {
+            assert!(header.content(&bytes).is_ok());
+        }
+    }
+}
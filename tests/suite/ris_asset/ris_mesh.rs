@@ -0,0 +1,96 @@
+use ris_asset::assets::ris_mesh;
+use ris_asset::assets::ris_mesh::RisMesh;
+use ris_math::color::Rgb;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+
+fn triangle() -> RisMesh {
+    RisMesh {
+        positions: vec![
+            Vec3(0.0, 0.0, 0.0),
+            Vec3(1.0, 0.0, 0.0),
+            Vec3(0.0, 1.0, 0.0),
+        ],
+        normals: vec![Vec3(0.0, 0.0, 1.0); 3],
+        uvs: vec![Vec2(0.0, 0.0), Vec2(1.0, 0.0), Vec2(0.0, 1.0)],
+        colors: vec![Rgb(1.0, 0.0, 0.0), Rgb(0.0, 1.0, 0.0), Rgb(0.0, 0.0, 1.0)],
+        indices: vec![0, 1, 2],
+    }
+}
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let mesh = triangle();
+
+    let bytes = mesh.serialize().unwrap();
+    let deserialized = RisMesh::load(&bytes).unwrap();
+
+    assert_eq!(format!("{:?}", mesh), format!("{:?}", deserialized));
+}
+
+#[test]
+fn should_serialize_mesh_without_optional_attributes() {
+    let mut mesh = triangle();
+    mesh.normals.clear();
+    mesh.uvs.clear();
+    mesh.colors.clear();
+
+    let bytes = mesh.serialize().unwrap();
+    let deserialized = RisMesh::load(&bytes).unwrap();
+
+    assert_eq!(format!("{:?}", mesh), format!("{:?}", deserialized));
+}
+
+#[test]
+fn should_load_into_mesh() {
+    let bytes = triangle().serialize().unwrap();
+
+    let mesh = ris_mesh::load(&bytes).unwrap();
+
+    assert_eq!(mesh.vertices.len(), 3);
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    assert_eq!(mesh.vertices[1].pos, Vec3(1.0, 0.0, 0.0));
+    assert_eq!(mesh.vertices[2].uv, Vec2(0.0, 1.0));
+    assert_eq!(mesh.vertices[0].color.0, 1.0);
+    assert_eq!(mesh.vertices[0].color.1, 0.0);
+}
+
+#[test]
+fn should_default_missing_attributes_when_loading_into_mesh() {
+    let mut ris_mesh = triangle();
+    ris_mesh.uvs.clear();
+    ris_mesh.colors.clear();
+
+    let mesh = ris_mesh.into_mesh();
+
+    for vertex in mesh.vertices {
+        assert_eq!(vertex.uv, Vec2(0.0, 0.0));
+        assert_eq!(vertex.color.0, 1.0);
+        assert_eq!(vertex.color.1, 1.0);
+        assert_eq!(vertex.color.2, 1.0);
+    }
+}
+
+#[test]
+fn should_not_serialize_invalid_mesh() {
+    let mut mesh = triangle();
+    mesh.indices.push(3);
+    assert!(mesh.serialize().is_err());
+
+    let mut mesh = triangle();
+    mesh.indices.pop();
+    assert!(mesh.serialize().is_err());
+
+    let mut mesh = triangle();
+    mesh.normals.pop();
+    assert!(mesh.serialize().is_err());
+}
+
+#[test]
+fn should_not_load_other_assets() {
+    let header = ris_asset::RisHeader::new(ris_asset::assets::ris_scene::MAGIC, Vec::new());
+    let bytes = header.serialize().unwrap();
+
+    assert!(RisMesh::load(&bytes).is_err());
+    assert!(RisMesh::load(&[]).is_err());
+}
@@ -1,6 +1,7 @@
 use std::any::TypeId;
 
 use ris_asset::assets::ris_scene;
+use ris_asset::RisHeader;
 use ris_data::asset_id::AssetId;
 use ris_data::ecs::components::script::DynScriptComponent;
 use ris_data::ecs::decl::GameObjectHandle;
@@ -13,6 +14,7 @@ use ris_data::ecs::script_prelude::*;
 use ris_error::RisResult;
 use ris_rng::rng::Rng;
 use ris_rng::rng::Seed;
+use ris_util::testing::miri_choose;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Call {
@@ -78,7 +80,7 @@ impl Script for TestScript {
 
     fn deserialize(&mut self, stream: &mut SceneReader) -> RisResult<()> {
         self.inner.calls.push(Call::Deserialize);
-        let payload_len = ris_io::read_uint(stream)?;
+        let payload_len = stream.read_len(1)?;
         self.inner.payload = vec![0; payload_len];
         ris_io::read(stream, &mut self.inner.payload)?;
         self.inner.game_object = stream.read_game_object()?;
@@ -345,3 +347,103 @@ fn get_inner(scene: &Scene, handle: DynComponentHandle) -> RisResult<TestInner>
 
     Ok(inner)
 }
+
+#[test]
+fn should_not_panic_when_loading_random_bytes() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let mut rng = Rng::new(seed);
+
+    let scene = fuzz_scene();
+
+    for _ in 0..miri_choose(1_000, 10) {
+        let len = rng.next_i32_between(0, 200) as usize;
+        let bytes = rng.next_bytes(len);
+        assert_loads_or_fails(&scene, &bytes);
+
+        // a valid magic gets past the first check
+        let mut bytes = rng.next_bytes(len);
+        bytes.splice(0..0, ris_scene::MAGIC);
+        assert_loads_or_fails(&scene, &bytes);
+    }
+}
+
+#[test]
+fn should_not_panic_when_loading_corrupted_scene() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let mut rng = Rng::new(seed);
+
+    let scene = fuzz_scene();
+    let serialized = ris_scene::serialize(&scene, Some(0)).unwrap();
+    let header = RisHeader::load(&serialized).unwrap().unwrap();
+    let header_bytes = header.serialize().unwrap();
+    let content = header.content(&serialized).unwrap();
+    let uncompressed = miniz_oxide::inflate::decompress_to_vec(content).unwrap();
+
+    // the uncorrupted scene loads
+    assert_loads_or_fails(&scene, &serialized);
+
+    for _ in 0..miri_choose(1_000, 10) {
+        let mut corrupted = uncompressed.clone();
+        let corruption_count = rng.next_i32_between(1, 4);
+        for _ in 0..corruption_count {
+            let index = rng.next_i32_between(0, corrupted.len() as i32 - 1) as usize;
+            corrupted[index] = rng.next_u8();
+        }
+
+        if rng.next_bool() {
+            let len = rng.next_i32_between(0, corrupted.len() as i32) as usize;
+            corrupted.truncate(len);
+        }
+
+        let compressed =
+            miniz_oxide::deflate::compress_to_vec(&corrupted, ris_scene::COMPRESSION_LEVEL);
+        let mut bytes = header_bytes.clone();
+        bytes.extend(compressed);
+        assert_loads_or_fails(&scene, &bytes);
+
+        // corrupt the compressed bytes directly
+        let mut bytes = serialized.clone();
+        let index = rng.next_i32_between(0, bytes.len() as i32 - 1) as usize;
+        bytes[index] = rng.next_u8();
+        assert_loads_or_fails(&scene, &bytes);
+    }
+}
+
+fn fuzz_scene() -> Scene {
+    let registry = Registry::new(vec![Registry::script::<TestScript>().unwrap()]).unwrap();
+    let scene_create_info = SceneCreateInfo {
+        static_chunks: 2,
+        game_objects_per_static_chunk: 8,
+        registry: Some(registry),
+        ..Default::default()
+    };
+    let scene = Scene::new(scene_create_info).unwrap();
+    assert_eq!(scene.reserve_chunk().unwrap(), 0);
+
+    let mut rng = Rng::new(Seed([0; 16]));
+    let g0 = GameObjectHandle::new_static(&scene, 0).unwrap();
+    let g1 = GameObjectHandle::new_static(&scene, 0).unwrap();
+    let g2 = GameObjectHandle::new_static(&scene, 0).unwrap();
+    g1.set_parent(&scene, Some(g0), 0, false).unwrap();
+    g2.set_parent(&scene, Some(g0), 1, false).unwrap();
+
+    let gs = [g0, g1, g2];
+    fill_data(&scene, g0, &mut rng, "zero", &gs).unwrap();
+    fill_data(&scene, g1, &mut rng, "one", &gs).unwrap();
+    fill_data(&scene, g2, &mut rng, "two", &gs).unwrap();
+
+    scene
+}
+
+fn assert_loads_or_fails(scene: &Scene, bytes: &[u8]) {
+    // failing to load must release the chunk, otherwise it cannot be reserved again
+    if let Ok(Some(index)) = ris_scene::load(scene, bytes) {
+        scene.clear_chunk(index);
+    }
+
+    let index = scene.reserve_chunk().unwrap();
+    assert_eq!(index, 1);
+    scene.clear_chunk(index);
+}
@@ -0,0 +1,71 @@
+use ris_asset::assets::ris_shader_variants::RisShaderVariants;
+use ris_asset::assets::ris_shader_variants::ShaderVariant;
+use ris_data::asset_id::AssetId;
+
+fn variants() -> RisShaderVariants {
+    let variant = |name: &str, geom: bool| ShaderVariant {
+        vert: Some(AssetId::Path(format!("shaders/{}.vert.spv", name))),
+        geom: geom.then(|| AssetId::Path(format!("shaders/{}.geom.spv", name))),
+        frag: Some(AssetId::Path(format!("shaders/{}.frag.spv", name))),
+    };
+
+    RisShaderVariants {
+        keywords: vec![String::from("TEXTURED"), String::from("VERTEX_COLOR")],
+        variants: vec![
+            variant("default", false),
+            variant("default.textured", true),
+            variant("default.vertex_color", false),
+            variant("default.textured.vertex_color", true),
+        ],
+    }
+}
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let variants = variants();
+
+    let bytes = variants.serialize().unwrap();
+    let deserialized = RisShaderVariants::load(&bytes).unwrap();
+
+    assert_eq!(variants, deserialized);
+}
+
+#[test]
+fn should_get_variant_by_keywords() {
+    let variants = variants();
+
+    let none = variants.get(&[]).unwrap();
+    let textured = variants.get(&["TEXTURED"]).unwrap();
+    let both = variants.get(&["VERTEX_COLOR", "TEXTURED"]).unwrap();
+
+    assert_eq!(
+        none.vert,
+        Some(AssetId::Path(String::from("shaders/default.vert.spv")))
+    );
+    assert_eq!(
+        textured.geom,
+        Some(AssetId::Path(String::from(
+            "shaders/default.textured.geom.spv"
+        )))
+    );
+    assert_eq!(
+        both.frag,
+        Some(AssetId::Path(String::from(
+            "shaders/default.textured.vertex_color.frag.spv"
+        )))
+    );
+    assert_eq!(variants.mask(&["VERTEX_COLOR"]).unwrap(), 2);
+}
+
+#[test]
+fn should_not_get_unknown_keyword() {
+    let variants = variants();
+    assert!(variants.get(&["UNKNOWN"]).is_err());
+}
+
+#[test]
+fn should_not_serialize_when_variant_count_does_not_match() {
+    let mut variants = variants();
+    variants.variants.pop();
+    assert!(variants.serialize().is_err());
+}
@@ -0,0 +1,73 @@
+use ris_asset::assets::ris_texture::RisTexture;
+use ris_asset::assets::ris_texture::TextureColorSpace;
+use ris_asset::assets::ris_texture::TextureFilter;
+use ris_asset::assets::ris_texture::TextureWrap;
+use ris_asset::mipmap;
+use ris_asset::mipmap::MipFilter;
+use ris_asset::mipmap::MipInfo;
+use ris_asset::mipmap::MipLevel;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+
+fn texture() -> RisTexture {
+    let mut rng = Rng::new(Seed::new().unwrap());
+    let pixels = rng.next_bytes(8 * 4 * 4);
+
+    let info = MipInfo {
+        filter: Some(MipFilter::Kaiser),
+        srgb: false,
+        premultiply_alpha: false,
+    };
+    let mips = mipmap::generate(8, 4, &pixels, info).unwrap();
+
+    RisTexture {
+        color_space: TextureColorSpace::Linear,
+        wrap: TextureWrap::ClampToEdge,
+        filter: TextureFilter::Nearest,
+        premultiplied_alpha: true,
+        mips,
+    }
+}
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let texture = texture();
+
+    let bytes = texture.serialize().unwrap();
+    let deserialized = RisTexture::load(&bytes).unwrap();
+
+    assert_eq!(texture, deserialized);
+    assert_eq!(deserialized.width(), 8);
+    assert_eq!(deserialized.height(), 4);
+    assert_eq!(deserialized.mips.len(), 4);
+}
+
+#[test]
+fn should_not_serialize_texture_without_mips() {
+    let texture = RisTexture::default();
+
+    assert!(texture.serialize().is_err());
+}
+
+#[test]
+fn should_not_load_mips_with_invalid_size() {
+    let mut texture = texture();
+    texture.mips[1] = MipLevel {
+        width: 3,
+        height: 2,
+        pixels_rgba: vec![0; 3 * 2 * 4],
+    };
+
+    let bytes = texture.serialize().unwrap();
+
+    assert!(RisTexture::load(&bytes).is_err());
+}
+
+#[test]
+fn should_not_load_truncated_texture() {
+    let bytes = texture().serialize().unwrap();
+
+    for len in [0, 16, 30, bytes.len() - 1] {
+        assert!(RisTexture::load(&bytes[..len]).is_err(), "{}", len);
+    }
+}
@@ -0,0 +1,332 @@
+use std::path::PathBuf;
+
+use ash::vk;
+
+use ris_asset::importer::glsl_to_spirv_importer;
+use ris_asset::spirv_reflection;
+use ris_asset::spirv_reflection::DescriptorBinding;
+use ris_asset::spirv_reflection::DescriptorKind;
+use ris_asset::spirv_reflection::Format;
+use ris_asset::spirv_reflection::InterfaceVariable;
+use ris_asset::spirv_reflection::PushConstantBlock;
+use ris_asset::spirv_reflection::ScalarKind;
+use ris_asset::spirv_reflection::ShaderReflection;
+use ris_asset::spirv_reflection::ShaderStage;
+use ris_data::ecs::mesh::VERTEX_ATTRIBUTE_DESCRIPTIONS;
+use ris_video_renderers::scene::scene_renderer::PushConstants;
+use ris_video_renderers::scene::scene_renderer::UniformBufferObject;
+
+// a hand assembled module, which is equivalent to:
+//
+// layout(push_constant) uniform PushConstants { mat4 model; } pc;
+// layout(set = 0, binding = 0) uniform UniformBufferObject { mat4 view; mat4 proj; } ubo;
+// layout(binding = 1) uniform sampler2D tex_sampler;
+// layout(set = 1, binding = 2) uniform texture2D textures[4];
+// layout(location = 1) in vec2 in_uv;
+// layout(location = 0) in vec3 in_position;
+// layout(location = 0) out vec4 out_color;
+// out gl_PerVertex { vec4 gl_Position; };
+fn test_module() -> Vec<u8> {
+    fn op(opcode: u32, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | opcode];
+        words.extend_from_slice(operands);
+        words
+    }
+
+    fn op_name(opcode: u32, operands: &[u32], name: &str) -> Vec<u32> {
+        let mut bytes = name.as_bytes().to_vec();
+        bytes.resize((bytes.len() / 4 + 1) * 4, 0);
+
+        let mut operands = operands.to_vec();
+        operands.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]])),
+        );
+        op(opcode, &operands)
+    }
+
+    let instructions = [
+        op_name(15, &[0, 1], "main"),
+        op_name(5, &[10], "PushConstants"),
+        op_name(5, &[11], "pc"),
+        op_name(5, &[20], "UniformBufferObject"),
+        op_name(5, &[21], "ubo"),
+        op_name(5, &[31], "tex_sampler"),
+        op_name(5, &[35], "textures"),
+        op_name(5, &[41], "in_uv"),
+        op_name(5, &[42], "in_position"),
+        op_name(5, &[43], "out_color"),
+        // decorations
+        op(72, &[9, 0, 35, 0]),
+        op(72, &[9, 0, 7, 16]),
+        op(71, &[9, 2]),
+        op(72, &[19, 0, 35, 0]),
+        op(72, &[19, 0, 7, 16]),
+        op(72, &[19, 1, 35, 64]),
+        op(72, &[19, 1, 7, 16]),
+        op(71, &[19, 2]),
+        op(71, &[21, 34, 0]),
+        op(71, &[21, 33, 0]),
+        op(71, &[31, 33, 1]),
+        op(71, &[35, 34, 1]),
+        op(71, &[35, 33, 2]),
+        op(71, &[41, 30, 1]),
+        op(71, &[42, 30, 0]),
+        op(71, &[43, 30, 0]),
+        op(72, &[50, 0, 11, 0]),
+        op(71, &[50, 2]),
+        // types
+        op(22, &[2, 32]),
+        op(23, &[3, 2, 2]),
+        op(23, &[4, 2, 3]),
+        op(23, &[5, 2, 4]),
+        op(24, &[6, 5, 4]),
+        op(21, &[7, 32, 0]),
+        op(43, &[7, 8, 4]),
+        op(30, &[9, 6]),
+        op(32, &[10, 9, 9]),
+        op(59, &[10, 11, 9]),
+        op(30, &[19, 6, 6]),
+        op(32, &[20, 2, 19]),
+        op(59, &[20, 21, 2]),
+        op(25, &[28, 2, 1, 0, 0, 0, 1, 0]),
+        op(27, &[29, 28]),
+        op(32, &[30, 0, 29]),
+        op(59, &[30, 31, 0]),
+        op(28, &[33, 28, 8]),
+        op(32, &[34, 0, 33]),
+        op(59, &[34, 35, 0]),
+        op(32, &[40, 1, 3]),
+        op(59, &[40, 41, 1]),
+        op(32, &[44, 1, 4]),
+        op(59, &[44, 42, 1]),
+        op(32, &[45, 3, 5]),
+        op(59, &[45, 43, 3]),
+        op(30, &[50, 5]),
+        op(32, &[51, 3, 50]),
+        op(59, &[51, 52, 3]),
+    ];
+
+    let mut words = vec![0x0723_0203, 0x0001_0000, 0, 60, 0];
+    for instruction in instructions {
+        words.extend(instruction);
+    }
+
+    words.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn f32_format(components: u32, columns: u32) -> Format {
+    Format {
+        scalar: ScalarKind::Float,
+        bits: 32,
+        components,
+        columns,
+    }
+}
+
+#[test]
+fn should_reflect() {
+    let reflection = spirv_reflection::reflect(&test_module()).unwrap();
+
+    let expected = ShaderReflection {
+        stage: ShaderStage::Vertex,
+        inputs: vec![
+            InterfaceVariable {
+                location: 0,
+                format: f32_format(3, 1),
+                name: String::from("in_position"),
+            },
+            InterfaceVariable {
+                location: 1,
+                format: f32_format(2, 1),
+                name: String::from("in_uv"),
+            },
+        ],
+        outputs: vec![InterfaceVariable {
+            location: 0,
+            format: f32_format(4, 1),
+            name: String::from("out_color"),
+        }],
+        descriptor_bindings: vec![
+            DescriptorBinding {
+                set: 0,
+                binding: 0,
+                kind: DescriptorKind::UniformBuffer,
+                count: 1,
+                size: 128,
+                name: String::from("ubo"),
+            },
+            DescriptorBinding {
+                set: 0,
+                binding: 1,
+                kind: DescriptorKind::CombinedImageSampler,
+                count: 1,
+                size: 0,
+                name: String::from("tex_sampler"),
+            },
+            DescriptorBinding {
+                set: 1,
+                binding: 2,
+                kind: DescriptorKind::SampledImage,
+                count: 4,
+                size: 0,
+                name: String::from("textures"),
+            },
+        ],
+        push_constants: Some(PushConstantBlock {
+            size: 64,
+            name: String::from("pc"),
+        }),
+    };
+
+    assert_eq!(reflection, expected);
+}
+
+#[test]
+fn should_reflect_big_endian() {
+    let bytes = test_module()
+        .chunks_exact(4)
+        .flat_map(|x| [x[3], x[2], x[1], x[0]])
+        .collect::<Vec<_>>();
+
+    let little_endian = spirv_reflection::reflect(&test_module()).unwrap();
+    let big_endian = spirv_reflection::reflect(&bytes).unwrap();
+    assert_eq!(little_endian, big_endian);
+}
+
+#[test]
+fn should_not_reflect_invalid_module() {
+    let mut bytes = test_module();
+    assert!(spirv_reflection::reflect(&bytes[..bytes.len() - 1]).is_err());
+    assert!(spirv_reflection::reflect(&bytes[..12]).is_err());
+
+    bytes[0] = 0;
+    assert!(spirv_reflection::reflect(&bytes).is_err());
+
+    // header, followed by an OpTypeStruct without operands
+    let words: [u32; 6] = [0x0723_0203, 0x0001_0000, 0, 100, 0, (1 << 16) | 30];
+    let bytes = words
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect::<Vec<_>>();
+    assert!(spirv_reflection::reflect(&bytes).is_err());
+}
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let vertex = spirv_reflection::reflect(&test_module()).unwrap();
+    let fragment = ShaderReflection {
+        stage: ShaderStage::Fragment,
+        inputs: vec![InterfaceVariable {
+            location: 0,
+            format: Format {
+                scalar: ScalarKind::Uint,
+                bits: 32,
+                components: 1,
+                columns: 1,
+            },
+            name: String::new(),
+        }],
+        outputs: Vec::new(),
+        descriptor_bindings: Vec::new(),
+        push_constants: None,
+    };
+    let reflections = vec![vertex, fragment];
+
+    let serialized = spirv_reflection::serialize(&reflections).unwrap();
+    let deserialized = spirv_reflection::deserialize(&serialized).unwrap();
+
+    assert_eq!(reflections, deserialized);
+}
+
+#[test]
+fn should_format() {
+    assert_eq!(f32_format(1, 1).to_string(), "f32");
+    assert_eq!(f32_format(3, 1).to_string(), "f32x3");
+    assert_eq!(f32_format(4, 4).to_string(), "f32x4x4");
+    assert_eq!("f32x4x4".parse::<Format>().unwrap(), f32_format(4, 4));
+    assert!("x32".parse::<Format>().is_err());
+    assert!("f32x4x4x4".parse::<Format>().is_err());
+}
+
+fn vk_format(format: Format) -> vk::Format {
+    match (
+        format.scalar,
+        format.bits,
+        format.components,
+        format.columns,
+    ) {
+        (ScalarKind::Float, 32, 1, 1) => vk::Format::R32_SFLOAT,
+        (ScalarKind::Float, 32, 2, 1) => vk::Format::R32G32_SFLOAT,
+        (ScalarKind::Float, 32, 3, 1) => vk::Format::R32G32B32_SFLOAT,
+        (ScalarKind::Float, 32, 4, 1) => vk::Format::R32G32B32A32_SFLOAT,
+        format => panic!("unsupported format: {:?}", format),
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn should_match_rust_layouts_of_default_shader() {
+    let executable_string = std::env::args().next().expect("no cli args");
+    let executable_path = PathBuf::from(executable_string);
+    let executable_directory = executable_path.parent().expect("executable has no parent");
+    let mut source = PathBuf::from(executable_directory);
+    source.push("..");
+    source.push("..");
+    source.push("..");
+    source.push("assets/__raw/shaders/default.glsl");
+
+    let dir = ris_util::prep_test_dir!();
+    let targets = glsl_to_spirv_importer::target_extensions(&source)
+        .unwrap()
+        .iter()
+        .map(|x| dir.join(format!("default.{}", x)))
+        .collect::<Vec<_>>();
+    glsl_to_spirv_importer::import(source, targets.clone(), &dir, None).unwrap();
+
+    let reflection_target = &targets[glsl_to_spirv_importer::OUT_EXT.len() - 1];
+    let text = std::fs::read_to_string(reflection_target).unwrap();
+    let reflections = spirv_reflection::deserialize(&text).unwrap();
+    let vertex = reflections
+        .iter()
+        .find(|x| x.stage == ShaderStage::Vertex)
+        .unwrap();
+    let fragment = reflections
+        .iter()
+        .find(|x| x.stage == ShaderStage::Fragment)
+        .unwrap();
+
+    // vertex layout
+    assert_eq!(vertex.inputs.len(), VERTEX_ATTRIBUTE_DESCRIPTIONS.len());
+    for (input, attribute) in vertex.inputs.iter().zip(VERTEX_ATTRIBUTE_DESCRIPTIONS) {
+        assert_eq!(input.location, attribute.location);
+        assert_eq!(vk_format(input.format), attribute.format);
+    }
+
+    // push constants
+    let push_constants = vertex.push_constants.as_ref().unwrap();
+    assert_eq!(
+        push_constants.size as usize,
+        std::mem::size_of::<PushConstants>()
+    );
+
+    // descriptors
+    let ubo = vertex
+        .descriptor_bindings
+        .iter()
+        .find(|x| x.set == 0 && x.binding == 0)
+        .unwrap();
+    assert_eq!(ubo.kind, DescriptorKind::UniformBuffer);
+    assert_eq!(
+        ubo.size as usize,
+        std::mem::size_of::<UniformBufferObject>()
+    );
+
+    let sampler = fragment
+        .descriptor_bindings
+        .iter()
+        .find(|x| x.set == 0 && x.binding == 1)
+        .unwrap();
+    assert_eq!(sampler.kind, DescriptorKind::CombinedImageSampler);
+}
@@ -0,0 +1,173 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use ris_core::crash_report;
+use ris_core::log_appenders::ring_buffer_appender;
+use ris_core::log_appenders::ring_buffer_appender::RingBufferAppender;
+use ris_jobs::job_system;
+use ris_log::log::IAppender;
+use ris_log::log_message::LogMessage;
+
+// the messages of the ring buffer are global, thus these tests must not run in parallel
+static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn plain(message: &str) -> LogMessage {
+    LogMessage::Plain(String::from(message))
+}
+
+#[test]
+fn should_keep_last_messages() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    assert!(ring_buffer_appender::messages().is_none());
+
+    let mut appender = RingBufferAppender::new(3).unwrap();
+    assert_eq!(ring_buffer_appender::messages().unwrap().len(), 0);
+
+    for i in 0..5 {
+        appender.print(&plain(&format!("message {}", i)));
+    }
+
+    let messages = ring_buffer_appender::messages()
+        .unwrap()
+        .iter()
+        .map(|x| x.fmt(false))
+        .collect::<Vec<_>>();
+    assert_eq!(messages, vec!["message 2", "message 3", "message 4"]);
+
+    drop(appender);
+    assert!(ring_buffer_appender::messages().is_none());
+}
+
+#[test]
+fn should_generate_report() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let app_info = "some app info";
+
+    let report = crash_report::generate(&app_info, "panicked at test", "0: main");
+    assert!(report.contains("panic:\npanicked at test\n"));
+    assert!(report.contains("backtrace:\n0: main\n"));
+    assert!(report.contains("app info:\nsome app info\n"));
+    assert!(report.contains("log messages are unavailable"));
+
+    let mut appender = RingBufferAppender::new(8).unwrap();
+    appender.print(&plain("last words"));
+    crash_report::set_frame_number(42);
+
+    let report = crash_report::generate(&app_info, "panicked at test", "0: main");
+    assert!(report.contains("frame: 42\n"));
+    assert!(report.contains("last 1 log messages:\n\nlast words\n"));
+}
+
+#[test]
+fn should_write_report() {
+    let test_dir = ris_util::prep_test_dir!();
+
+    let first = crash_report::write(&test_dir, "first report").unwrap();
+    let second = crash_report::write(&test_dir, "second report").unwrap();
+
+    assert_ne!(first, second);
+    assert_eq!(std::fs::read_to_string(first).unwrap(), "first report");
+    assert_eq!(std::fs::read_to_string(second).unwrap(), "second report");
+}
+
+#[test]
+fn should_only_report_uncaught_panics() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let test_dir = ris_util::prep_test_dir!();
+    crash_report::set_panic_hook();
+
+    let app_info = "some app info";
+
+    let result = crash_report::catch_unwind(&test_dir, &app_info, || {
+        let caught = std::panic::catch_unwind(|| panic!("handled on purpose"));
+        assert!(caught.is_err());
+        42
+    });
+    assert_eq!(result, 42);
+    assert!(!test_dir.exists() || std::fs::read_dir(&test_dir).unwrap().next().is_none());
+
+    let result = std::panic::catch_unwind(|| {
+        crash_report::catch_unwind(&test_dir, &app_info, || panic!("uncaught on purpose"))
+    });
+    assert!(result.is_err());
+
+    let reports = std::fs::read_dir(&test_dir)
+        .unwrap()
+        .map(|x| x.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(reports.len(), 1);
+
+    let report = std::fs::read_to_string(&reports[0]).unwrap();
+    assert!(report.contains("uncaught on purpose"));
+    assert!(!report.contains("handled on purpose"));
+    assert!(report.contains("app info:\nsome app info\n"));
+}
+
+#[test]
+fn should_report_panic_resumed_from_other_thread() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let test_dir = ris_util::prep_test_dir!();
+    crash_report::set_panic_hook();
+
+    let app_info = "some app info";
+
+    let result = std::panic::catch_unwind(|| {
+        crash_report::catch_unwind(&test_dir, &app_info, || {
+            let caught = std::panic::catch_unwind(|| panic!("stale on purpose"));
+            assert!(caught.is_err());
+
+            let result = std::thread::spawn(|| panic!("fresh on purpose")).join();
+            std::panic::resume_unwind(result.unwrap_err())
+        })
+    });
+    assert!(result.is_err());
+
+    let reports = std::fs::read_dir(&test_dir)
+        .unwrap()
+        .map(|x| x.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(reports.len(), 1);
+
+    let report = std::fs::read_to_string(&reports[0]).unwrap();
+    assert!(report.contains("fresh on purpose"));
+    assert!(!report.contains("stale on purpose"));
+    assert!(!report.contains("did not record this panic"));
+}
+
+#[test]
+fn should_report_panic_of_worker_thread() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let test_dir = ris_util::prep_test_dir!();
+    crash_report::set_panic_hook();
+    crash_report::report_unresumed_panics(test_dir.clone(), "some app info");
+
+    let job_system = job_system::init(100, 10, 2, false);
+
+    // the calling thread never runs pending jobs, thus the job is stolen by the worker thread
+    let future = job_system::submit(|| panic!("worker panic on purpose"));
+    drop(future);
+
+    // other tests may panic in jobs as well, thus reports are searched by their content
+    let start = Instant::now();
+    let mut reported = false;
+    while !reported && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+
+        let Ok(entries) = std::fs::read_dir(&test_dir) else {
+            continue;
+        };
+
+        reported = entries.filter_map(|x| x.ok()).any(|x| {
+            std::fs::read_to_string(x.path())
+                .map(|x| x.contains("worker panic on purpose"))
+                .unwrap_or(false)
+        });
+    }
+
+    job_system::set_panic_handler(None);
+    drop(job_system);
+
+    assert!(reported);
+}
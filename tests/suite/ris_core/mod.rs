@@ -0,0 +1,2 @@
+#[cfg(not(miri))]
+pub mod crash_report;
@@ -5,4 +5,5 @@ pub mod buttons;
 pub mod cell;
 pub mod keys;
 pub mod ptr;
+pub mod rebind_matrix;
 pub mod ris_map;
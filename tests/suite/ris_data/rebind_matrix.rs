@@ -0,0 +1,40 @@
+use ris_data::input::rebind_matrix::RebindMatrix;
+
+#[test]
+fn should_parse_empty_as_default() {
+    let rebind_matrix = "".parse::<RebindMatrix>().unwrap();
+    assert_eq!(rebind_matrix, RebindMatrix::default());
+    assert_eq!(rebind_matrix.to_string(), "");
+}
+
+#[test]
+fn should_parse_rebinds() {
+    let rebind_matrix = "0=1+2, 3=none,31=0".parse::<RebindMatrix>().unwrap();
+
+    assert_eq!(rebind_matrix.data[0], 0b110);
+    assert_eq!(rebind_matrix.data[1], 0b10);
+    assert_eq!(rebind_matrix.data[3], 0);
+    assert_eq!(rebind_matrix.data[31], 1);
+}
+
+#[test]
+fn should_format_rows_that_differ_from_default() {
+    let mut rebind_matrix = RebindMatrix::default();
+    rebind_matrix.data[5] = 0;
+    rebind_matrix.data[2] = (1 << 4) | (1 << 0);
+
+    assert_eq!(rebind_matrix.to_string(), "2=0+4,5=none");
+    assert_eq!(
+        rebind_matrix.to_string().parse::<RebindMatrix>().unwrap(),
+        rebind_matrix
+    );
+}
+
+#[test]
+fn should_not_parse_invalid_rebinds() {
+    assert!("0".parse::<RebindMatrix>().is_err());
+    assert!("32=0".parse::<RebindMatrix>().is_err());
+    assert!("0=32".parse::<RebindMatrix>().is_err());
+    assert!("a=0".parse::<RebindMatrix>().is_err());
+    assert!("0=".parse::<RebindMatrix>().is_err());
+}
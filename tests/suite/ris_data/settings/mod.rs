@@ -1 +1,4 @@
 pub mod ris_yaml;
+pub mod schema;
+pub mod serializer;
+pub mod values;
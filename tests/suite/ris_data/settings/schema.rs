@@ -0,0 +1,123 @@
+use ris_data::settings::schema;
+use ris_data::settings::schema::SettingValue;
+
+#[test]
+fn should_have_unique_keys_with_sections() {
+    for (i, setting) in schema::ALL.iter().enumerate() {
+        assert!(setting.key.contains('.'), "{}", setting.key);
+        assert_eq!(schema::index_of(setting), Some(i), "{}", setting.key);
+    }
+}
+
+#[test]
+fn should_have_valid_defaults() {
+    for setting in schema::ALL.iter() {
+        let default = setting.default_value();
+        let validated = setting.validate(default.clone()).unwrap();
+        assert_eq!(validated, default, "{}", setting.key);
+    }
+}
+
+#[test]
+fn should_split_key() {
+    assert_eq!(schema::WINDOW_VSYNC.section(), "window");
+    assert_eq!(schema::WINDOW_VSYNC.name(), "vsync");
+}
+
+#[test]
+fn should_find_by_key() {
+    let setting = schema::find("audio.music_volume").unwrap();
+    assert_eq!(setting.key, schema::AUDIO_MUSIC_VOLUME.key);
+    assert!(schema::find("audio.unknown").is_none());
+}
+
+#[test]
+fn should_validate_range() {
+    let setting = &schema::WINDOW_WIDTH;
+    assert!(setting.validate(SettingValue::Int(320)).is_ok());
+    assert!(setting.validate(SettingValue::Int(16384)).is_ok());
+    assert!(setting.validate(SettingValue::Int(319)).is_err());
+    assert!(setting.validate(SettingValue::Int(16385)).is_err());
+
+    let setting = &schema::AUDIO_MASTER_VOLUME;
+    assert!(setting.validate(SettingValue::Float(0.0)).is_ok());
+    assert!(setting.validate(SettingValue::Float(1.0)).is_ok());
+    assert!(setting.validate(SettingValue::Float(-0.1)).is_err());
+    assert!(setting.validate(SettingValue::Float(1.1)).is_err());
+    assert!(setting.validate(SettingValue::Float(f32::NAN)).is_err());
+}
+
+#[test]
+fn should_not_validate_wrong_type() {
+    assert!(schema::WINDOW_VSYNC.validate(SettingValue::Int(1)).is_err());
+    assert!(schema::JOB_WORKERS
+        .validate(SettingValue::Float(1.0))
+        .is_err());
+    assert!(schema::LOG_LEVEL
+        .validate(SettingValue::Bool(true))
+        .is_err());
+}
+
+#[test]
+fn should_validate_choice() {
+    let setting = &schema::LOG_LEVEL;
+    assert_eq!(
+        setting
+            .validate(SettingValue::Text(" Info ".to_string()))
+            .unwrap(),
+        SettingValue::Text("info".to_string()),
+    );
+    assert!(setting
+        .validate(SettingValue::Text("verbose".to_string()))
+        .is_err());
+}
+
+#[test]
+fn should_validate_text() {
+    let setting = &schema::LOG_FILTER;
+    assert_eq!(
+        setting
+            .parse(" ris_asset=debug , ris_jobs=warning ")
+            .unwrap(),
+        SettingValue::Text("ris_asset=debug,ris_jobs=warning".to_string()),
+    );
+    assert!(setting.parse("ris_asset=verbose").is_err());
+    assert!(setting.parse("ris_asset=debug#").is_err());
+
+    let setting = &schema::INPUT_KEYBOARD_REBINDS;
+    assert_eq!(
+        setting.parse("3=none, 0=2+1").unwrap(),
+        SettingValue::Text("0=1+2,3=none".to_string()),
+    );
+    assert!(setting.parse("32=0").is_err());
+}
+
+#[test]
+fn should_parse() {
+    assert_eq!(
+        schema::WINDOW_FULLSCREEN.parse("true").unwrap(),
+        SettingValue::Bool(true),
+    );
+    assert_eq!(
+        schema::JOB_WORKERS.parse(" 4 ").unwrap(),
+        SettingValue::Int(4),
+    );
+    assert_eq!(
+        schema::AUDIO_EFFECTS_VOLUME.parse("0.5").unwrap(),
+        SettingValue::Float(0.5),
+    );
+    assert!(schema::WINDOW_FULLSCREEN.parse("yes").is_err());
+    assert!(schema::JOB_WORKERS.parse("-1").is_err());
+}
+
+#[test]
+fn should_describe_range_in_comment() {
+    assert_eq!(
+        schema::WINDOW_HEIGHT.comment(),
+        "height of the window in pixels. 240 to 16384",
+    );
+    assert_eq!(
+        schema::LOG_LEVEL.comment(),
+        "messages below this level are not logged. one of trace, debug, info, warning, error, fatal, none",
+    );
+}
@@ -0,0 +1,82 @@
+use ris_data::settings::schema;
+use ris_data::settings::schema::SettingValue;
+use ris_data::settings::serializer;
+use ris_data::settings::Settings;
+
+#[test]
+fn should_serialize_and_deserialize() {
+    let mut settings = Settings::new();
+    settings
+        .set(&schema::JOB_WORKERS, SettingValue::Int(7))
+        .unwrap();
+    settings
+        .set(&schema::WINDOW_FULLSCREEN, SettingValue::Bool(true))
+        .unwrap();
+    settings
+        .set(&schema::AUDIO_MASTER_VOLUME, SettingValue::Float(0.25))
+        .unwrap();
+    settings
+        .set(
+            &schema::LOG_FILTER,
+            SettingValue::Text("ris_io=info".to_string()),
+        )
+        .unwrap();
+    settings
+        .set(
+            &schema::INPUT_MOUSE_REBINDS,
+            SettingValue::Text("0=1+2".to_string()),
+        )
+        .unwrap();
+
+    let bytes = serializer::write_bytes(&settings).unwrap();
+    let deserialized = serializer::read_bytes(&bytes).unwrap();
+
+    for setting in schema::ALL.iter() {
+        assert_eq!(
+            deserialized.get(setting),
+            settings.get(setting),
+            "{}",
+            setting.key
+        );
+    }
+
+    assert!(!deserialized.changed());
+}
+
+#[test]
+fn should_write_descriptions_as_comments() {
+    let bytes = serializer::write_bytes(&Settings::new()).unwrap();
+    let string = String::from_utf8(bytes).unwrap();
+
+    assert!(string.starts_with("# job\n"));
+    assert!(string.contains("\nwindow.width: 640 # width of the window in pixels. 320 to 16384\n"));
+    assert!(string.contains("\n\n# audio\n"));
+}
+
+#[test]
+fn should_use_defaults_for_missing_keys() {
+    let settings = serializer::read_bytes(b"window.vsync: true\n").unwrap();
+
+    assert!(settings.get_bool(&schema::WINDOW_VSYNC));
+    assert_eq!(settings.get_int(&schema::WINDOW_WIDTH), 640);
+}
+
+#[test]
+fn should_skip_unknown_keys() {
+    let bytes = b"# jobs
+job.workers: 2
+job.unknown: 3 # from another version
+window.vsync: true
+";
+    let settings = serializer::read_bytes(bytes).unwrap();
+
+    assert_eq!(settings.get_int(&schema::JOB_WORKERS), 2);
+    assert!(settings.get_bool(&schema::WINDOW_VSYNC));
+}
+
+#[test]
+fn should_not_deserialize_invalid_values() {
+    assert!(serializer::read_bytes(b"job.workers: many\n").is_err());
+    assert!(serializer::read_bytes(b"window.width: 1\n").is_err());
+    assert!(serializer::read_bytes(b"log.level: verbose\n").is_err());
+}
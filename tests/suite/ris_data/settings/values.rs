@@ -0,0 +1,128 @@
+use ris_data::settings::schema;
+use ris_data::settings::schema::SettingValue;
+use ris_data::settings::Settings;
+use ris_log::log_filter::LogFilter;
+use ris_log::log_level::LogLevel;
+
+#[test]
+fn should_start_with_defaults() {
+    let settings = Settings::new();
+
+    for setting in schema::ALL.iter() {
+        assert_eq!(*settings.get(setting), setting.default_value());
+        assert!(!settings.setting_changed(setting));
+    }
+
+    assert!(!settings.changed());
+}
+
+#[test]
+fn should_track_changes() {
+    let mut settings = Settings::new();
+
+    settings
+        .set(&schema::WINDOW_VSYNC, SettingValue::Bool(true))
+        .unwrap();
+    assert!(settings.get_bool(&schema::WINDOW_VSYNC));
+    assert!(settings.setting_changed(&schema::WINDOW_VSYNC));
+    assert!(!settings.setting_changed(&schema::WINDOW_FULLSCREEN));
+    assert!(settings.changed());
+
+    settings.reset();
+    assert!(!settings.setting_changed(&schema::WINDOW_VSYNC));
+    assert!(!settings.changed());
+
+    // same value is no change
+    settings
+        .set(&schema::WINDOW_VSYNC, SettingValue::Bool(true))
+        .unwrap();
+    assert!(!settings.setting_changed(&schema::WINDOW_VSYNC));
+}
+
+#[test]
+fn should_keep_value_when_invalid() {
+    let mut settings = Settings::new();
+
+    let result = settings.set(&schema::AUDIO_MUSIC_VOLUME, SettingValue::Float(2.0));
+    assert!(result.is_err());
+    assert_eq!(settings.get_float(&schema::AUDIO_MUSIC_VOLUME), 1.0);
+    assert!(!settings.setting_changed(&schema::AUDIO_MUSIC_VOLUME));
+}
+
+#[test]
+fn should_restore_defaults() {
+    let mut settings = Settings::new();
+    settings
+        .set(&schema::WINDOW_WIDTH, SettingValue::Int(1920))
+        .unwrap();
+    settings.reset();
+
+    settings.restore_defaults();
+    assert_eq!(settings.get_int(&schema::WINDOW_WIDTH), 640);
+    assert!(settings.setting_changed(&schema::WINDOW_WIDTH));
+    assert!(!settings.setting_changed(&schema::WINDOW_HEIGHT));
+}
+
+#[test]
+fn should_assign() {
+    let mut source = Settings::new();
+    source
+        .set(&schema::JOB_WORKERS, SettingValue::Int(3))
+        .unwrap();
+
+    let mut settings = Settings::new();
+    settings.assign(&source);
+    assert_eq!(settings.get_int(&schema::JOB_WORKERS), 3);
+    assert!(settings.setting_changed(&schema::JOB_WORKERS));
+    assert!(!settings.setting_changed(&schema::WINDOW_WIDTH));
+}
+
+#[test]
+fn should_combine_log_level_and_filter() {
+    let mut settings = Settings::new();
+    settings
+        .set(&schema::LOG_LEVEL, SettingValue::Text("info".to_string()))
+        .unwrap();
+    settings
+        .set(
+            &schema::LOG_FILTER,
+            SettingValue::Text("ris_asset=debug".to_string()),
+        )
+        .unwrap();
+
+    let log_filter = settings.log_filter();
+    assert_eq!(log_filter.to_string(), "info,ris_asset=debug");
+    assert!(settings.log_filter_changed());
+}
+
+#[test]
+fn should_split_log_filter() {
+    let mut settings = Settings::new();
+    let log_filter = "warning,ris_asset=debug,ris_jobs=error"
+        .parse::<LogFilter>()
+        .unwrap();
+    settings.set_log_filter(&log_filter).unwrap();
+
+    assert_eq!(settings.get_text(&schema::LOG_LEVEL), "warning");
+    assert_eq!(
+        settings.get_text(&schema::LOG_FILTER),
+        "ris_asset=debug,ris_jobs=error",
+    );
+    assert_eq!(settings.log_filter(), log_filter);
+    assert_eq!(settings.log_filter().default, LogLevel::Warning);
+}
+
+#[test]
+fn should_get_rebind_matrix() {
+    let mut settings = Settings::new();
+    settings
+        .set(
+            &schema::INPUT_GAMEPAD_REBINDS,
+            SettingValue::Text("1=none".to_string()),
+        )
+        .unwrap();
+
+    let rebind_matrix = settings.rebind_matrix(&schema::INPUT_GAMEPAD_REBINDS);
+    assert_eq!(rebind_matrix.data[0], 1);
+    assert_eq!(rebind_matrix.data[1], 0);
+}
@@ -0,0 +1,89 @@
+use std::io::Cursor;
+use std::io::SeekFrom;
+
+use ris_io::BoundedReader;
+use ris_io::FatPtr;
+
+#[test]
+fn should_read_len_within_bounds() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_uint(&mut stream, 3).unwrap();
+    ris_io::write(&mut stream, &[0; 6]).unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, 3).unwrap();
+    assert_eq!(reader.remaining().unwrap(), 10);
+    assert_eq!(reader.read_len(2).unwrap(), 3);
+    assert_eq!(reader.remaining().unwrap(), 6);
+}
+
+#[test]
+fn should_not_read_len_exceeding_the_maximum() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_uint(&mut stream, 4).unwrap();
+    ris_io::write(&mut stream, &[0; 4]).unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, 3).unwrap();
+    assert!(reader.read_len(1).is_err());
+}
+
+#[test]
+fn should_not_read_len_exceeding_the_remaining_bytes() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_uint(&mut stream, 3).unwrap();
+    ris_io::write(&mut stream, &[0; 5]).unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, usize::MAX).unwrap();
+    assert!(reader.check_len(usize::MAX, 2).is_err());
+    assert!(reader.read_len(2).is_err());
+}
+
+#[test]
+fn should_read_varint_len() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_varint(&mut stream, 300).unwrap();
+    ris_io::write(&mut stream, &[0; 300]).unwrap();
+    ris_io::write_varint(&mut stream, 1).unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, 1_000).unwrap();
+    assert_eq!(reader.read_varint_len(1).unwrap(), 300);
+    ris_io::seek(&mut reader, SeekFrom::Current(300)).unwrap();
+    assert!(reader.read_varint_len(1).is_err());
+}
+
+#[test]
+fn should_read_string() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_string(&mut stream, "hello").unwrap();
+    ris_io::write_uint(&mut stream, 1_000).unwrap();
+    ris_io::write(&mut stream, b"world").unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, 1_000).unwrap();
+    assert_eq!(reader.read_string().unwrap(), "hello");
+    assert!(reader.read_string().is_err());
+}
+
+#[test]
+fn should_not_read_fat_ptr_outside_of_the_stream() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_fat_ptr(&mut stream, FatPtr { addr: 16, len: 32 }).unwrap();
+    ris_io::write_fat_ptr(&mut stream, FatPtr { addr: 16, len: 33 }).unwrap();
+    ris_io::write_fat_ptr(
+        &mut stream,
+        FatPtr {
+            addr: u64::MAX,
+            len: 2,
+        },
+    )
+    .unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+
+    let mut reader = BoundedReader::new(stream, 0).unwrap();
+    assert_eq!(reader.read_fat_ptr().unwrap(), FatPtr { addr: 16, len: 32 });
+    assert!(reader.read_fat_ptr().is_err());
+    assert!(reader.read_fat_ptr().is_err());
+}
@@ -9,6 +9,7 @@ use chrono::Duration;
 use chrono::Local;
 
 use ris_io::fallback_file::FallbackFileAppend;
+use ris_io::fallback_file::Rotation;
 
 #[test]
 fn should_create_directories() {
@@ -182,3 +183,248 @@ fn should_give_access_to_current_file() {
     assert_eq!(lines[1], "");
     assert_eq!(lines[2], "i am a very important message");
 }
+
+fn old_entries(test_dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut old_dir = PathBuf::from(test_dir);
+    old_dir.push("old");
+
+    let mut entries = std::fs::read_dir(old_dir)
+        .unwrap()
+        .map(|x| x.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries
+}
+
+fn create_old_files(test_dir: &std::path::Path, count: usize, size: usize) -> Vec<PathBuf> {
+    let mut old_dir = PathBuf::from(test_dir);
+    old_dir.push("old");
+    std::fs::create_dir_all(&old_dir).unwrap();
+
+    let mut file_paths = Vec::new();
+    for i in 0..count {
+        // old files are sorted by name, thus these are older than files named by the date
+        let mut file_path = PathBuf::from(&old_dir);
+        file_path.push(format!("2000-01-01_{}", i));
+
+        std::fs::write(&file_path, vec![b'x'; size]).unwrap();
+        file_paths.push(file_path);
+    }
+
+    file_paths
+}
+
+fn gunzip(bytes: &[u8]) -> Vec<u8> {
+    assert_eq!(bytes[0..3], [0x1f, 0x8b, 8]);
+
+    let deflated = &bytes[10..bytes.len() - 8];
+    let decompressed = miniz_oxide::inflate::decompress_to_vec(deflated).unwrap();
+
+    let mut len = [0; 4];
+    len.copy_from_slice(&bytes[bytes.len() - 4..]);
+    assert_eq!(u32::from_le_bytes(len) as usize, decompressed.len());
+
+    decompressed
+}
+
+#[test]
+fn should_not_rotate_by_default() {
+    let test_dir = ris_util::prep_test_dir!();
+    let mut appender = FallbackFileAppend::new(&test_dir, ".test", 10).unwrap();
+
+    writeln!(appender.current(), "{}", "x".repeat(1024)).unwrap();
+
+    assert!(!appender.rotate_if_required().unwrap());
+    assert!(old_entries(&test_dir).is_empty());
+}
+
+#[test]
+fn should_rotate_when_exceeding_size() {
+    let test_dir = ris_util::prep_test_dir!();
+    let rotation = Rotation {
+        max_file_size: Some(128),
+        ..Default::default()
+    };
+    let mut appender = FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    writeln!(appender.current(), "small").unwrap();
+    assert!(!appender.rotate_if_required().unwrap());
+
+    writeln!(appender.current(), "{}", "x".repeat(128)).unwrap();
+    assert!(appender.rotate_if_required().unwrap());
+    assert!(!appender.rotate_if_required().unwrap());
+
+    writeln!(appender.current(), "new message").unwrap();
+    drop(appender);
+
+    let old_entries = old_entries(&test_dir);
+    assert_eq!(old_entries.len(), 1);
+    let old_content = std::fs::read_to_string(&old_entries[0]).unwrap();
+    let old_lines = old_content.lines().collect::<Vec<_>>();
+    assert_eq!(old_lines.len(), 4);
+    assert!(DateTime::parse_from_rfc3339(old_lines[0]).is_ok());
+    assert_eq!(old_lines[2], "small");
+    assert_eq!(old_lines[3], "x".repeat(128));
+
+    let mut current_path = PathBuf::from(&test_dir);
+    current_path.push("current.test");
+    let current_content = std::fs::read_to_string(current_path).unwrap();
+    let current_lines = current_content.lines().collect::<Vec<_>>();
+    assert_eq!(current_lines.len(), 3);
+    assert!(DateTime::parse_from_rfc3339(current_lines[0]).is_ok());
+    assert_eq!(current_lines[2], "new message");
+}
+
+#[test]
+fn should_rotate_when_exceeding_age() {
+    let test_dir = ris_util::prep_test_dir!();
+    let rotation = Rotation {
+        max_file_age: Some(std::time::Duration::from_secs(60 * 60)),
+        ..Default::default()
+    };
+    let mut appender =
+        FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation.clone()).unwrap();
+    assert!(!appender.rotate_if_required().unwrap());
+    drop(appender);
+
+    let test_dir = ris_util::prep_test_dir!();
+    let rotation = Rotation {
+        max_file_age: Some(std::time::Duration::ZERO),
+        ..Default::default()
+    };
+    let mut appender = FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+    assert!(appender.rotate_if_required().unwrap());
+    drop(appender);
+
+    assert_eq!(old_entries(&test_dir).len(), 1);
+}
+
+#[test]
+fn should_compress_rotated_files() {
+    let test_dir = ris_util::prep_test_dir!();
+    let rotation = Rotation {
+        compress: true,
+        ..Default::default()
+    };
+    let mut appender = FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    let message = "i am a very important message ".repeat(100);
+    writeln!(appender.current(), "{}", message).unwrap();
+    appender.rotate().unwrap();
+    drop(appender);
+
+    let old_entries = old_entries(&test_dir);
+    assert_eq!(old_entries.len(), 1);
+    assert!(old_entries[0].to_str().unwrap().ends_with(".test.gz"));
+
+    let compressed = std::fs::read(&old_entries[0]).unwrap();
+    assert!(compressed.len() < message.len());
+
+    let decompressed = String::from_utf8(gunzip(&compressed)).unwrap();
+    let lines = decompressed.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(DateTime::parse_from_rfc3339(lines[0]).is_ok());
+    assert_eq!(lines[2], message);
+}
+
+#[test]
+fn should_finish_compression_before_rotating_again() {
+    let test_dir = ris_util::prep_test_dir!();
+    let rotation = Rotation {
+        compress: true,
+        ..Default::default()
+    };
+    let mut appender = FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    for i in 0..3 {
+        writeln!(appender.current(), "message {}", i).unwrap();
+        appender.rotate().unwrap();
+
+        // compression runs in the background, thus the new current file is writable right away
+        writeln!(appender.current(), "after rotation {}", i).unwrap();
+    }
+
+    appender.finish_compression().unwrap();
+
+    let old_entries = old_entries(&test_dir);
+    assert_eq!(old_entries.len(), 3);
+    for entry in old_entries.iter() {
+        assert!(entry.to_str().unwrap().ends_with(".test.gz"));
+        let compressed = std::fs::read(entry).unwrap();
+        assert!(String::from_utf8(gunzip(&compressed)).is_ok());
+    }
+
+    drop(appender);
+}
+
+#[test]
+fn should_compress_current_file_on_creation() {
+    let test_dir = ris_util::prep_test_dir!();
+
+    let mut current_path = PathBuf::from(&test_dir);
+    current_path.push("current.test");
+    std::fs::write(&current_path, "i am a unique file\n").unwrap();
+
+    let rotation = Rotation {
+        compress: true,
+        ..Default::default()
+    };
+    FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    let old_entries = old_entries(&test_dir);
+    assert_eq!(old_entries.len(), 1);
+    assert!(old_entries[0].ends_with("i am a unique file.test.gz"));
+
+    let compressed = std::fs::read(&old_entries[0]).unwrap();
+    assert_eq!(gunzip(&compressed), b"i am a unique file\n");
+}
+
+#[test]
+fn should_delete_files_exceeding_total_size() {
+    let test_dir = ris_util::prep_test_dir!();
+    let file_paths = create_old_files(&test_dir, 10, 100);
+
+    let rotation = Rotation {
+        max_total_size: Some(450),
+        ..Default::default()
+    };
+    FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    for file_path in file_paths.iter().take(6) {
+        assert!(!file_path.exists());
+    }
+
+    for file_path in file_paths.iter().skip(6) {
+        assert!(file_path.exists());
+    }
+}
+
+#[test]
+fn should_reserve_max_file_size_for_current_file() {
+    let test_dir = ris_util::prep_test_dir!();
+    let file_paths = create_old_files(&test_dir, 10, 100);
+
+    let rotation = Rotation {
+        max_file_size: Some(100),
+        max_total_size: Some(450),
+        ..Default::default()
+    };
+    let mut appender = FallbackFileAppend::with_rotation(&test_dir, ".test", 10, rotation).unwrap();
+
+    for file_path in file_paths.iter().take(7) {
+        assert!(!file_path.exists());
+    }
+
+    for file_path in file_paths.iter().skip(7) {
+        assert!(file_path.exists());
+    }
+
+    writeln!(appender.current(), "{}", "x".repeat(100)).unwrap();
+    assert!(appender.rotate_if_required().unwrap());
+    drop(appender);
+
+    // the rotated file is the newest, thus the oldest remaining file is deleted
+    assert!(!file_paths[7].exists());
+    assert!(file_paths[8].exists());
+    assert!(file_paths[9].exists());
+}
@@ -0,0 +1,92 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use ris_io::fallback_file::AtomicFile;
+use ris_io::fallback_file::FallbackFileOverwrite;
+
+#[test]
+fn should_write_atomically() {
+    let test_dir = ris_util::prep_test_dir!();
+    let path = test_dir.join("file.bin");
+
+    ris_io::fallback_file::write_atomic(&path, b"first").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+    ris_io::fallback_file::write_atomic(&path, b"second").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"second");
+
+    // no temporary file is left behind
+    let entries = std::fs::read_dir(&test_dir).unwrap().count();
+    assert_eq!(entries, 1);
+}
+
+#[test]
+fn should_keep_previous_content_when_write_is_interrupted() {
+    let test_dir = ris_util::prep_test_dir!();
+    let path = test_dir.join("file.bin");
+    ris_io::fallback_file::write_atomic(&path, b"previous").unwrap();
+
+    // the process dies before committing. forgetting the file skips all cleanup
+    let mut file = AtomicFile::create(&path).unwrap();
+    file.write_all(b"half of the n").unwrap();
+    let temp_path = file.temp_path().to_path_buf();
+    std::mem::forget(file);
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"previous");
+    assert!(temp_path.exists());
+
+    // the next write is not affected by the leftover
+    ris_io::fallback_file::write_atomic(&path, b"new").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"new");
+    assert!(!temp_path.exists());
+}
+
+#[test]
+fn should_discard_uncommitted_file() {
+    let test_dir = ris_util::prep_test_dir!();
+    let path = test_dir.join("file.bin");
+    ris_io::fallback_file::write_atomic(&path, b"previous").unwrap();
+
+    let mut file = AtomicFile::create(&path).unwrap();
+    file.write_all(b"discarded").unwrap();
+    let temp_path = file.temp_path().to_path_buf();
+    drop(file);
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"previous");
+    assert!(!temp_path.exists());
+}
+
+#[test]
+fn should_not_create_file_when_first_write_is_interrupted() {
+    let test_dir = ris_util::prep_test_dir!();
+    let path = test_dir.join("file.bin");
+
+    let mut file = AtomicFile::create(&path).unwrap();
+    file.write_all(b"interrupted").unwrap();
+    std::mem::forget(file);
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn should_keep_current_file_when_overwrite_is_interrupted() {
+    let test_dir = ris_util::prep_test_dir!();
+    let overwriter = FallbackFileOverwrite::new(&test_dir, ".test", 10);
+    overwriter.overwrite_current(b"zero").unwrap();
+
+    let mut current_path = PathBuf::from(&test_dir);
+    current_path.push("current.test");
+
+    // the process dies while the new current file is written
+    let mut file = AtomicFile::create(&current_path).unwrap();
+    file.write_all(b"corrupt").unwrap();
+    std::mem::forget(file);
+
+    assert_eq!(overwriter.get_by_index(0).unwrap(), b"zero");
+    assert_eq!(overwriter.available_paths(), vec![current_path.clone()]);
+
+    overwriter.overwrite_current(b"one").unwrap();
+    assert_eq!(overwriter.get_by_index(0).unwrap(), b"one");
+    assert_eq!(overwriter.get_by_index(1).unwrap(), b"zero");
+    assert_eq!(overwriter.available_paths().len(), 2);
+}
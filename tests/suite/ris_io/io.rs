@@ -733,3 +733,93 @@ fn test_read<T>(
 
     result
 }
+
+#[test]
+fn should_read_and_write_varint() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let rng = Rc::new(RefCell::new(Rng::new(seed)));
+
+    testing::repeat(miri_choose(1_000, 10), move |_| {
+        let mut rng = rng.borrow_mut();
+        let shift = rng.next_i32_between(0, 63);
+        let input = ((rng.next_u32() as u64) << 32 | rng.next_u32() as u64) >> shift;
+        let mut stream = Cursor::new(Vec::new());
+        let ptr = ris_io::write_varint(&mut stream, input).unwrap();
+        ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+        let output = ris_io::read_varint(&mut stream).unwrap();
+        assert_eq!(input, output);
+
+        let expected_len = usize::max(1, (64 - input.leading_zeros() as usize).div_ceil(7));
+        assert_eq!(ptr.len as usize, expected_len);
+    });
+}
+
+#[test]
+fn should_write_varint_as_leb128() {
+    let cases: [(u64, &[u8]); 6] = [
+        (0, &[0x00]),
+        (1, &[0x01]),
+        (127, &[0x7F]),
+        (128, &[0x80, 0x01]),
+        (624_485, &[0xE5, 0x8E, 0x26]),
+        (
+            u64::MAX,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+    ];
+
+    for (input, expected) in cases {
+        let mut stream = Cursor::new(Vec::new());
+        ris_io::write_varint(&mut stream, input).unwrap();
+        assert_eq!(stream.into_inner(), expected);
+    }
+}
+
+#[test]
+fn should_read_and_write_signed_varint() {
+    let cases: [(i64, usize); 7] = [
+        (0, 1),
+        (-1, 1),
+        (1, 1),
+        (-64, 1),
+        (64, 2),
+        (i64::MAX, 10),
+        (i64::MIN, 10),
+    ];
+
+    for (input, expected_len) in cases {
+        let mut stream = Cursor::new(Vec::new());
+        let ptr = ris_io::write_varint_signed(&mut stream, input).unwrap();
+        ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+        let output = ris_io::read_varint_signed(&mut stream).unwrap();
+        assert_eq!(input, output);
+        assert_eq!(ptr.len as usize, expected_len);
+    }
+}
+
+#[test]
+fn should_not_read_invalid_varint() {
+    // unterminated
+    let mut stream = Cursor::new(vec![0x80, 0x80]);
+    assert!(ris_io::read_varint(&mut stream).is_err());
+
+    // too long
+    let mut stream = Cursor::new(vec![0x80; 11]);
+    assert!(ris_io::read_varint(&mut stream).is_err());
+
+    // overflows an u64
+    let mut bytes = vec![0xFF; 9];
+    bytes.push(0x02);
+    let mut stream = Cursor::new(bytes);
+    assert!(ris_io::read_varint(&mut stream).is_err());
+}
+
+#[test]
+fn should_not_allocate_string_len_exceeding_the_stream() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_uint(&mut stream, u32::MAX as usize).unwrap();
+    ris_io::write(&mut stream, b"abc").unwrap();
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+    assert!(ris_io::read_string(&mut stream).is_err());
+}
@@ -1,5 +1,10 @@
+pub mod bounded_reader;
 #[cfg(not(miri))]
 pub mod fallback_file_append;
 #[cfg(not(miri))]
+pub mod fallback_file_atomic;
+#[cfg(not(miri))]
 pub mod fallback_file_overwrite;
 pub mod io;
+pub mod pod;
+pub mod serialize;
@@ -0,0 +1,124 @@
+use std::cell::RefCell;
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::rc::Rc;
+
+use ris_data::ecs::mesh::Vertex;
+use ris_math::color::Rgb;
+use ris_math::matrix::Mat4;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec4;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+use ris_util::testing;
+use ris_util::testing::miri_choose;
+
+#[test]
+fn should_write_slice_as_little_endian() {
+    let mut stream = Cursor::new(Vec::new());
+    let ptr = ris_io::write_slice(&mut stream, &[0x0102_0304u32, 0x0506_0708]).unwrap();
+    assert_eq!(ptr, ris_io::FatPtr { addr: 0, len: 8 });
+    assert_eq!(
+        stream.into_inner(),
+        vec![0x04, 0x03, 0x02, 0x01, 0x08, 0x07, 0x06, 0x05],
+    );
+}
+
+#[test]
+fn should_write_slice_like_element_wise_writes() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let rng = Rc::new(RefCell::new(Rng::new(seed)));
+
+    testing::repeat(miri_choose(100, 10), move |_| {
+        let mut rng = rng.borrow_mut();
+        let len = rng.next_i32_between(0, 100) as usize;
+        let values = (0..len).map(|_| rng.next_pos_3()).collect::<Vec<_>>();
+
+        let mut expected = Cursor::new(Vec::new());
+        for value in values.iter() {
+            ris_io::write_vec3(&mut expected, *value).unwrap();
+        }
+
+        let mut actual = Cursor::new(Vec::new());
+        ris_io::write_slice(&mut actual, &values).unwrap();
+
+        assert_eq!(actual.into_inner(), expected.into_inner());
+    });
+}
+
+#[test]
+fn should_read_and_write_slice() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let rng = Rc::new(RefCell::new(Rng::new(seed)));
+
+    testing::repeat(miri_choose(100, 10), move |_| {
+        let mut rng = rng.borrow_mut();
+        let len = rng.next_i32_between(0, 100) as usize;
+        let input = (0..len).map(|_| rng.next_u32() as u16).collect::<Vec<_>>();
+
+        let mut stream = Cursor::new(Vec::new());
+        ris_io::write_u8(&mut stream, 42).unwrap();
+        let ptr = ris_io::write_slice(&mut stream, &input).unwrap();
+        assert_eq!(ptr.addr, 1);
+        assert_eq!(ptr.len as usize, len * 2);
+
+        ris_io::seek(&mut stream, SeekFrom::Start(1)).unwrap();
+        let mut output = vec![0u16; len];
+        ris_io::read_slice(&mut stream, &mut output).unwrap();
+        assert_eq!(input, output);
+    });
+}
+
+#[test]
+fn should_read_and_write_vertices() {
+    let mut rng = Rng::new(Seed([1; 16]));
+    let input = (0..10)
+        .map(|_| Vertex {
+            pos: rng.next_pos_3(),
+            color: Rgb(rng.next_f32(), rng.next_f32(), rng.next_f32()),
+            uv: Vec2(rng.next_f32(), rng.next_f32()),
+        })
+        .collect::<Vec<_>>();
+
+    let mut stream = Cursor::new(Vec::new());
+    let ptr = ris_io::write_slice(&mut stream, &input).unwrap();
+    assert_eq!(ptr.len, 10 * 32);
+
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+    let output = ris_io::read_vec::<Vertex>(&mut stream, input.len()).unwrap();
+
+    assert_eq!(ris_io::as_bytes(&input), ris_io::as_bytes(&output));
+    ris_util::assert_vec3_eq!(input[3].pos, output[3].pos);
+}
+
+#[test]
+fn should_read_and_write_matrices() {
+    let input = [
+        Mat4::init(1.0),
+        Mat4::init(2.0),
+        Mat4(
+            Vec4(1.0, 2.0, 3.0, 4.0),
+            Vec4(5.0, 6.0, 7.0, 8.0),
+            Vec4(9.0, 10.0, 11.0, 12.0),
+            Vec4(13.0, 14.0, 15.0, 16.0),
+        ),
+    ];
+
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::write_slice(&mut stream, &input).unwrap();
+
+    ris_io::seek(&mut stream, SeekFrom::Start(0)).unwrap();
+    let mut output = [Mat4::default(); 3];
+    ris_io::read_slice(&mut stream, &mut output).unwrap();
+
+    assert_eq!(input, output);
+}
+
+#[test]
+fn should_not_read_slice_when_stream_has_not_enough_bytes() {
+    let mut stream = Cursor::new(vec![1, 2, 3, 4, 5, 6, 7]);
+    let mut output = [0u32; 2];
+    assert!(ris_io::read_slice(&mut stream, &mut output).is_err());
+}
@@ -0,0 +1,174 @@
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::rc::Rc;
+
+use ris_data::asset_id::AssetId;
+use ris_io::FatPtr;
+use ris_io::RisDeserialize;
+use ris_io::RisSerialize;
+use ris_math::matrix::Mat4;
+use ris_math::quaternion::Quat;
+use ris_math::vector::Bvec3;
+use ris_math::vector::Vec2;
+use ris_math::vector::Vec3;
+use ris_rng::rng::Rng;
+use ris_rng::rng::Seed;
+use ris_util::testing;
+use ris_util::testing::miri_choose;
+
+#[derive(Debug, PartialEq, RisSerialize, RisDeserialize)]
+struct Named {
+    position: Vec3,
+    rotation: Quat,
+    name: String,
+    children: Vec<Unnamed>,
+    parent: Option<AssetId>,
+}
+
+#[derive(Debug, PartialEq, RisSerialize, RisDeserialize)]
+struct Unnamed(u16, i64, bool);
+
+#[derive(Debug, PartialEq, RisSerialize, RisDeserialize)]
+struct Unit;
+
+#[derive(Debug, PartialEq, RisSerialize, RisDeserialize)]
+struct Generic<T> {
+    value: T,
+    values: [T; 2],
+}
+
+fn roundtrip<T: RisSerialize + RisDeserialize + PartialEq + Debug>(input: T) -> FatPtr {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::seek(&mut stream, SeekFrom::Start(3)).unwrap();
+    let ptr = ris_io::serialize(&mut stream, &input).unwrap();
+    let end = ris_io::seek(&mut stream, SeekFrom::Current(0)).unwrap();
+    assert_eq!(ptr.addr, 3);
+    assert_eq!(ptr.end(), end);
+
+    ris_io::seek(&mut stream, SeekFrom::Start(3)).unwrap();
+    let output = ris_io::deserialize::<T>(&mut stream).unwrap();
+    assert_eq!(input, output);
+    assert_eq!(
+        ris_io::seek(&mut stream, SeekFrom::Current(0)).unwrap(),
+        end
+    );
+
+    ptr
+}
+
+#[test]
+fn should_serialize_primitives() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let rng = Rc::new(RefCell::new(Rng::new(seed)));
+
+    testing::repeat(miri_choose(1_000, 10), move |_| {
+        let mut rng = rng.borrow_mut();
+        assert_eq!(roundtrip(rng.next_u8()).len, 1);
+        assert_eq!(roundtrip(rng.next_u32() as u16).len, 2);
+        assert_eq!(roundtrip(rng.next_u32()).len, 4);
+        assert_eq!(roundtrip(rng.next_u32() as u64 * 3).len, 8);
+        assert_eq!(roundtrip(rng.next_i32() as i8).len, 1);
+        assert_eq!(roundtrip(rng.next_i32() as i16).len, 2);
+        assert_eq!(roundtrip(rng.next_i32()).len, 4);
+        assert_eq!(roundtrip(rng.next_i32() as i64 * 3).len, 8);
+        assert_eq!(roundtrip(rng.next_f32()).len, 4);
+        assert_eq!(roundtrip(rng.next_f32() as f64).len, 8);
+        assert_eq!(roundtrip(rng.next_u32() as usize).len, 4);
+        assert_eq!(roundtrip(rng.next_i32() as isize).len, 4);
+        assert_eq!(roundtrip(rng.next_bool()).len, 1);
+    });
+}
+
+#[test]
+fn should_serialize_math_types() {
+    let seed = Seed::new().unwrap();
+    println!("seed: {:?}", seed);
+    let rng = Rc::new(RefCell::new(Rng::new(seed)));
+
+    testing::repeat(miri_choose(1_000, 10), move |_| {
+        let mut rng = rng.borrow_mut();
+        assert_eq!(roundtrip(rng.next_pos_2()).len, 8);
+        assert_eq!(roundtrip(rng.next_pos_3()).len, 12);
+        assert_eq!(roundtrip(rng.next_pos_4()).len, 16);
+        assert_eq!(roundtrip(rng.next_rot()).len, 16);
+        assert_eq!(
+            roundtrip(Bvec3(rng.next_bool(), rng.next_bool(), rng.next_bool())).len,
+            1
+        );
+
+        let m = Mat4(
+            rng.next_pos_4(),
+            rng.next_pos_4(),
+            rng.next_pos_4(),
+            rng.next_pos_4(),
+        );
+        assert_eq!(roundtrip(m).len, 64);
+    });
+}
+
+#[test]
+fn should_serialize_containers() {
+    assert_eq!(roundtrip(String::from("hello")).len, 4 + 5);
+    assert_eq!(roundtrip(String::new()).len, 4);
+    assert_eq!(roundtrip(vec![1u16, 2, 3]).len, 4 + 6);
+    assert_eq!(roundtrip(Vec::<u16>::new()).len, 4);
+    assert_eq!(
+        roundtrip(vec![vec![String::from("a")], vec![]]).len,
+        4 + 9 + 4
+    );
+    assert_eq!(roundtrip(Some(42u32)).len, 1 + 4);
+    assert_eq!(roundtrip(None::<u32>).len, 1);
+    assert_eq!(roundtrip([7u8; 16]).len, 16);
+    assert_eq!(roundtrip(FatPtr { addr: 1, len: 2 }).len, 16);
+    assert_eq!(roundtrip(AssetId::Index(42)).len, 1 + 4);
+    assert_eq!(roundtrip(AssetId::Path(String::from("a/b"))).len, 1 + 4 + 3);
+}
+
+#[test]
+fn should_serialize_derived_structs() {
+    let input = Named {
+        position: Vec3(1.0, 2.0, 3.0),
+        rotation: Quat::identity(),
+        name: String::from("named"),
+        children: vec![Unnamed(1, -2, true), Unnamed(3, -4, false)],
+        parent: Some(AssetId::Path(String::from("parent"))),
+    };
+    let expected_len = 12 + 16 + (4 + 5) + (4 + 2 * 11) + (1 + 1 + 4 + 6);
+    assert_eq!(roundtrip(input).len, expected_len);
+
+    assert_eq!(roundtrip(Unit).len, 0);
+
+    let input = Generic {
+        value: Vec2(1.0, 2.0),
+        values: [Vec2(3.0, 4.0), Vec2(5.0, 6.0)],
+    };
+    assert_eq!(roundtrip(input).len, 24);
+}
+
+#[test]
+fn should_serialize_fields_in_declaration_order() {
+    let mut stream = Cursor::new(Vec::new());
+    ris_io::serialize(&mut stream, &Unnamed(0x0102, 0x03, true)).unwrap();
+
+    let bytes = stream.into_inner();
+    assert_eq!(bytes, vec![0x02, 0x01, 0x03, 0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn should_not_deserialize_invalid_data() {
+    let mut stream = Cursor::new(vec![2, 0, 0, 0, 0]);
+    assert!(ris_io::deserialize::<AssetId>(&mut stream).is_err());
+
+    let mut stream = Cursor::new(vec![2]);
+    assert!(ris_io::deserialize::<Option<u8>>(&mut stream).is_err());
+
+    // the len claims more elements than there are bytes
+    let mut stream = Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 1, 2]);
+    assert!(ris_io::deserialize::<Vec<u8>>(&mut stream).is_err());
+
+    let mut stream = Cursor::new(vec![1, 2, 3]);
+    assert!(ris_io::deserialize::<Named>(&mut stream).is_err());
+}
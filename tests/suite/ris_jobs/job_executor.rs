@@ -0,0 +1,108 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+
+use ris_jobs::job_executor;
+use ris_jobs::job_future::SettableJobFuture;
+use ris_jobs::job_system;
+use ris_util::testing::miri_choose;
+use ris_util::testing::repeat;
+
+#[test]
+fn should_run_async_task() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let future = job_executor::spawn(async { 42 });
+    let result = future.wait(None).unwrap();
+    assert_eq!(result, 42);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_await_jobs() {
+    repeat(miri_choose(100, 5), |_| {
+        let job_system = job_system::init(100, 10, 4, false);
+
+        let future = job_executor::spawn(async {
+            let bytes = job_system::submit(|| vec![1u8, 2, 3]).await;
+            let sum =
+                job_system::submit(move || bytes.iter().map(|x| *x as u32).sum::<u32>()).await;
+            let references = (0..sum)
+                .map(|i| job_system::submit(move || i * 2))
+                .collect::<Vec<_>>();
+
+            let mut result = Vec::new();
+            for reference in references {
+                result.push(reference.await);
+            }
+
+            result
+        });
+
+        let result = future.wait(None).unwrap();
+        assert_eq!(result, vec![0, 2, 4, 6, 8, 10]);
+
+        drop(job_system);
+    })
+}
+
+#[test]
+fn should_await_future_set_by_other_thread() {
+    repeat(miri_choose(100, 5), |_| {
+        let job_system = job_system::init(100, 10, 2, false);
+
+        let (settable, future) = SettableJobFuture::new();
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let polls_copy = polls.clone();
+        let task = job_executor::spawn(async move {
+            polls_copy.fetch_add(1, Ordering::SeqCst);
+            future.await + 1
+        });
+
+        let set_handle = thread::spawn(move || {
+            thread::yield_now();
+            settable.set(41);
+        });
+
+        let result = task.wait(None).unwrap();
+        set_handle.join().unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(polls.load(Ordering::SeqCst), 1);
+
+        drop(job_system);
+    })
+}
+
+#[test]
+fn should_run_async_task_without_job_system() {
+    let (settable, future) = SettableJobFuture::<i32>::new();
+
+    let task = job_executor::spawn(async move { future.await * 2 });
+    let task = task.try_take().unwrap_err();
+
+    settable.set(21);
+
+    let result = task
+        .try_take()
+        .unwrap_or_else(|_| panic!("task is not done"));
+    assert_eq!(result, 42);
+}
+
+#[test]
+fn should_block_on_async_task() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let result = job_executor::block_on(async {
+        let a = job_executor::spawn(async { 20 });
+        let b = job_system::submit(|| 22);
+        a.await + b.await
+    });
+
+    assert_eq!(result, 42);
+
+    drop(job_system);
+}
@@ -5,6 +5,8 @@ use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
 
+use ris_jobs::job_executor;
+use ris_jobs::job_future::JobFuture;
 use ris_jobs::job_future::SettableJobFuture;
 use ris_util::testing::miri_choose;
 use ris_util::testing::repeat;
@@ -62,3 +64,51 @@ fn should_timeout() {
         assert!(timed_out);
     })
 }
+
+#[test]
+fn should_run_continuation_on_setting_thread() {
+    repeat(miri_choose(1_000, 10), |_| {
+        let (settable, future) = SettableJobFuture::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let events_clone = events.clone();
+        let future = future.then(move |x: i32| {
+            events_clone.lock().unwrap().push("continued");
+            x + 1
+        });
+
+        assert!(events.lock().unwrap().is_empty());
+
+        let set_handle = thread::spawn(move || settable.set(41));
+        set_handle.join().unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0], "continued");
+        assert_eq!(future.try_take().ok(), Some(42));
+    })
+}
+
+#[test]
+fn should_wake_when_set() {
+    repeat(miri_choose(1_000, 10), |_| {
+        let (settable, future) = SettableJobFuture::new();
+
+        let set_handle = thread::spawn(move || {
+            thread::yield_now();
+            settable.set(42);
+        });
+
+        let result = job_executor::block_on(future);
+        set_handle.join().unwrap();
+
+        assert_eq!(result, 42);
+    })
+}
+
+#[test]
+fn should_be_ready_immediately_when_done() {
+    let future = JobFuture::<i32>::done();
+    let result = job_executor::block_on(future);
+    assert_eq!(result, 0);
+}
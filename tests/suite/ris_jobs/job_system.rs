@@ -5,7 +5,11 @@ use std::thread;
 use std::time::Duration;
 use std::time::Instant;
 
+use ris_jobs::job_future::JobDependency;
+use ris_jobs::job_future::JobFuture;
 use ris_jobs::job_system;
+use ris_jobs::job_system::FullBufferPolicy;
+use ris_jobs::job_system::JobPriority;
 use ris_util::testing::miri_choose;
 use ris_util::testing::repeat;
 use ris_util::testing::retry;
@@ -311,3 +315,352 @@ fn should_read_and_write_rw_lock() {
         assert_eq!(results[results.len() - 1], end_result);
     });
 }
+
+#[test]
+fn should_submit_after_dependencies() {
+    repeat(5, |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let mut futures = Vec::new();
+
+        for i in 0..miri_choose(100, 5) {
+            let results_copy = results.clone();
+            let future = job_system::submit(move || {
+                results_copy.lock().unwrap().push(i);
+            });
+
+            futures.push(future);
+        }
+
+        let other_type = job_system::submit(|| "hello world");
+
+        let mut dependencies = futures
+            .iter()
+            .map(|x| x as &dyn JobDependency)
+            .collect::<Vec<_>>();
+        dependencies.push(&other_type);
+
+        let results_copy = results.clone();
+        let future = job_system::submit_after(&dependencies, move || {
+            let results = results_copy.lock().unwrap();
+            results.len()
+        });
+
+        let len = future.wait(None).unwrap();
+        assert_eq!(len, miri_choose(100, 5));
+        assert_eq!(other_type.wait(None).unwrap(), "hello world");
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_submit_after_completed_dependencies() {
+    let job_system = job_system::init(100, 10, 1, false);
+
+    let future = job_system::submit_after(&[], || 1);
+    assert_eq!(future.wait(None).unwrap(), 1);
+
+    let done = JobFuture::<()>::done();
+    let future = job_system::submit_after(&[&done, &done], || 2);
+    assert_eq!(future.wait(None).unwrap(), 2);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_chain_continuations() {
+    repeat(5, |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let mut futures = Vec::new();
+        for i in 0..miri_choose(100, 5) {
+            let future = job_system::submit(move || i)
+                .then(|x| x + 1)
+                .then(|x| x * 2);
+            futures.push(future);
+        }
+
+        for (i, future) in futures.into_iter().enumerate() {
+            assert_eq!(future.wait(None).unwrap(), (i + 1) * 2);
+        }
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_spawn_scoped_jobs_borrowing_stack_data() {
+    repeat(miri_choose(5, 2), |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let mut values = (0..miri_choose(1000, 20)).collect::<Vec<usize>>();
+        let sums = Mutex::new(Vec::new());
+
+        job_system::scope(|s| {
+            for chunk in values.chunks(7) {
+                let sums = &sums;
+                s.spawn(move || {
+                    let sum = chunk.iter().sum::<usize>();
+                    sums.lock().unwrap().push(sum);
+                });
+            }
+        });
+
+        // the scope has ended, thus values may be mutated again
+        values.push(0);
+
+        let sums = sums.into_inner().unwrap();
+        assert_eq!(sums.len(), miri_choose(1000usize, 20).div_ceil(7));
+        assert_eq!(sums.iter().sum::<usize>(), values.iter().sum::<usize>(),);
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_return_results_of_scoped_jobs() {
+    repeat(miri_choose(5, 2), |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let text = String::from("hello world");
+
+        let (first, second) = job_system::scope(|s| {
+            let first = s.spawn(|| &text[..5]);
+            let second = s.spawn(|| &text[6..]);
+
+            (first.wait(None).unwrap(), second.wait(None).unwrap())
+        });
+
+        assert_eq!(first, "hello");
+        assert_eq!(second, "world");
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_spawn_scoped_jobs_within_scoped_jobs() {
+    repeat(miri_choose(5, 2), |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let count = miri_choose(100, 5);
+        let results = Mutex::new(Vec::new());
+
+        job_system::scope(|s| {
+            for i in 0..count {
+                let results = &results;
+                s.spawn(move || {
+                    results.lock().unwrap().push(i);
+
+                    s.spawn(move || {
+                        results.lock().unwrap().push(i + count);
+                    });
+                });
+            }
+        });
+
+        let results = results.into_inner().unwrap();
+        assert_eq!(results.len(), count * 2);
+        for i in 0..count * 2 {
+            assert!(results.contains(&i));
+        }
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_resume_panic_of_scoped_job() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let done = Mutex::new(Vec::new());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        job_system::scope(|s| {
+            s.spawn(|| panic!("scoped job panicked on purpose"));
+
+            for i in 0..miri_choose(10, 3) {
+                let done = &done;
+                s.spawn(move || done.lock().unwrap().push(i));
+            }
+        });
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(done.into_inner().unwrap().len(), miri_choose(10, 3));
+
+    drop(job_system);
+}
+
+#[test]
+fn should_resume_panic_of_scoped_job_when_waiting_on_it() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        job_system::scope(|s| {
+            let future = s.spawn(|| -> usize { panic!("scoped job panicked on purpose") });
+            future.wait(None)
+        })
+    }));
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"scoped job panicked on purpose"),
+    );
+
+    drop(job_system);
+}
+
+#[test]
+fn should_schedule_continuations_and_scoped_jobs_with_parent_priority() {
+    let job_system = job_system::init(100, 10, 1, false);
+
+    assert_eq!(job_system::current_priority(), JobPriority::Normal);
+
+    let continuation = job_system::submit_with_priority(JobPriority::Background, || ())
+        .then(|_| job_system::current_priority());
+    assert_eq!(continuation.wait(None).unwrap(), JobPriority::Background);
+
+    let after = job_system::submit_with_priority(JobPriority::High, || ());
+    let after = job_system::submit_after(&[&after], job_system::current_priority);
+    assert_eq!(after.wait(None).unwrap(), JobPriority::High);
+
+    let scoped = job_system::submit_with_priority(JobPriority::High, || {
+        job_system::scope(|s| s.spawn(job_system::current_priority).wait(None).unwrap())
+    });
+    assert_eq!(scoped.wait(None).unwrap(), JobPriority::High);
+
+    assert_eq!(job_system::current_priority(), JobPriority::Normal);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_prefer_higher_priorities() {
+    let job_system = job_system::init(100, 10, 1, false);
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let mut futures = Vec::new();
+
+    for priority in [
+        JobPriority::Background,
+        JobPriority::Normal,
+        JobPriority::High,
+        JobPriority::Background,
+        JobPriority::High,
+    ] {
+        let results_copy = results.clone();
+        let future = job_system::submit_with_priority(priority, move || {
+            results_copy.lock().unwrap().push(priority);
+        });
+
+        futures.push(future);
+    }
+
+    for _ in 0..5 {
+        job_system::run_pending_job(file!(), line!());
+    }
+
+    assert_eq!(
+        *results.lock().unwrap(),
+        vec![
+            JobPriority::High,
+            JobPriority::High,
+            JobPriority::Normal,
+            JobPriority::Background,
+            JobPriority::Background,
+        ],
+    );
+
+    let stats = job_system::stats();
+    assert_eq!(stats.submitted, [2, 1, 2]);
+    assert_eq!(stats.invoked, [2, 1, 2]);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_run_inline_when_buffer_is_full() {
+    let job_system = job_system::init(4, 10, 1, false);
+    job_system::set_full_buffer_policy(FullBufferPolicy::RunInline);
+    assert_eq!(
+        job_system::full_buffer_policy(),
+        FullBufferPolicy::RunInline
+    );
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let mut futures = Vec::new();
+
+    for i in 0..10 {
+        let results_copy = results.clone();
+        let future = job_system::submit(move || results_copy.lock().unwrap().push(i));
+        futures.push(future);
+    }
+
+    assert_eq!(*results.lock().unwrap(), vec![4, 5, 6, 7, 8, 9]);
+
+    let stats = job_system::stats();
+    assert_eq!(stats.submitted, [0, 10, 0]);
+    assert_eq!(stats.run_inline, 6);
+
+    drop(job_system);
+    assert_eq!(results.lock().unwrap().len(), 10);
+}
+
+#[test]
+fn should_block_when_buffer_is_full() {
+    let job_system = job_system::init(4, 10, 1, false);
+    assert_eq!(job_system::full_buffer_policy(), FullBufferPolicy::Block);
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let mut futures = Vec::new();
+
+    for i in 0..10 {
+        let results_copy = results.clone();
+        let future = job_system::submit(move || results_copy.lock().unwrap().push(i));
+        futures.push(future);
+    }
+
+    assert_eq!(results.lock().unwrap().len(), 6);
+
+    let stats = job_system::stats();
+    assert_eq!(stats.submitted, [0, 10, 0]);
+    assert_eq!(stats.blocked, 6);
+
+    drop(job_system);
+    assert_eq!(results.lock().unwrap().len(), 10);
+}
+
+#[test]
+fn should_grow_when_buffer_is_full() {
+    let job_system = job_system::init(4, 10, 1, false);
+    job_system::set_full_buffer_policy(FullBufferPolicy::Grow);
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let mut futures = Vec::new();
+
+    for i in 0..10 {
+        let results_copy = results.clone();
+        let future = job_system::submit(move || results_copy.lock().unwrap().push(i));
+        futures.push(future);
+    }
+
+    assert!(results.lock().unwrap().is_empty());
+
+    let stats = job_system::stats();
+    assert_eq!(stats.submitted, [0, 10, 0]);
+    assert_eq!(stats.grown, 6);
+    assert_eq!(stats.overflow, [0, 6, 0]);
+
+    for future in futures {
+        future.wait(None).unwrap();
+    }
+
+    let mut results = results.lock().unwrap().clone();
+    results.sort();
+    assert_eq!(results, (0..10).collect::<Vec<_>>());
+
+    drop(job_system);
+}
@@ -0,0 +1,226 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use ris_jobs::job_future::SettableJobFuture;
+use ris_jobs::job_system;
+use ris_jobs::job_system::JobPriority;
+use ris_jobs::job_trace::JobTrace;
+use ris_jobs::job_trace::TraceEvent;
+use ris_jobs::job_trace::TraceEventKind;
+use ris_jobs::job_trace::WorkerUtilization;
+use ris_util::testing::miri_choose;
+use ris_util::testing::retry;
+
+#[test]
+fn should_not_trace_by_default() {
+    let job_system = job_system::init(100, 10, 1, false);
+
+    assert!(!job_system::is_tracing());
+
+    let future = job_system::submit(|| ());
+    future.wait(None).unwrap();
+
+    let trace = job_system::trace();
+    assert_eq!(trace.workers.len(), 1);
+    assert!(trace.workers[0].is_empty());
+
+    drop(job_system);
+}
+
+#[test]
+fn should_trace_jobs_and_spin() {
+    let job_system = job_system::init(100, 10, 1, false);
+    job_system::set_tracing(true);
+
+    let mut futures = Vec::new();
+    for priority in JobPriority::ALL {
+        futures.push(job_system::submit_with_priority(priority, || ()));
+    }
+
+    for future in futures {
+        future.wait(None).unwrap();
+    }
+
+    // nothing left to run, thus this spins
+    job_system::run_pending_job(file!(), line!());
+    job_system::run_pending_job(file!(), line!());
+    let future = job_system::submit(|| ());
+    future.wait(None).unwrap();
+
+    job_system::set_tracing(false);
+
+    let trace = job_system::trace();
+    let kinds = trace.workers[0].iter().map(|x| x.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            job_kind(JobPriority::High),
+            job_kind(JobPriority::Normal),
+            job_kind(JobPriority::Background),
+            TraceEventKind::Spin,
+            job_kind(JobPriority::Normal),
+        ],
+    );
+
+    for pair in trace.workers[0].windows(2) {
+        assert!(pair[0].start + pair[0].duration <= pair[1].start);
+    }
+
+    drop(job_system);
+}
+
+#[test]
+fn should_trace_steals_and_idle() {
+    retry(miri_choose(10, 2), || {
+        let job_system = job_system::init(100, 10, 2, false);
+        job_system::set_tracing(true);
+
+        let is_running = Arc::new(AtomicBool::new(true));
+        let is_running_copy = is_running.clone();
+        let future = job_system::submit(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            is_running_copy.store(false, Ordering::SeqCst);
+        });
+
+        // the other worker steals the job, while this thread waits without running any jobs
+        while is_running.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        future.wait(None).unwrap();
+
+        // the job is recorded after the future was set
+        let start = Instant::now();
+        while job_system::utilization()[1].busy.is_zero() {
+            assert!(start.elapsed() < Duration::from_secs(1));
+            std::thread::yield_now();
+        }
+
+        // give the worker some time to idle
+        std::thread::sleep(Duration::from_millis(5));
+
+        let trace = job_system::trace();
+        let utilization = job_system::utilization();
+        drop(job_system);
+
+        let worker = &trace.workers[1];
+        assert!(worker
+            .iter()
+            .any(|x| x.kind == TraceEventKind::Steal { from: 0 }));
+        assert!(worker.iter().any(|x| x.kind
+            == TraceEventKind::Job {
+                priority: JobPriority::Normal,
+                stolen: true,
+            }));
+        assert!(worker.iter().any(|x| x.kind == TraceEventKind::Idle));
+
+        assert!(utilization[1].busy >= Duration::from_millis(10));
+        assert!(utilization[1].idle > Duration::ZERO);
+    });
+}
+
+#[test]
+fn should_not_count_nested_jobs_and_waits_as_busy() {
+    let job_system = job_system::init(100, 10, 1, false);
+
+    let start = Instant::now();
+    job_system::set_tracing(true);
+
+    let outer = job_system::submit(|| {
+        // runs nested, while the outer job waits for it
+        let inner = job_system::submit(|| std::thread::sleep(Duration::from_millis(10)));
+        inner.wait(None).unwrap();
+
+        // nothing left to run, thus this spins inside the outer job
+        let (_settable_future, future) = SettableJobFuture::<()>::new();
+        assert!(future.wait(Some(Duration::from_millis(10))).is_err());
+    });
+    outer.wait(None).unwrap();
+
+    let utilization = job_system::utilization()[0];
+    let wall = start.elapsed();
+    job_system::set_tracing(false);
+
+    let total = utilization.busy + utilization.idle + utilization.spin;
+    assert!(total <= wall, "{:?} > {:?}", total, wall);
+    assert!(utilization.busy >= Duration::from_millis(10));
+    // the spin starts once the first attempt found no job, thus slightly after the timeout
+    assert!(utilization.spin > Duration::ZERO);
+
+    let fractions = (utilization.busy.as_secs_f64()
+        + utilization.idle.as_secs_f64()
+        + utilization.spin.as_secs_f64())
+        / wall.as_secs_f64();
+    assert!(fractions <= 1.0, "{}", fractions);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_calculate_busy_fraction() {
+    let utilization = WorkerUtilization {
+        busy: Duration::from_millis(30),
+        idle: Duration::from_millis(50),
+        spin: Duration::from_millis(20),
+    };
+    assert!((utilization.busy_fraction() - 0.3).abs() < 0.000_001);
+    assert_eq!(WorkerUtilization::default().busy_fraction(), 0.0);
+
+    let delta = utilization
+        - WorkerUtilization {
+            busy: Duration::from_millis(10),
+            idle: Duration::from_millis(50),
+            spin: Duration::from_millis(20),
+        };
+    assert_eq!(delta.busy_fraction(), 1.0);
+}
+
+#[test]
+fn should_export_chrome_json() {
+    let trace = JobTrace {
+        workers: vec![
+            vec![TraceEvent {
+                kind: job_kind(JobPriority::High),
+                start: Duration::from_micros(5),
+                duration: Duration::from_nanos(1_500),
+            }],
+            vec![
+                TraceEvent {
+                    kind: TraceEventKind::Steal { from: 0 },
+                    start: Duration::from_micros(7),
+                    duration: Duration::ZERO,
+                },
+                TraceEvent {
+                    kind: TraceEventKind::Idle,
+                    start: Duration::from_micros(10),
+                    duration: Duration::from_micros(2),
+                },
+            ],
+        ],
+        dropped_events: 0,
+    };
+
+    let json = trace.to_chrome_json();
+
+    let expected = [
+        "{\"traceEvents\":[",
+        "{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":0,\"args\":{\"name\":\"worker 0\"}},",
+        "{\"name\":\"job\",\"cat\":\"high\",\"ph\":\"X\",\"ts\":5.000,\"dur\":1.500,\"pid\":0,\"tid\":0,\"args\":{\"priority\":\"high\",\"stolen\":false}},",
+        "{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":1,\"args\":{\"name\":\"worker 1\"}},",
+        "{\"name\":\"steal\",\"ph\":\"i\",\"s\":\"t\",\"ts\":7.000,\"pid\":0,\"tid\":1,\"args\":{\"from\":0}},",
+        "{\"name\":\"idle\",\"ph\":\"X\",\"ts\":10.000,\"dur\":2.000,\"pid\":0,\"tid\":1}",
+        "],\"displayTimeUnit\":\"ms\"}",
+    ]
+    .join("\n");
+
+    assert_eq!(json, expected);
+}
+
+fn job_kind(priority: JobPriority) -> TraceEventKind {
+    TraceEventKind::Job {
+        priority,
+        stolen: false,
+    }
+}
@@ -1,4 +1,7 @@
 pub mod job;
 pub mod job_buffer;
+pub mod job_executor;
 pub mod job_future;
 pub mod job_system;
+pub mod job_trace;
+pub mod parallel;
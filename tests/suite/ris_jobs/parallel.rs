@@ -0,0 +1,153 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use ris_jobs::job_system;
+use ris_jobs::parallel;
+use ris_util::testing::miri_choose;
+use ris_util::testing::repeat;
+
+#[test]
+fn should_run_parallel_for() {
+    repeat(5, |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let count = miri_choose(1000, 20);
+        let visited = (0..count).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+        parallel::parallel_for(10..count, 7, |i| {
+            visited[i].fetch_add(1, Ordering::SeqCst);
+        });
+
+        for (i, value) in visited.iter().enumerate() {
+            let expected = if i < 10 { 0 } else { 1 };
+            assert_eq!(value.load(Ordering::SeqCst), expected, "{}", i);
+        }
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_run_parallel_for_on_empty_range_and_zero_grain() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let counter = AtomicUsize::new(0);
+    parallel::parallel_for(5..5, 10, |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+    parallel::parallel_for(0..10, 0, |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    assert_eq!(counter.load(Ordering::SeqCst), 10);
+
+    drop(job_system);
+}
+
+#[test]
+fn should_run_parallel_for_on_non_worker_thread() {
+    let counter = AtomicUsize::new(0);
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            parallel::parallel_for(0..100, 3, |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        });
+    });
+
+    assert_eq!(counter.load(Ordering::SeqCst), 100);
+}
+
+#[test]
+fn should_run_par_chunks_mut() {
+    repeat(5, |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let count = miri_choose(1000, 25);
+        let mut values = vec![0; count];
+
+        parallel::par_chunks_mut(&mut values, 8, |chunk, values| {
+            assert!(values.len() <= 8);
+            for (i, value) in values.iter_mut().enumerate() {
+                *value = chunk * 8 + i;
+            }
+        });
+
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(*value, i);
+        }
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_run_parallel_reduce() {
+    repeat(5, |_| {
+        let job_system = job_system::init(100, 10, 10, false);
+
+        let count = miri_choose(1000, 30);
+        let values = (0..count).collect::<Vec<_>>();
+
+        let sum = parallel::parallel_reduce(0..count, 16, 0, |i| values[i], |a, b| a + b);
+        assert_eq!(sum, count * (count - 1) / 2);
+
+        // not commutative, but associative
+        let concatenated = parallel::parallel_reduce(
+            0..count,
+            16,
+            String::new(),
+            |i| format!("{},", i),
+            |a, b| a + &b,
+        );
+        let expected = (0..count).map(|i| format!("{},", i)).collect::<String>();
+        assert_eq!(concatenated, expected);
+
+        let empty = parallel::parallel_reduce(0..0, 16, 42, |i| i, |a, b| a + b);
+        assert_eq!(empty, 42);
+
+        drop(job_system);
+    });
+}
+
+#[test]
+fn should_resume_panic_of_chunk() {
+    let job_system = job_system::init(100, 10, 4, false);
+
+    let is_panicking = AtomicBool::new(false);
+    let visited = AtomicUsize::new(0);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        parallel::parallel_for(0..10, 5, |i| {
+            if i == 0 {
+                // the first chunk runs on the calling thread. it waits, such that the second
+                // chunk is stolen and panics on another worker
+                let start = Instant::now();
+                while !is_panicking.load(Ordering::SeqCst) {
+                    assert!(start.elapsed() < Duration::from_secs(5));
+                    std::hint::spin_loop();
+                }
+            }
+
+            if i == 5 {
+                is_panicking.store(true, Ordering::SeqCst);
+                panic!("chunk panicked on purpose");
+            }
+
+            visited.fetch_add(1, Ordering::SeqCst);
+        });
+    }));
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"chunk panicked on purpose"),
+    );
+    assert_eq!(visited.load(Ordering::SeqCst), 5);
+
+    drop(job_system);
+}
@@ -0,0 +1,98 @@
+use ris_log::constructed_log_message::ConstructedLogMessage;
+use ris_log::log_field::LogField;
+use ris_log::log_field::LogValue;
+use ris_log::log_level::LogLevel;
+use ris_log::log_message::LogMessage;
+
+fn message(fields: Vec<LogField>) -> ConstructedLogMessage {
+    ConstructedLogMessage {
+        package: String::from("ris_test"),
+        file: String::from("src/test.rs"),
+        line: 42,
+        timestamp: ris_log::log::get_timestamp(),
+        priority: LogLevel::Info,
+        message: String::from("loaded asset"),
+        fields,
+    }
+}
+
+#[test]
+fn should_format_without_fields() {
+    let message = message(Vec::new());
+
+    let formatted = message.fmt(false);
+
+    let first_line = formatted.lines().next().unwrap();
+    assert!(first_line.ends_with("Info: loaded asset"));
+}
+
+#[test]
+fn should_format_fields() {
+    let message = message(vec![
+        LogField::new("asset_id", 7usize),
+        LogField::new("ms", 1.5f32),
+        LogField::new("path", "a/b.txt"),
+        LogField::new("name", "with space"),
+        LogField::new("empty", ""),
+        LogField::new("ok", true),
+    ]);
+
+    let formatted = message.fmt(false);
+
+    let first_line = formatted.lines().next().unwrap();
+    assert!(first_line.ends_with(
+        "Info: loaded asset asset_id=7 ms=1.5 path=a/b.txt name=\"with space\" empty=\"\" ok=true"
+    ));
+}
+
+#[test]
+fn should_convert_values() {
+    assert_eq!(LogValue::from(-3i8), LogValue::Int(-3));
+    assert_eq!(LogValue::from(3u16), LogValue::UInt(3));
+    assert_eq!(LogValue::from(0.5f64), LogValue::Float(0.5));
+    assert_eq!(LogValue::from(false), LogValue::Bool(false));
+    assert_eq!(
+        LogValue::from(String::from("a")),
+        LogValue::String(String::from("a"))
+    );
+    assert_eq!(
+        LogField::debug("x", &Some(1)).value,
+        LogValue::String(String::from("Some(1)"))
+    );
+    assert_eq!(
+        LogField::display("x", &'c').value,
+        LogValue::String(String::from("c"))
+    );
+}
+
+#[test]
+fn should_format_json() {
+    let message = message(vec![
+        LogField::new("asset_id", 7usize),
+        LogField::new("offset", -2i32),
+        LogField::new("ms", 1.5f32),
+        LogField::new("nan", f32::NAN),
+        LogField::new("ok", true),
+        LogField::new("path", "C:\\a \"b\"\n"),
+    ]);
+
+    let json = message.to_json();
+
+    let expected = format!(
+        concat!(
+            "{{\"timestamp\":\"{}\",\"level\":\"Info\",\"package\":\"ris_test\",",
+            "\"file\":\"src/test.rs\",\"line\":42,\"message\":\"loaded asset\",",
+            "\"fields\":{{\"asset_id\":7,\"offset\":-2,\"ms\":1.5,\"nan\":null,\"ok\":true,",
+            "\"path\":\"C:\\\\a \\\"b\\\"\\n\"}}}}",
+        ),
+        message.timestamp.to_rfc3339(),
+    );
+    assert_eq!(json, expected);
+    assert!(!json.contains('\n'));
+}
+
+#[test]
+fn should_escape_control_characters_in_json() {
+    let json = LogMessage::Plain(String::from("a\u{1}\tb")).to_json();
+    assert_eq!(json, "{\"message\":\"a\\u0001\\tb\"}");
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use ris_log::constructed_log_message::ConstructedLogMessage;
+use ris_log::log::IAppender;
+use ris_log::log_field::LogField;
+use ris_log::log_field::LogValue;
+use ris_log::log_filter::LogFilter;
+use ris_log::log_level::LogLevel;
+use ris_log::log_message::LogMessage;
+use ris_log::log_queue::OverflowPolicy;
+use ris_log::log_queue::QueueOptions;
+
+// the log is global, thus tests which initialize it must not run in parallel
+static LOCK: Mutex<()> = Mutex::new(());
+
+struct TestAppender {
+    messages: Arc<Mutex<Vec<LogMessage>>>,
+}
+
+impl IAppender for TestAppender {
+    fn print(&mut self, message: &LogMessage) {
+        self.messages.lock().unwrap().push(message.clone());
+    }
+}
+
+fn messages_starting_with(messages: &[LogMessage], prefix: &str) -> Vec<ConstructedLogMessage> {
+    messages
+        .iter()
+        .filter_map(|x| match x {
+            LogMessage::Constructed(x) if x.message.starts_with(prefix) => Some(x.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn should_log_fields() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let appender = Box::new(TestAppender {
+        messages: messages.clone(),
+    });
+
+    let log_guard = ris_log::log::init(LogLevel::Trace, vec![appender]);
+
+    let id = Some(7);
+    let ms = 12u64;
+    let path = String::from("a/b.txt");
+    ris_log::info!("should_log_fields plain {}", 1);
+    ris_log::info!(ms = ms; "should_log_fields single");
+    ris_log::warning!(asset_id = ?id, ms = ms, path = %path, valid = true; "should_log_fields {}", "many",);
+
+    drop(log_guard);
+
+    let messages = messages.lock().unwrap();
+    let constructed = messages_starting_with(&messages, "should_log_fields");
+
+    assert_eq!(constructed.len(), 3);
+
+    assert_eq!(constructed[0].message, "should_log_fields plain 1");
+    assert!(constructed[0].fields.is_empty());
+
+    assert_eq!(constructed[1].message, "should_log_fields single");
+    assert_eq!(constructed[1].fields, vec![LogField::new("ms", 12u64)]);
+
+    assert_eq!(constructed[2].message, "should_log_fields many");
+    assert_eq!(constructed[2].priority, LogLevel::Warning);
+    assert_eq!(
+        constructed[2].fields,
+        vec![
+            LogField::new("asset_id", "Some(7)"),
+            LogField::new("ms", LogValue::UInt(12)),
+            LogField::new("path", "a/b.txt"),
+            LogField::new("valid", true),
+        ]
+    );
+}
+
+#[test]
+fn should_filter_by_package() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let appender = Box::new(TestAppender {
+        messages: messages.clone(),
+    });
+
+    let filter = "trace,ris_engine=warning".parse::<LogFilter>().unwrap();
+    let log_guard = ris_log::log::init(filter, vec![appender]);
+
+    ris_log::info!("should_filter_by_package info");
+    ris_log::warning!("should_filter_by_package warning");
+
+    let filter = "trace,tests/suite/ris_log=debug"
+        .parse::<LogFilter>()
+        .unwrap();
+    ris_log::log::set_filter(filter.clone());
+    assert_eq!(ris_log::log::filter(), filter);
+
+    ris_log::trace!("should_filter_by_package trace");
+    ris_log::debug!("should_filter_by_package debug");
+
+    drop(log_guard);
+
+    let messages = messages.lock().unwrap();
+    let constructed = messages_starting_with(&messages, "should_filter_by_package");
+
+    let logged = constructed
+        .iter()
+        .map(|x| x.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        logged,
+        vec![
+            "should_filter_by_package warning",
+            "should_filter_by_package debug",
+        ]
+    );
+}
+
+struct SlowAppender {
+    messages: Arc<Mutex<Vec<LogMessage>>>,
+}
+
+impl IAppender for SlowAppender {
+    fn print(&mut self, message: &LogMessage) {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        self.messages.lock().unwrap().push(message.clone());
+    }
+}
+
+#[test]
+fn should_flush() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    assert!(ris_log::log::flush(std::time::Duration::ZERO));
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let appender = Box::new(SlowAppender {
+        messages: messages.clone(),
+    });
+
+    let log_guard = ris_log::log::init(LogLevel::Trace, vec![appender]);
+
+    for i in 0..5 {
+        ris_log::info!("should_flush {}", i);
+    }
+
+    let flushed = ris_log::log::flush(std::time::Duration::from_secs(10));
+    let constructed = messages_starting_with(&messages.lock().unwrap(), "should_flush");
+
+    drop(log_guard);
+
+    assert!(flushed);
+    assert_eq!(constructed.len(), 5);
+    assert_eq!(constructed[4].message, "should_flush 4");
+}
+
+#[test]
+fn should_report_dropped_messages() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let appender = Box::new(SlowAppender {
+        messages: messages.clone(),
+    });
+
+    let queue_options = QueueOptions {
+        capacity: 1,
+        policy: OverflowPolicy::DropOldest,
+        report_interval: std::time::Duration::ZERO,
+    };
+    let log_guard = ris_log::log::init_with_queue(LogLevel::Trace, vec![appender], queue_options);
+
+    for i in 0..20 {
+        ris_log::info!("should_report_dropped_messages {}", i);
+    }
+
+    let dropped_count = ris_log::log::dropped_count();
+    drop(log_guard);
+
+    assert!(dropped_count > 0);
+
+    let messages = messages.lock().unwrap();
+    let logged = messages_starting_with(&messages, "should_report_dropped_messages");
+    let reports = messages_starting_with(&messages, "log queue was full");
+
+    assert!(logged.len() < 20);
+    assert_eq!(
+        logged.last().unwrap().message,
+        "should_report_dropped_messages 19"
+    );
+
+    let reported = reports
+        .iter()
+        .map(|x| match x.fields[0].value {
+            LogValue::UInt(dropped) => dropped as usize,
+            _ => panic!("unexpected field {:?}", x.fields[0]),
+        })
+        .sum::<usize>();
+    assert_eq!(reported + logged.len(), 20);
+    assert_eq!(
+        reports.last().unwrap().fields[1],
+        LogField::new("total", reported)
+    );
+}
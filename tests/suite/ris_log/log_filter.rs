@@ -0,0 +1,106 @@
+use ris_log::log_filter::LogFilter;
+use ris_log::log_filter::LogTarget;
+use ris_log::log_level::LogLevel;
+
+#[test]
+fn should_parse_filter() {
+    let filter = "info, ris_asset=debug,ris_jobs=WARN,crates\\ris_io/src=off"
+        .parse::<LogFilter>()
+        .unwrap();
+
+    assert_eq!(filter.default, LogLevel::Info);
+    assert_eq!(
+        filter.targets,
+        vec![
+            LogTarget {
+                target: String::from("ris_asset"),
+                level: LogLevel::Debug,
+            },
+            LogTarget {
+                target: String::from("ris_jobs"),
+                level: LogLevel::Warning,
+            },
+            LogTarget {
+                target: String::from("crates/ris_io/src"),
+                level: LogLevel::None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_default_to_trace() {
+    let filter = "ris_asset=error".parse::<LogFilter>().unwrap();
+    assert_eq!(filter.default, LogLevel::Trace);
+
+    let filter = "".parse::<LogFilter>().unwrap();
+    assert_eq!(filter, LogFilter::default());
+}
+
+#[test]
+fn should_overwrite_duplicate_targets() {
+    let filter = "ris_asset=error,ris_asset=debug"
+        .parse::<LogFilter>()
+        .unwrap();
+
+    assert_eq!(filter.targets.len(), 1);
+    assert_eq!(filter.targets[0].level, LogLevel::Debug);
+}
+
+#[test]
+fn should_not_parse_invalid_filter() {
+    assert!("verbose".parse::<LogFilter>().is_err());
+    assert!("ris_asset=verbose".parse::<LogFilter>().is_err());
+    assert!("=debug".parse::<LogFilter>().is_err());
+    assert!("ris_asset::loader=debug".parse::<LogFilter>().is_err());
+    assert!("ris_asset#=debug".parse::<LogFilter>().is_err());
+}
+
+#[test]
+fn should_format_and_parse_roundtrip() {
+    let filter = "warning,ris_asset=debug,crates/ris_jobs/src/job_system.rs=none"
+        .parse::<LogFilter>()
+        .unwrap();
+
+    let formatted = filter.to_string();
+    let parsed = formatted.parse::<LogFilter>().unwrap();
+
+    assert_eq!(
+        formatted,
+        "warning,ris_asset=debug,crates/ris_jobs/src/job_system.rs=none"
+    );
+    assert_eq!(parsed, filter);
+}
+
+#[test]
+fn should_match_package_and_file() {
+    let filter = "warning,ris_asset=debug,crates/ris_asset/src/loader=error"
+        .parse::<LogFilter>()
+        .unwrap();
+
+    let asset = filter.level("ris_asset", "crates/ris_asset/src/asset_importer.rs");
+    let loader = filter.level("ris_asset", "crates/ris_asset/src/loader/scene_loader.rs");
+    let windows = filter.level("ris_other", "crates\\ris_asset\\src\\loader\\a.rs");
+    let other = filter.level("ris_jobs", "crates/ris_jobs/src/job.rs");
+
+    assert_eq!(asset, LogLevel::Debug);
+    assert_eq!(loader, LogLevel::Error);
+    assert_eq!(windows, LogLevel::Error);
+    assert_eq!(other, LogLevel::Warning);
+
+    // shorter than the target
+    let short = filter.level("ris_other", "crates/ris_asset");
+    assert_eq!(short, LogLevel::Warning);
+}
+
+#[test]
+fn should_enable_levels() {
+    let filter = "error,ris_asset=debug".parse::<LogFilter>().unwrap();
+
+    assert!(filter.is_enabled(LogLevel::Debug, "ris_asset", "a.rs"));
+    assert!(!filter.is_enabled(LogLevel::Trace, "ris_asset", "a.rs"));
+    assert!(!filter.is_enabled(LogLevel::Warning, "ris_jobs", "a.rs"));
+    assert!(filter.is_enabled(LogLevel::Fatal, "ris_jobs", "a.rs"));
+    assert!(!filter.is_enabled(LogLevel::None, "ris_jobs", "a.rs"));
+    assert_eq!(filter.min_level(), LogLevel::Debug);
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use ris_log::constructed_log_message::ConstructedLogMessage;
+use ris_log::log_level::LogLevel;
+use ris_log::log_message::LogMessage;
+use ris_log::log_queue::LogQueue;
+use ris_log::log_queue::OverflowPolicy;
+use ris_log::log_queue::QueueOptions;
+
+fn queue(capacity: usize, policy: OverflowPolicy) -> LogQueue {
+    LogQueue::new(QueueOptions {
+        capacity,
+        policy,
+        ..Default::default()
+    })
+}
+
+fn constructed(priority: LogLevel, message: &str) -> LogMessage {
+    LogMessage::Constructed(ConstructedLogMessage {
+        package: String::from("ris_test"),
+        file: String::from(file!()),
+        line: line!(),
+        timestamp: ris_log::log::get_timestamp(),
+        priority,
+        message: String::from(message),
+        fields: Vec::new(),
+    })
+}
+
+fn plain(message: &str) -> LogMessage {
+    LogMessage::Plain(String::from(message))
+}
+
+fn pop_strings(queue: &LogQueue) -> Vec<String> {
+    let batch = queue.pop_all(Duration::ZERO).unwrap();
+    queue.finish(batch.len());
+    batch
+        .iter()
+        .map(|x| match x {
+            LogMessage::Constructed(x) => x.message.clone(),
+            LogMessage::Plain(x) => x.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn should_pop_all_in_order() {
+    let queue = queue(8, OverflowPolicy::Block);
+
+    queue.push(plain("a"));
+    queue.push(plain("b"));
+    queue.push(plain("c"));
+
+    assert_eq!(queue.len(), 3);
+    assert_eq!(pop_strings(&queue), vec!["a", "b", "c"]);
+    assert!(queue.is_empty());
+    assert_eq!(queue.accepted(), 3);
+    assert_eq!(queue.finished(), 3);
+    assert_eq!(queue.dropped(), 0);
+}
+
+#[test]
+fn should_return_empty_batch_on_timeout() {
+    let queue = queue(8, OverflowPolicy::Block);
+
+    let batch = queue.pop_all(Duration::from_millis(1)).unwrap();
+
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn should_drop_oldest() {
+    let queue = queue(2, OverflowPolicy::DropOldest);
+
+    queue.push(plain("a"));
+    queue.push(plain("b"));
+    queue.push(plain("c"));
+    queue.push(plain("d"));
+
+    assert_eq!(pop_strings(&queue), vec!["c", "d"]);
+    assert_eq!(queue.dropped(), 2);
+    assert_eq!(queue.accepted(), 4);
+    assert_eq!(queue.finished(), 4);
+}
+
+#[test]
+fn should_drop_below_level() {
+    let queue = queue(3, OverflowPolicy::DropBelow(LogLevel::Warning));
+
+    queue.push(constructed(LogLevel::Trace, "trace"));
+    queue.push(constructed(LogLevel::Warning, "warning"));
+    queue.push(plain("plain"));
+    queue.push(constructed(LogLevel::Info, "info"));
+    queue.push(constructed(LogLevel::Error, "error"));
+
+    assert_eq!(pop_strings(&queue), vec!["warning", "plain", "error"]);
+    assert_eq!(queue.dropped(), 2);
+    assert_eq!(queue.accepted(), 4);
+    assert_eq!(queue.finished(), 4);
+}
+
+#[test]
+fn should_block_until_popped() {
+    let queue = Arc::new(queue(1, OverflowPolicy::Block));
+    queue.push(plain("a"));
+
+    let thread_queue = queue.clone();
+    let handle = std::thread::spawn(move || {
+        thread_queue.push(plain("b"));
+    });
+
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+    assert_eq!(pop_strings(&queue), vec!["a"]);
+
+    handle.join().unwrap();
+    assert_eq!(pop_strings(&queue), vec!["b"]);
+    assert_eq!(queue.dropped(), 0);
+}
+
+#[test]
+fn should_block_when_nothing_is_below_level() {
+    let queue = Arc::new(queue(1, OverflowPolicy::DropBelow(LogLevel::Warning)));
+    queue.push(constructed(LogLevel::Error, "a"));
+
+    let thread_queue = queue.clone();
+    let handle = std::thread::spawn(move || {
+        thread_queue.push(constructed(LogLevel::Fatal, "b"));
+    });
+
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+    assert_eq!(pop_strings(&queue), vec!["a"]);
+
+    handle.join().unwrap();
+    assert_eq!(pop_strings(&queue), vec!["b"]);
+}
+
+#[test]
+fn should_not_block_consumer() {
+    let queue = queue(1, OverflowPolicy::Block);
+    queue.set_consumer();
+
+    queue.push(plain("a"));
+    queue.push(plain("b"));
+
+    assert_eq!(pop_strings(&queue), vec!["a"]);
+    assert_eq!(queue.dropped(), 1);
+}
+
+#[test]
+fn should_close() {
+    let queue = Arc::new(queue(1, OverflowPolicy::Block));
+    queue.push(plain("a"));
+
+    let thread_queue = queue.clone();
+    let handle = std::thread::spawn(move || {
+        thread_queue.push(plain("b"));
+    });
+
+    std::thread::sleep(Duration::from_millis(50));
+    queue.close();
+    handle.join().unwrap();
+
+    queue.push(plain("c"));
+
+    assert_eq!(pop_strings(&queue), vec!["a"]);
+    assert!(queue.pop_all(Duration::from_secs(10)).is_none());
+    assert_eq!(queue.dropped(), 2);
+}
@@ -0,0 +1,4 @@
+pub mod constructed_log_message;
+pub mod log;
+pub mod log_filter;
+pub mod log_queue;